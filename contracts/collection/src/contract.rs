@@ -0,0 +1,507 @@
+use crate::error::ContractError;
+use crate::msg::{
+    AllNftInfoResponse, ApprovalResponse, ContractInfoResponse, Cw721ReceiveMsg, ExecuteMsg,
+    InstantiateMsg, MinterResponse, NftInfoResponse, NftTransferredMsg, NumTokensResponse,
+    OwnerOfResponse, QueryMsg, TokensResponse, TransferHookResponse,
+};
+use crate::state::{
+    tokens, Approval, CollectionInfo, Metadata, TokenInfo, COLLECTION_INFO, CONTRACT_NAME,
+    CONTRACT_VERSION, DEFAULT_LIMIT, MAX_LIMIT, OPERATORS, TOKEN_COUNT,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult,
+};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Store the contract name and version
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Save contract state
+    let transfer_hook = msg
+        .transfer_hook
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    COLLECTION_INFO.save(
+        deps.storage,
+        &CollectionInfo {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: deps.api.addr_validate(&msg.minter)?,
+            transfer_hook,
+        },
+    )?;
+    TOKEN_COUNT.save(deps.storage, &0u64)?;
+
+    // return response
+    Ok(Response::new().add_attribute("method", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => execute_transfer_nft(deps, env, &info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute_send_nft(deps, env, &info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute_approve(deps, env, &info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute_revoke(deps, env, &info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute_approve_all(deps, env, &info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps, &info, operator),
+        ExecuteMsg::Mint {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        } => execute_mint(deps, &info, token_id, owner, token_uri, extension),
+        ExecuteMsg::Burn { token_id } => execute_burn(deps, &info, token_id),
+    }
+}
+
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let hook_msg = transfer_nft(deps, &env, info, &recipient, &token_id)?;
+
+    // return response
+    Ok(Response::new().add_messages(hook_msg).add_attributes(vec![
+        attr("action", "transfer_nft"),
+        attr("sender", info.sender.to_string()),
+        attr("recipient", recipient.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let hook_msg = transfer_nft(deps, &env, info, &contract, &token_id)?;
+
+    // Notify the receiving contract of the transfer
+    let receive_msg = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    }
+    .into_cosmos_msg(contract.to_string())?;
+
+    // return response
+    Ok(Response::new()
+        .add_messages(hook_msg)
+        .add_message(receive_msg)
+        .add_attributes(vec![
+            attr("action", "send_nft"),
+            attr("sender", info.sender.to_string()),
+            attr("recipient", contract.to_string()),
+            attr("token_id", token_id),
+        ]))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    // Only the owner or an operator of the owner can approve spenders
+    let mut token = tokens().load(deps.storage, &token_id)?;
+    check_can_approve(deps.as_ref(), &env, info, &token)?;
+
+    // Replace any existing approval of spender on token_id
+    token
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    token.approvals.push(Approval {
+        spender: spender.clone(),
+        expires,
+    });
+    tokens().save(deps.storage, &token_id, &token)?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve"),
+        attr("sender", info.sender.to_string()),
+        attr("spender", spender.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+
+    // Only the owner or an operator of the owner can revoke spenders
+    let mut token = tokens().load(deps.storage, &token_id)?;
+    check_can_approve(deps.as_ref(), &env, info, &token)?;
+
+    token
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    tokens().save(deps.storage, &token_id, &token)?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke"),
+        attr("sender", info.sender.to_string()),
+        attr("spender", spender.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+pub fn execute_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    OPERATORS.save(deps.storage, (&info.sender, &operator), &expires)?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_all"),
+        attr("sender", info.sender.to_string()),
+        attr("operator", operator.to_string()),
+    ]))
+}
+
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    info: &MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_all"),
+        attr("sender", info.sender.to_string()),
+        attr("operator", operator.to_string()),
+    ]))
+}
+
+pub fn execute_mint(
+    deps: DepsMut,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: Metadata,
+) -> Result<Response, ContractError> {
+    verify_caller_is_minter(deps.as_ref(), info)?;
+
+    // Ensure token_id is not already in circulation
+    if tokens().has(deps.storage, &token_id) {
+        return Err(ContractError::Claimed { token_id });
+    }
+
+    let owner = deps.api.addr_validate(&owner)?;
+    tokens().save(
+        deps.storage,
+        &token_id,
+        &TokenInfo {
+            owner: owner.clone(),
+            approvals: vec![],
+            token_uri,
+            extension,
+        },
+    )?;
+    TOKEN_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "mint"),
+        attr("minter", info.sender.to_string()),
+        attr("owner", owner.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+pub fn execute_burn(
+    deps: DepsMut,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    verify_caller_is_minter(deps.as_ref(), info)?;
+
+    // Load the token first, so burning an unknown token_id fails
+    tokens().load(deps.storage, &token_id)?;
+    tokens().remove(deps.storage, &token_id)?;
+    TOKEN_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count - 1) })?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "burn"),
+        attr("sender", info.sender.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+/// Moves token_id to recipient.
+/// Returns the message notifying the transfer hook, if any.
+fn transfer_nft(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &Addr,
+    token_id: &str,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    // Only the owner, an approved spender or an operator of the owner can transfer the token
+    let mut token = tokens().load(deps.storage, token_id)?;
+    check_can_send(deps.as_ref(), env, info, &token)?;
+
+    // Approvals do not carry over to the new owner
+    token.owner = recipient.clone();
+    token.approvals = vec![];
+    tokens().save(deps.storage, token_id, &token)?;
+
+    // Let the transfer hook follow the new owner of the token
+    let transfer_hook = COLLECTION_INFO.load(deps.storage)?.transfer_hook;
+    Ok(transfer_hook
+        .map(|hook| {
+            NftTransferredMsg {
+                token_id: token_id.to_string(),
+                owner: recipient.to_string(),
+            }
+            .into_cosmos_msg(hook.to_string())
+        })
+        .transpose()?)
+}
+
+fn verify_caller_is_minter(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let collection_info = COLLECTION_INFO.load(deps.storage)?;
+    if info.sender.ne(&collection_info.minter) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+fn is_operator(deps: Deps, env: &Env, owner: &Addr, operator: &Addr) -> StdResult<bool> {
+    Ok(OPERATORS
+        .may_load(deps.storage, (owner, operator))?
+        .map(|expires| !expires.is_expired(&env.block))
+        .unwrap_or(false))
+}
+
+fn check_can_approve(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    if info.sender.eq(&token.owner) || is_operator(deps, env, &token.owner, &info.sender)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {})
+}
+
+fn check_can_send(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    let is_approved = token
+        .approvals
+        .iter()
+        .any(|approval| approval.spender == info.sender && !approval.is_expired(&env.block));
+    if is_approved {
+        return Ok(());
+    }
+
+    check_can_approve(deps, env, info, token)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_binary(&query_owner_of(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::NumTokens {} => to_binary(&query_num_tokens(deps)?),
+        QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps)?),
+        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_binary(&AllNftInfoResponse {
+            access: query_owner_of(
+                deps,
+                env,
+                token_id.clone(),
+                include_expired.unwrap_or(false),
+            )?,
+            info: query_nft_info(deps, token_id)?,
+        }),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+        QueryMsg::TransferHook {} => to_binary(&query_transfer_hook(deps)?),
+    }
+}
+
+pub fn query_owner_of(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<OwnerOfResponse> {
+    let token = tokens().load(deps.storage, &token_id)?;
+    Ok(OwnerOfResponse {
+        owner: token.owner.to_string(),
+        approvals: token
+            .approvals
+            .into_iter()
+            .filter(|approval| include_expired || !approval.is_expired(&env.block))
+            .map(|approval| ApprovalResponse {
+                spender: approval.spender.to_string(),
+                expires: approval.expires,
+            })
+            .collect(),
+    })
+}
+
+pub fn query_num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
+    let count = TOKEN_COUNT.load(deps.storage)?;
+    Ok(NumTokensResponse { count })
+}
+
+pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let collection_info = COLLECTION_INFO.load(deps.storage)?;
+    Ok(ContractInfoResponse {
+        name: collection_info.name,
+        symbol: collection_info.symbol,
+    })
+}
+
+pub fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
+    let token = tokens().load(deps.storage, &token_id)?;
+    Ok(NftInfoResponse {
+        token_uri: token.token_uri,
+        extension: token.extension,
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let tokens = tokens()
+        .idx
+        .owner
+        .prefix(owner)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = tokens()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_minter(deps: Deps) -> StdResult<MinterResponse> {
+    let collection_info = COLLECTION_INFO.load(deps.storage)?;
+    Ok(MinterResponse {
+        minter: Some(collection_info.minter.to_string()),
+    })
+}
+
+pub fn query_transfer_hook(deps: Deps) -> StdResult<TransferHookResponse> {
+    let collection_info = COLLECTION_INFO.load(deps.storage)?;
+    Ok(TransferHookResponse {
+        transfer_hook: collection_info.transfer_hook.map(|hook| hook.to_string()),
+    })
+}
@@ -0,0 +1,998 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        msg::{
+            AllNftInfoResponse, ContractInfoResponse, Cw721ReceiveMsg, ExecuteMsg, InstantiateMsg,
+            MinterResponse, NftInfoResponse, NftTransferredMsg, NumTokensResponse, OwnerOfResponse,
+            QueryMsg, TokensResponse, TransferHookResponse,
+        },
+        state::{Metadata, Trait},
+    };
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+        StdResult,
+    };
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+    use cw_utils::Expiration;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    const MINTER: &str = "sudomod";
+    const USER: &str = "user";
+    const BUYER: &str = "buyer";
+    const OPERATOR: &str = "operator";
+    const TOKEN_ID: &str = "vault/1";
+
+    // Mock contract receiving tokens through SendNft, and acting as a transfer hook
+    const RECEIVED: Item<Cw721ReceiveMsg> = Item::new("RECEIVED");
+    const TRANSFERRED: Item<NftTransferredMsg> = Item::new("TRANSFERRED");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    enum MockReceiverExecuteMsg {
+        ReceiveNft(Cw721ReceiveMsg),
+        NftTransferred(NftTransferredMsg),
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    enum MockReceiverQueryMsg {
+        Received {},
+        Transferred {},
+    }
+
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_router, _api, _storage| {})
+    }
+
+    fn contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    fn receiver_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            |deps: DepsMut,
+             _env: Env,
+             _info: MessageInfo,
+             msg: MockReceiverExecuteMsg|
+             -> StdResult<Response> {
+                match msg {
+                    MockReceiverExecuteMsg::ReceiveNft(receive_msg) => {
+                        if receive_msg.msg.as_slice() == b"reject" {
+                            return Err(StdError::generic_err("token rejected"));
+                        }
+                        RECEIVED.save(deps.storage, &receive_msg)?;
+                    }
+                    MockReceiverExecuteMsg::NftTransferred(transferred_msg) => {
+                        TRANSFERRED.save(deps.storage, &transferred_msg)?;
+                    }
+                }
+                Ok(Response::default())
+            },
+            |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| -> StdResult<Response> {
+                Ok(Response::default())
+            },
+            |deps: Deps, _env: Env, msg: MockReceiverQueryMsg| -> StdResult<Binary> {
+                match msg {
+                    MockReceiverQueryMsg::Received {} => to_binary(&RECEIVED.load(deps.storage)?),
+                    MockReceiverQueryMsg::Transferred {} => {
+                        to_binary(&TRANSFERRED.load(deps.storage)?)
+                    }
+                }
+            },
+        );
+        Box::new(contract)
+    }
+
+    fn instantiate_receiver(app: &mut App) -> Addr {
+        let code_id = app.store_code(receiver_contract_template());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(USER),
+            &Empty {},
+            &[],
+            "receiver",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn instantiate_collection(app: &mut App) -> Addr {
+        instantiate_collection_with_hook(app, None)
+    }
+
+    fn instantiate_collection_with_hook(app: &mut App, transfer_hook: Option<&Addr>) -> Addr {
+        let code_id = app.store_code(contract_template());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(MINTER),
+            &InstantiateMsg {
+                name: "SudoStake Lender Positions".to_string(),
+                symbol: "LRO".to_string(),
+                minter: MINTER.to_string(),
+                transfer_hook: transfer_hook.map(|hook| hook.to_string()),
+            },
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn mint_msg(owner: &str) -> ExecuteMsg {
+        mint_token_msg(TOKEN_ID, owner)
+    }
+
+    fn mint_token_msg(token_id: &str, owner: &str) -> ExecuteMsg {
+        ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: Metadata {
+                attributes: vec![Trait {
+                    trait_type: "vault_address".to_string(),
+                    value: "vault".to_string(),
+                }],
+            },
+        }
+    }
+
+    fn get_owner_of(app: &mut App, collection: &Addr) -> OwnerOfResponse {
+        get_owner_of_including_expired(app, collection, None)
+    }
+
+    fn get_owner_of_including_expired(
+        app: &mut App,
+        collection: &Addr,
+        include_expired: Option<bool>,
+    ) -> OwnerOfResponse {
+        let msg = QueryMsg::OwnerOf {
+            token_id: TOKEN_ID.to_string(),
+            include_expired,
+        };
+        let result: OwnerOfResponse = app.wrap().query_wasm_smart(collection, &msg).unwrap();
+
+        result
+    }
+
+    fn get_num_tokens(app: &mut App, collection: &Addr) -> u64 {
+        let msg = QueryMsg::NumTokens {};
+        let result: NumTokensResponse = app.wrap().query_wasm_smart(collection, &msg).unwrap();
+
+        result.count
+    }
+
+    fn transfer_msg(recipient: &str) -> ExecuteMsg {
+        ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: TOKEN_ID.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mint() {
+        // Step 1
+        // Get collection instance
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the minter mints a token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Mint TOKEN_ID to USER, and verify its owner and metadata
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, USER.to_string());
+        assert_eq!(get_num_tokens(&mut app, &collection), 1);
+        let nft_info: NftInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &QueryMsg::NftInfo {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(nft_info.extension.attributes[0].value, "vault".to_string());
+        let tokens: TokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &QueryMsg::Tokens {
+                    owner: USER.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(tokens.tokens, vec![TOKEN_ID.to_string()]);
+
+        // Step 4
+        // Test error case ContractError::Claimed {}
+        // when minting a token_id already in circulation
+        // ------------------------------------------------------------------------------
+        app.execute_contract(Addr::unchecked(MINTER), collection, &mint_msg(BUYER), &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_transfer_nft() {
+        // Step 1
+        // Get collection instance and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the owner transfers the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Approve BUYER on the token, and transfer it to BUYER as the spender
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &ExecuteMsg::Approve {
+                spender: BUYER.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_owner_of(&mut app, &collection).approvals.len(), 1);
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap();
+
+        // Step 4
+        // Verify that BUYER owns the token, and the approvals were cleared
+        // ------------------------------------------------------------------------------
+        let owner_of = get_owner_of(&mut app, &collection);
+        assert_eq!(owner_of.owner, BUYER.to_string());
+        assert!(owner_of.approvals.is_empty());
+
+        // Step 5
+        // Test error case ContractError::Expired {}
+        // when approving an operator with an expired approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &ExecuteMsg::ApproveAll {
+                operator: OPERATOR.to_string(),
+                expires: Some(Expiration::AtHeight(1)),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 6
+        // Approve OPERATOR on every token of BUYER, and transfer the token back to USER
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &ExecuteMsg::ApproveAll {
+                operator: OPERATOR.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &transfer_msg(USER),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, USER.to_string());
+
+        // Step 7
+        // Test error case ContractError::Unauthorized {}
+        // when OPERATOR transfers a token that BUYER no longer owns
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection,
+            &transfer_msg(OPERATOR),
+            &[],
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_burn() {
+        // Step 1
+        // Get collection instance and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // when the owner burns the token
+        // ------------------------------------------------------------------------------
+        let burn_msg = ExecuteMsg::Burn {
+            token_id: TOKEN_ID.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(USER), collection.clone(), &burn_msg, &[])
+            .unwrap_err();
+
+        // Step 3
+        // Burn the token as the minter, and verify it is no longer in circulation
+        // ------------------------------------------------------------------------------
+        app.execute_contract(Addr::unchecked(MINTER), collection.clone(), &burn_msg, &[])
+            .unwrap();
+        assert_eq!(get_num_tokens(&mut app, &collection), 0);
+        app.wrap()
+            .query_wasm_smart::<OwnerOfResponse>(
+                &collection,
+                &QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Mint TOKEN_ID again once the previous token is burned
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(BUYER),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, BUYER.to_string());
+
+        // Step 5
+        // Test error case StdError::NotFound {}
+        // when burning an unknown token_id
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &ExecuteMsg::Burn {
+                token_id: "vault/2".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(get_num_tokens(&mut app, &collection), 1);
+    }
+
+    #[test]
+    fn test_approve_and_revoke() {
+        // Step 1
+        // Get collection instance and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the owner or an operator approves a spender
+        // ------------------------------------------------------------------------------
+        let expires_at = app.block_info().height + 5;
+        let approve_msg = |expires: Option<Expiration>| ExecuteMsg::Approve {
+            spender: BUYER.to_string(),
+            token_id: TOKEN_ID.to_string(),
+            expires,
+        };
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &approve_msg(Some(Expiration::AtHeight(expires_at))),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Test error case ContractError::Expired {}
+        // when approving a spender with an expired approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &approve_msg(Some(Expiration::AtHeight(1))),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 4
+        // Approve BUYER until expires_at, and verify the approval is listed
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &approve_msg(Some(Expiration::AtHeight(expires_at))),
+            &[],
+        )
+        .unwrap();
+        let owner_of = get_owner_of(&mut app, &collection);
+        assert_eq!(owner_of.approvals.len(), 1);
+        assert_eq!(owner_of.approvals[0].spender, BUYER.to_string());
+        assert_eq!(
+            owner_of.approvals[0].expires,
+            Expiration::AtHeight(expires_at)
+        );
+
+        // Step 5
+        // Approving BUYER again replaces the previous approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &approve_msg(Some(Expiration::AtHeight(expires_at))),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_owner_of(&mut app, &collection).approvals.len(), 1);
+
+        // Step 6
+        // Move past expires_at, and verify the expired approval is only
+        // listed when include_expired is set
+        // ------------------------------------------------------------------------------
+        app.update_block(|block| block.height = expires_at);
+        assert!(get_owner_of(&mut app, &collection).approvals.is_empty());
+        assert_eq!(
+            get_owner_of_including_expired(&mut app, &collection, Some(true))
+                .approvals
+                .len(),
+            1
+        );
+
+        // Step 7
+        // Test error case ContractError::Unauthorized {}
+        // when BUYER transfers the token with an expired approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 8
+        // Approve BUYER without an expiration, then test error case
+        // ContractError::Unauthorized {} when BUYER revokes their own approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &approve_msg(None),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_owner_of(&mut app, &collection).approvals[0].expires,
+            Expiration::Never {}
+        );
+        let revoke_msg = ExecuteMsg::Revoke {
+            spender: BUYER.to_string(),
+            token_id: TOKEN_ID.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(BUYER), collection.clone(), &revoke_msg, &[])
+            .unwrap_err();
+
+        // Step 9
+        // Revoke BUYER as the owner, and verify BUYER can no longer transfer the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(Addr::unchecked(USER), collection.clone(), &revoke_msg, &[])
+            .unwrap();
+        assert!(
+            get_owner_of_including_expired(&mut app, &collection, Some(true))
+                .approvals
+                .is_empty()
+        );
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, USER.to_string());
+
+        // Step 10
+        // Test error case StdError::NotFound {}
+        // when approving a spender on an unknown token_id
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection,
+            &ExecuteMsg::Approve {
+                spender: BUYER.to_string(),
+                token_id: "vault/2".to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_approve_all_and_revoke_all() {
+        // Step 1
+        // Get collection instance and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Approve OPERATOR on every token of USER until expires_at, and have
+        // OPERATOR approve BUYER as a spender on behalf of USER
+        // ------------------------------------------------------------------------------
+        let expires_at = app.block_info().height + 5;
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &ExecuteMsg::ApproveAll {
+                operator: OPERATOR.to_string(),
+                expires: Some(Expiration::AtHeight(expires_at)),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &ExecuteMsg::Approve {
+                spender: BUYER.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_owner_of(&mut app, &collection).approvals[0].spender,
+            BUYER.to_string()
+        );
+
+        // Step 3
+        // OPERATOR can also revoke spenders on behalf of USER
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &ExecuteMsg::Revoke {
+                spender: BUYER.to_string(),
+                token_id: TOKEN_ID.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(get_owner_of(&mut app, &collection).approvals.is_empty());
+
+        // Step 4
+        // Move past expires_at, then test error case ContractError::Unauthorized {}
+        // when OPERATOR transfers the token with an expired operator approval
+        // ------------------------------------------------------------------------------
+        app.update_block(|block| block.height = expires_at);
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &transfer_msg(OPERATOR),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 5
+        // Approve OPERATOR again without an expiration, then revoke it
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &ExecuteMsg::ApproveAll {
+                operator: OPERATOR.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &ExecuteMsg::RevokeAll {
+                operator: OPERATOR.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 6
+        // Test error case ContractError::Unauthorized {}
+        // when a revoked OPERATOR transfers or approves on the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &transfer_msg(OPERATOR),
+            &[],
+        )
+        .unwrap_err();
+        app.execute_contract(
+            Addr::unchecked(OPERATOR),
+            collection.clone(),
+            &ExecuteMsg::Approve {
+                spender: OPERATOR.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, USER.to_string());
+
+        // Step 7
+        // Revoking an operator that was never approved is a no-op
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection,
+            &ExecuteMsg::RevokeAll {
+                operator: BUYER.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_send_nft() {
+        // Step 1
+        // Get collection and receiver instances, and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        let receiver = instantiate_receiver(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+        let send_msg = |msg: &[u8]| ExecuteMsg::SendNft {
+            contract: receiver.to_string(),
+            token_id: TOKEN_ID.to_string(),
+            msg: Binary::from(msg),
+        };
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the owner sends the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection.clone(),
+            &send_msg(b"hello"),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Verify the transfer is rolled back when the receiver rejects the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &send_msg(b"reject"),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(get_owner_of(&mut app, &collection).owner, USER.to_string());
+
+        // Step 4
+        // Send the token to the receiver, and verify it got the ReceiveNft message
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &send_msg(b"hello"),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_owner_of(&mut app, &collection).owner,
+            receiver.to_string()
+        );
+        let received: Cw721ReceiveMsg = app
+            .wrap()
+            .query_wasm_smart(&receiver, &MockReceiverQueryMsg::Received {})
+            .unwrap();
+        assert_eq!(
+            received,
+            Cw721ReceiveMsg {
+                sender: USER.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: Binary::from(b"hello"),
+            }
+        );
+
+        // Step 5
+        // Test error case ContractError::Unauthorized {}
+        // when USER sends the token they no longer own
+        // ------------------------------------------------------------------------------
+        app.execute_contract(Addr::unchecked(USER), collection, &send_msg(b"hello"), &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_queries() {
+        // Step 1
+        // Get collection instance, and verify the contract info and minter
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let collection = instantiate_collection(&mut app);
+        let contract_info: ContractInfoResponse = app
+            .wrap()
+            .query_wasm_smart(&collection, &QueryMsg::ContractInfo {})
+            .unwrap();
+        assert_eq!(
+            contract_info,
+            ContractInfoResponse {
+                name: "SudoStake Lender Positions".to_string(),
+                symbol: "LRO".to_string(),
+            }
+        );
+        let minter: MinterResponse = app
+            .wrap()
+            .query_wasm_smart(&collection, &QueryMsg::Minter {})
+            .unwrap();
+        assert_eq!(minter.minter, Some(MINTER.to_string()));
+        let transfer_hook: TransferHookResponse = app
+            .wrap()
+            .query_wasm_smart(&collection, &QueryMsg::TransferHook {})
+            .unwrap();
+        assert_eq!(transfer_hook.transfer_hook, None);
+        assert_eq!(get_num_tokens(&mut app, &collection), 0);
+
+        // Step 2
+        // Mint three tokens to USER and one token to BUYER
+        // ------------------------------------------------------------------------------
+        for (token_id, owner) in [
+            ("vault/1", USER),
+            ("vault/2", BUYER),
+            ("vault/3", USER),
+            ("vault/4", USER),
+        ] {
+            app.execute_contract(
+                Addr::unchecked(MINTER),
+                collection.clone(),
+                &mint_token_msg(token_id, owner),
+                &[],
+            )
+            .unwrap();
+        }
+        assert_eq!(get_num_tokens(&mut app, &collection), 4);
+
+        // Step 3
+        // Page through the tokens owned by USER
+        // ------------------------------------------------------------------------------
+        let tokens_of = |app: &App, owner: &str, start_after: Option<&str>| -> Vec<String> {
+            let tokens: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &collection,
+                    &QueryMsg::Tokens {
+                        owner: owner.to_string(),
+                        start_after: start_after.map(|token_id| token_id.to_string()),
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            tokens.tokens
+        };
+        assert_eq!(tokens_of(&app, USER, None), vec!["vault/1", "vault/3"]);
+        assert_eq!(tokens_of(&app, USER, Some("vault/3")), vec!["vault/4"]);
+        assert_eq!(tokens_of(&app, BUYER, None), vec!["vault/2"]);
+        assert!(tokens_of(&app, OPERATOR, None).is_empty());
+
+        // Step 4
+        // Page through every token of the collection
+        // ------------------------------------------------------------------------------
+        let all_tokens = |app: &App, start_after: Option<&str>| -> Vec<String> {
+            let tokens: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &collection,
+                    &QueryMsg::AllTokens {
+                        start_after: start_after.map(|token_id| token_id.to_string()),
+                        limit: Some(3),
+                    },
+                )
+                .unwrap();
+            tokens.tokens
+        };
+        assert_eq!(
+            all_tokens(&app, None),
+            vec!["vault/1", "vault/2", "vault/3"]
+        );
+        assert_eq!(all_tokens(&app, Some("vault/3")), vec!["vault/4"]);
+
+        // Step 5
+        // Transfer vault/1 to BUYER, and verify the owner index follows the token
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(tokens_of(&app, USER, None), vec!["vault/3", "vault/4"]);
+        assert_eq!(tokens_of(&app, BUYER, None), vec!["vault/1", "vault/2"]);
+
+        // Step 6
+        // Verify AllNftInfo returns both the owner and the metadata of the token
+        // ------------------------------------------------------------------------------
+        let all_nft_info: AllNftInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &QueryMsg::AllNftInfo {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(all_nft_info.access.owner, BUYER.to_string());
+        assert!(all_nft_info.access.approvals.is_empty());
+        assert_eq!(all_nft_info.info.token_uri, None);
+        assert_eq!(
+            all_nft_info.info.extension.attributes[0].trait_type,
+            "vault_address".to_string()
+        );
+
+        // Step 7
+        // Test error case StdError::NotFound {}
+        // when querying an unknown token_id
+        // ------------------------------------------------------------------------------
+        app.wrap()
+            .query_wasm_smart::<NftInfoResponse>(
+                &collection,
+                &QueryMsg::NftInfo {
+                    token_id: "vault/5".to_string(),
+                },
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_transfer_hook() {
+        // Step 1
+        // Get a collection instance notifying the receiver of every transfer,
+        // and mint TOKEN_ID to USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let receiver = instantiate_receiver(&mut app);
+        let collection = instantiate_collection_with_hook(&mut app, Some(&receiver));
+        let transfer_hook: TransferHookResponse = app
+            .wrap()
+            .query_wasm_smart(&collection, &QueryMsg::TransferHook {})
+            .unwrap();
+        assert_eq!(transfer_hook.transfer_hook, Some(receiver.to_string()));
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg(USER),
+            &[],
+        )
+        .unwrap();
+        let get_transferred = |app: &App| -> NftTransferredMsg {
+            app.wrap()
+                .query_wasm_smart(&receiver, &MockReceiverQueryMsg::Transferred {})
+                .unwrap()
+        };
+
+        // Step 2
+        // Transfer the token to BUYER, and verify the hook was notified of the new owner
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection.clone(),
+            &transfer_msg(BUYER),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_transferred(&app),
+            NftTransferredMsg {
+                token_id: TOKEN_ID.to_string(),
+                owner: BUYER.to_string(),
+            }
+        );
+
+        // Step 3
+        // Send the token to the receiver, and verify the hook was notified of the new owner
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            collection,
+            &ExecuteMsg::SendNft {
+                contract: receiver.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: Binary::from(b"hello"),
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_transferred(&app),
+            NftTransferredMsg {
+                token_id: TOKEN_ID.to_string(),
+                owner: receiver.to_string(),
+            }
+        );
+    }
+}
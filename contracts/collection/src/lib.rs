@@ -0,0 +1,6 @@
+pub mod contract;
+mod error;
+pub mod integration_test;
+pub mod msg;
+pub mod state;
+pub use crate::error::ContractError;
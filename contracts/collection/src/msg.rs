@@ -0,0 +1,204 @@
+use crate::state::Metadata;
+use cosmwasm_std::{to_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    /// Address allowed to mint and burn tokens, usually the sudomod contract
+    pub minter: String,
+    /// Contract notified through NftTransferred whenever a token changes owner,
+    /// usually the sudomod contract
+    pub transfer_hook: Option<String>,
+}
+
+/// Follows the cw721 spec, so tokens can be listed on any NFT marketplace
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Transfers token_id to recipient
+    TransferNft { recipient: String, token_id: String },
+
+    /// Transfers token_id to contract and triggers its ReceiveNft handler
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+
+    /// Allows spender to transfer or send token_id until expires
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+
+    /// Removes the approval of spender on token_id
+    Revoke { spender: String, token_id: String },
+
+    /// Allows operator to transfer or send every token of the caller until expires
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+
+    /// Removes the approval of operator on every token of the caller
+    RevokeAll { operator: String },
+
+    /// Allows the minter to mint token_id to owner
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Metadata,
+    },
+
+    /// Allows the minter to burn token_id
+    Burn { token_id: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns OwnerOfResponse
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    /// Returns NumTokensResponse
+    NumTokens {},
+
+    /// Returns ContractInfoResponse
+    ContractInfo {},
+
+    /// Returns NftInfoResponse
+    NftInfo { token_id: String },
+
+    /// Returns AllNftInfoResponse
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    /// Returns TokensResponse, listing the tokens owned by owner
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns TokensResponse, listing every token in the collection
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns MinterResponse
+    Minter {},
+
+    /// Returns TransferHookResponse
+    TransferHook {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalResponse {
+    pub spender: String,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerOfResponse {
+    pub owner: String,
+    pub approvals: Vec<ApprovalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: Metadata,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllNftInfoResponse {
+    pub access: OwnerOfResponse,
+    pub info: NftInfoResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferHookResponse {
+    pub transfer_hook: Option<String>,
+}
+
+/// Sent to the contract receiving a token through SendNft
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&ReceiverExecuteMsg::ReceiveNft(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+/// Sent to the transfer hook of the collection whenever token_id changes owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftTransferredMsg {
+    pub token_id: String,
+    pub owner: String,
+}
+
+impl NftTransferredMsg {
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&TransferHookExecuteMsg::NftTransferred(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum TransferHookExecuteMsg {
+    NftTransferred(NftTransferredMsg),
+}
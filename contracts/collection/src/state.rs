@@ -0,0 +1,89 @@
+use cosmwasm_std::{Addr, BlockInfo};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionInfo {
+    pub name: String,
+    pub symbol: String,
+    /// Only the minter can mint and burn tokens, as they represent positions
+    /// tracked by other contracts
+    pub minter: Addr,
+    /// Contract notified whenever a token changes owner
+    #[serde(default)]
+    pub transfer_hook: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Approval {
+    pub spender: Addr,
+    pub expires: Expiration,
+}
+
+impl Approval {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+/// On-chain metadata of a token
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Metadata {
+    pub attributes: Vec<Trait>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInfo {
+    pub owner: Addr,
+    pub approvals: Vec<Approval>,
+    pub token_uri: Option<String>,
+    pub extension: Metadata,
+}
+
+// contract info
+pub const CONTRACT_NAME: &str = "collection";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Limits for the range queries on this contract
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 100;
+
+// Stores the collection info set during initialization of the contract
+pub const COLLECTION_INFO: Item<CollectionInfo> = Item::new("COLLECTION_INFO");
+
+// Keeps count of the tokens in circulation
+pub const TOKEN_COUNT: Item<u64> = Item::new("TOKEN_COUNT");
+
+// Keeps track of the operators approved by an owner for all of their tokens
+pub const OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("OPERATORS");
+
+// Define the indexes for tokens
+pub struct TokenIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, TokenInfo, String>,
+}
+
+// Implement IndexList for TokenIndexes
+impl<'a> IndexList<TokenInfo> for TokenIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+        let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+// Keeps track of a mapping between the token_id : TokenInfo
+pub fn tokens<'a>() -> IndexedMap<'a, &'a str, TokenInfo, TokenIndexes<'a>> {
+    IndexedMap::new(
+        "TOKENS",
+        TokenIndexes {
+            owner: MultiIndex::new(|_pk, d| d.owner.clone(), "TOKENS", "TOKENS__owner"),
+        },
+    )
+}
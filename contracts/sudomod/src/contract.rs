@@ -1,13 +1,22 @@
 use crate::error::ContractError;
 use crate::helpers;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, VaultCodeListResponse};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, IsVaultResponse, QueryMsg, VaultCodeListResponse, VaultListResponse,
+};
+use crate::state::{
+    vault_list, Config, VaultCodeInfo, VaultInfo, VaultUpgradeApproval, CONFIG,
+    INSTANTIATE_VAULT_REPLY_ID, VAULT_CODE_LIST, VAULT_CODE_SEQ, VAULT_INSTANTIATION_SEQ,
+    VAULT_UPGRADE_APPROVALS,
+};
 use crate::state::{
-    Config, VaultCodeInfo, CONFIG, VAULT_CODE_LIST, VAULT_CODE_SEQ, VAULT_INSTANTIATION_SEQ,
+    ProtocolParams, CONTRACT_NAME, CONTRACT_VERSION, DEFAULT_LIQUIDITY_COMMISSION,
+    MAX_LIQUIDITY_COMMISSION, PARAMS,
 };
-use crate::state::{CONTRACT_NAME, CONTRACT_VERSION, DEFAULT_LIMIT, MAX_LIMIT};
+use collection::msg::{ExecuteMsg as CollectionExecuteMsg, NftTransferredMsg};
+use collection::state::{Metadata, Trait};
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Addr, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order,
-    ReplyOn, Response, StdResult, SubMsg, WasmMsg,
+    attr, entry_point, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Order, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
 };
 use cw_storage_plus::Bound;
 use std::ops::Add;
@@ -15,7 +24,7 @@ use std::ops::Add;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -29,9 +38,16 @@ pub fn instantiate(
             owner: info.sender,
             vault_code_info_updated_at: None,
             vault_creation_fee: None,
+            liquidity_commission: DEFAULT_LIQUIDITY_COMMISSION,
+            fee_collector: env.contract.address,
+            lender_position_collection: None,
+            vault_ownership_collection: None,
         },
     )?;
 
+    // Set the default protocol params
+    PARAMS.save(deps.storage, &ProtocolParams::default())?;
+
     // Set VAULT_CODE_SEQ to 0
     VAULT_CODE_SEQ.save(deps.storage, &0u64)?;
 
@@ -56,7 +72,35 @@ pub fn execute(
         ExecuteMsg::SetVaultCreationFee { amount } => {
             execute_set_vault_creation_fee(deps, &info, amount)
         }
-        ExecuteMsg::MintVault {} => execute_mint_vault(deps, &info),
+        ExecuteMsg::SetLiquidityCommission { commission } => {
+            execute_set_liquidity_commission(deps, &info, commission)
+        }
+        ExecuteMsg::SetFeeCollector { address } => execute_set_fee_collector(deps, &info, address),
+        ExecuteMsg::SetLenderPositionCollection { address } => {
+            execute_set_lender_position_collection(deps, env, &info, address)
+        }
+        ExecuteMsg::SetVaultOwnershipCollection { address } => {
+            execute_set_vault_ownership_collection(deps, env, &info, address)
+        }
+        ExecuteMsg::UpdateParams { params } => execute_update_params(deps, &info, params),
+        ExecuteMsg::MintVault {} => execute_mint_vault(deps, env, &info),
+        ExecuteMsg::UpgradeVault { vault_address } => {
+            execute_upgrade_vault(deps, env, &info, vault_address)
+        }
+        ExecuteMsg::ApproveVaultUpgrade { vault_address } => {
+            execute_approve_vault_upgrade(deps, &info, vault_address)
+        }
+        ExecuteMsg::VaultOwnerChanged { new_owner } => {
+            execute_vault_owner_changed(deps, &info, new_owner)
+        }
+        ExecuteMsg::NftTransferred(msg) => execute_nft_transferred(deps, &info, msg),
+        ExecuteMsg::LenderPositionOpened { token_id, lender } => {
+            execute_lender_position_opened(deps, &info, token_id, lender)
+        }
+        ExecuteMsg::LenderPositionClosed {
+            collection,
+            token_id,
+        } => execute_lender_position_closed(deps, &info, collection, token_id),
         ExecuteMsg::WithdrawBalance { to_address, funds } => {
             execute_withdraw_balance(deps, env, &info, to_address, funds)
         }
@@ -123,14 +167,128 @@ pub fn execute_set_vault_creation_fee(
         .add_attribute("amount", amount.to_string()))
 }
 
-pub fn execute_mint_vault(deps: DepsMut, info: &MessageInfo) -> Result<Response, ContractError> {
-    // Verify that VAULT_CODE_SEQ for setting vault_code_id is greater than 0
-    // Which indicates there is a vault_code_id already set
-    let vault_code_seq_id = VAULT_CODE_SEQ.load(deps.storage)?;
-    if vault_code_seq_id.eq(&0u64) {
-        return Err(ContractError::VaultCodeIdNotSet {});
+pub fn execute_set_liquidity_commission(
+    deps: DepsMut,
+    info: &MessageInfo,
+    commission: Decimal,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_is_owner(info, &deps)?;
+
+    // Verify that the commission does not exceed MAX_LIQUIDITY_COMMISSION
+    if commission > MAX_LIQUIDITY_COMMISSION {
+        return Err(ContractError::LiquidityCommissionTooHigh {
+            commission,
+            max: MAX_LIQUIDITY_COMMISSION,
+        });
     }
 
+    // Update liquidity_commission
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.liquidity_commission = commission;
+        Ok(data)
+    })?;
+
+    // return response
+    Ok(Response::new()
+        .add_attribute("method", "set_liquidity_commission")
+        .add_attribute("commission", commission.to_string()))
+}
+
+pub fn execute_set_fee_collector(
+    deps: DepsMut,
+    info: &MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_is_owner(info, &deps)?;
+
+    // Validate the fee_collector address
+    let fee_collector = deps.api.addr_validate(&address)?;
+
+    // Update fee_collector
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.fee_collector = fee_collector.clone();
+        Ok(data)
+    })?;
+
+    // return response
+    Ok(Response::new()
+        .add_attribute("method", "set_fee_collector")
+        .add_attribute("address", fee_collector.to_string()))
+}
+
+pub fn execute_set_lender_position_collection(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_is_owner(info, &deps)?;
+
+    // Verify that this contract can mint and burn tokens of the collection
+    let collection = deps.api.addr_validate(&address)?;
+    helpers::verify_contract_is_collection_minter(&deps, &env, &collection)?;
+
+    // Update lender_position_collection
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.lender_position_collection = Some(collection.clone());
+        Ok(data)
+    })?;
+
+    // return response
+    Ok(Response::new()
+        .add_attribute("method", "set_lender_position_collection")
+        .add_attribute("address", collection.to_string()))
+}
+
+pub fn execute_set_vault_ownership_collection(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_is_owner(info, &deps)?;
+
+    // Verify that this contract can mint tokens of the collection,
+    // and is notified whenever they change owner
+    let collection = deps.api.addr_validate(&address)?;
+    helpers::verify_contract_is_collection_minter(&deps, &env, &collection)?;
+    helpers::verify_contract_is_collection_transfer_hook(&deps, &env, &collection)?;
+
+    // Update vault_ownership_collection
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.vault_ownership_collection = Some(collection.clone());
+        Ok(data)
+    })?;
+
+    // return response
+    Ok(Response::new()
+        .add_attribute("method", "set_vault_ownership_collection")
+        .add_attribute("address", collection.to_string()))
+}
+
+pub fn execute_update_params(
+    deps: DepsMut,
+    info: &MessageInfo,
+    params: ProtocolParams,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_is_owner(info, &deps)?;
+
+    // Validate and save the new params
+    helpers::validate_protocol_params(&params)?;
+    PARAMS.save(deps.storage, &params)?;
+
+    // return response
+    Ok(Response::new().add_attribute("method", "update_params"))
+}
+
+pub fn execute_mint_vault(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+) -> Result<Response, ContractError> {
+    // Get the latest vault code info, which fails if no vault_code_id is set yet
+    let latest_code_info = helpers::get_latest_vault_code_info(deps.storage)?;
+
     // Verify that caller sends the correct vault_creation_fee
     // If no vault creation fee is set, it implies that this vault
     // instance will be created for free.
@@ -140,19 +298,21 @@ pub fn execute_mint_vault(deps: DepsMut, info: &MessageInfo) -> Result<Response,
     let vault_instance_seq_id = VAULT_INSTANTIATION_SEQ
         .update::<_, cosmwasm_std::StdError>(deps.storage, |id| Ok(id.add(1)))?;
 
-    // Add submessage to create a new vault
-    let latest_code_info = VAULT_CODE_LIST.load(deps.storage, vault_code_seq_id)?;
+    // Add submessage to create a new vault,
+    // with this contract as admin so that vault owners can upgrade through UpgradeVault
     let instantiate_vault_sub_msg = SubMsg {
         gas_limit: None,
-        id: 0u64,
-        reply_on: ReplyOn::Never,
+        id: INSTANTIATE_VAULT_REPLY_ID,
+        reply_on: ReplyOn::Success,
         msg: WasmMsg::Instantiate {
-            admin: None,
+            admin: Some(env.contract.address.to_string()),
             code_id: latest_code_info.code_id,
             msg: to_binary(&vault_contract::msg::InstantiateMsg {
                 owner_address: info.sender.to_string(),
+                factory_address: env.contract.address.to_string(),
                 from_code_id: latest_code_info.code_id,
                 index_number: vault_instance_seq_id,
+                unbonding_period: PARAMS.load(deps.storage)?.unbonding_period,
             })?,
             funds: vec![],
             label: format!("Vault Number {:?}", vault_instance_seq_id),
@@ -168,6 +328,253 @@ pub fn execute_mint_vault(deps: DepsMut, info: &MessageInfo) -> Result<Response,
         .add_attribute("vault_instance_seq_id", vault_instance_seq_id.to_string()))
 }
 
+pub fn execute_upgrade_vault(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    vault_address: String,
+) -> Result<Response, ContractError> {
+    let vault_address = deps.api.addr_validate(&vault_address)?;
+
+    // Verify that the vault is managed by this contract
+    let contract_info = deps
+        .querier
+        .query_wasm_contract_info(vault_address.clone())?;
+    if contract_info
+        .admin
+        .ne(&Some(env.contract.address.to_string()))
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Verify that the caller is the current owner of the vault
+    let vault_info = helpers::query_vault_info(&deps, &vault_address)?;
+    if info.sender.ne(&vault_info.config.owner) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Verify that the vault is not already running the latest vault_code_id
+    let latest_code_info = helpers::get_latest_vault_code_info(deps.storage)?;
+    if contract_info.code_id.eq(&latest_code_info.code_id) {
+        return Err(ContractError::VaultAlreadyUpToDate {
+            code_id: latest_code_info.code_id,
+        });
+    }
+
+    // When there is an active lender on the vault, they must have approved
+    // the upgrade to the latest vault_code_id
+    if let Some(vault_contract::types::ActiveOption {
+        lender: Some(lender),
+        ..
+    }) = vault_info.liquidity_request
+    {
+        let approved = VAULT_UPGRADE_APPROVALS
+            .may_load(deps.storage, &vault_address)?
+            .is_some_and(|approval| {
+                approval.lender.eq(&lender) && approval.code_id.eq(&latest_code_info.code_id)
+            });
+        if !approved {
+            return Err(ContractError::VaultUpgradeNotApprovedByLender {
+                lender: lender.to_string(),
+            });
+        }
+    }
+    VAULT_UPGRADE_APPROVALS.remove(deps.storage, &vault_address);
+
+    // Add message to migrate the vault to the latest vault_code_id
+    let migrate_msg = WasmMsg::Migrate {
+        contract_addr: vault_address.to_string(),
+        new_code_id: latest_code_info.code_id,
+        msg: to_binary(&vault_contract::msg::MigrateMsg {})?,
+    };
+
+    // return response
+    Ok(Response::new()
+        .add_message(migrate_msg)
+        .add_attributes(vec![
+            attr("method", "upgrade_vault"),
+            attr("vault_address", vault_address.to_string()),
+            attr("from_code_id", contract_info.code_id.to_string()),
+            attr("vault_code_id", latest_code_info.code_id.to_string()),
+        ]))
+}
+
+pub fn execute_approve_vault_upgrade(
+    deps: DepsMut,
+    info: &MessageInfo,
+    vault_address: String,
+) -> Result<Response, ContractError> {
+    let vault_address = deps.api.addr_validate(&vault_address)?;
+
+    // Verify that the caller is the active lender on the vault
+    let vault_info = helpers::query_vault_info(&deps, &vault_address)?;
+    match vault_info.liquidity_request {
+        Some(vault_contract::types::ActiveOption {
+            lender: Some(lender),
+            ..
+        }) if lender.eq(&info.sender) => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+
+    // A lender holding only a share of the option can not approve for the other lenders
+    let lenders = helpers::query_vault_lender_shares(&deps, &vault_address)?
+        .shares
+        .len() as u32;
+    if lenders > 1 {
+        return Err(ContractError::LenderPositionIsSyndicated { lenders });
+    }
+
+    // Record the approval for the latest vault_code_id
+    let latest_code_info = helpers::get_latest_vault_code_info(deps.storage)?;
+    VAULT_UPGRADE_APPROVALS.save(
+        deps.storage,
+        &vault_address,
+        &VaultUpgradeApproval {
+            lender: info.sender.clone(),
+            code_id: latest_code_info.code_id,
+        },
+    )?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "approve_vault_upgrade"),
+        attr("vault_address", vault_address.to_string()),
+        attr("vault_code_id", latest_code_info.code_id.to_string()),
+    ]))
+}
+
+pub fn execute_vault_owner_changed(
+    deps: DepsMut,
+    info: &MessageInfo,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    // Verify that the caller is a vault minted by this contract
+    let (_, mut vault_info) = vault_list()
+        .idx
+        .address
+        .item(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // Update the owner of the vault, which also updates the owner index
+    vault_info.owner = deps.api.addr_validate(&new_owner)?;
+    vault_list().save(deps.storage, vault_info.index_number, &vault_info)?;
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "vault_owner_changed"),
+        attr("vault_address", info.sender.to_string()),
+        attr("new_owner", new_owner),
+    ]))
+}
+
+pub fn execute_nft_transferred(
+    deps: DepsMut,
+    info: &MessageInfo,
+    msg: NftTransferredMsg,
+) -> Result<Response, ContractError> {
+    // Verify that the caller is one of the collections minted by this contract
+    let config = CONFIG.load(deps.storage)?;
+    let is_vault_ownership_collection = config
+        .vault_ownership_collection
+        .is_some_and(|collection| collection.eq(&info.sender));
+    let is_lender_position_collection = config
+        .lender_position_collection
+        .is_some_and(|collection| collection.eq(&info.sender));
+    if !is_vault_ownership_collection && !is_lender_position_collection {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The ownership of a vault is minted with its index_number as token_id,
+    // so the new owner of the token is the new owner of the vault
+    let vault_info = match msg.token_id.parse::<u64>() {
+        Ok(index_number) if is_vault_ownership_collection => {
+            vault_list().may_load(deps.storage, index_number)?
+        }
+        _ => None,
+    };
+    if let Some(mut vault_info) = vault_info {
+        vault_info.owner = deps.api.addr_validate(&msg.owner)?;
+        vault_list().save(deps.storage, vault_info.index_number, &vault_info)?;
+    }
+
+    // return response
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "nft_transferred"),
+        attr("collection", info.sender.to_string()),
+        attr("token_id", msg.token_id),
+        attr("owner", msg.owner),
+    ]))
+}
+
+pub fn execute_lender_position_opened(
+    deps: DepsMut,
+    info: &MessageInfo,
+    token_id: String,
+    lender: String,
+) -> Result<Response, ContractError> {
+    let vault_info = helpers::verify_caller_issued_lender_position(&deps, info, &token_id)?;
+    let collection = CONFIG
+        .load(deps.storage)?
+        .lender_position_collection
+        .ok_or(ContractError::LenderPositionCollectionNotSet {})?;
+
+    // Mint the lender position to lender, whoever owns it acts as the lender on the vault
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&CollectionExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: deps.api.addr_validate(&lender)?.to_string(),
+            token_uri: None,
+            extension: Metadata {
+                attributes: vec![
+                    Trait {
+                        trait_type: "vault_address".to_string(),
+                        value: vault_info.address.to_string(),
+                    },
+                    Trait {
+                        trait_type: "index_number".to_string(),
+                        value: vault_info.index_number.to_string(),
+                    },
+                ],
+            },
+        })?,
+        funds: vec![],
+    };
+
+    // return response
+    Ok(Response::new().add_message(mint_msg).add_attributes(vec![
+        attr("method", "lender_position_opened"),
+        attr("vault_address", info.sender.to_string()),
+        attr("token_id", token_id),
+        attr("lender", lender),
+    ]))
+}
+
+pub fn execute_lender_position_closed(
+    deps: DepsMut,
+    info: &MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    helpers::verify_caller_issued_lender_position(&deps, info, &token_id)?;
+
+    // Burn the lender position, as the option it represents is closed
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: deps.api.addr_validate(&collection)?.to_string(),
+        msg: to_binary(&CollectionExecuteMsg::Burn {
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    // return response
+    Ok(Response::new().add_message(burn_msg).add_attributes(vec![
+        attr("method", "lender_position_closed"),
+        attr("vault_address", info.sender.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
 pub fn execute_withdraw_balance(
     deps: DepsMut,
     env: Env,
@@ -237,13 +644,104 @@ pub fn execute_transfer_ownership(
     ]))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_VAULT_REPLY_ID => reply_instantiate_vault(deps, env, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+pub fn reply_instantiate_vault(
+    deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    // Get the address of the vault that was just instantiated
+    let res = cw_utils::parse_reply_instantiate_data(msg)?;
+    let vault_address = deps.api.addr_validate(&res.contract_address)?;
+
+    // Read the owner, code_id and index_number the vault was instantiated with
+    let vault_info = helpers::query_vault_info(&deps, &vault_address)?;
+
+    // Save the vault to the list of vaults minted by this contract
+    vault_list().save(
+        deps.storage,
+        vault_info.config.index_number,
+        &VaultInfo {
+            index_number: vault_info.config.index_number,
+            address: vault_address.clone(),
+            owner: vault_info.config.owner.clone(),
+            code_id: vault_info.config.from_code_id,
+            created_at: env.block.time,
+        },
+    )?;
+
+    // Mint the ownership of the vault to its owner, then have the vault
+    // hand its ownership over to whoever owns the token
+    let mut response = Response::new();
+    if let Some(collection) = CONFIG.load(deps.storage)?.vault_ownership_collection {
+        let token_id = vault_info.config.index_number.to_string();
+        let mint_msg = WasmMsg::Execute {
+            contract_addr: collection.to_string(),
+            msg: to_binary(&CollectionExecuteMsg::Mint {
+                token_id: token_id.clone(),
+                owner: vault_info.config.owner.to_string(),
+                token_uri: None,
+                extension: Metadata {
+                    attributes: vec![
+                        Trait {
+                            trait_type: "index_number".to_string(),
+                            value: vault_info.config.index_number.to_string(),
+                        },
+                        Trait {
+                            trait_type: "code_id".to_string(),
+                            value: vault_info.config.from_code_id.to_string(),
+                        },
+                        Trait {
+                            trait_type: "vault_address".to_string(),
+                            value: vault_address.to_string(),
+                        },
+                    ],
+                },
+            })?,
+            funds: vec![],
+        };
+        let tokenize_ownership_msg = WasmMsg::Execute {
+            contract_addr: vault_address.to_string(),
+            msg: to_binary(&vault_contract::msg::ExecuteMsg::TokenizeOwnership {
+                collection: collection.to_string(),
+                token_id,
+            })?,
+            funds: vec![],
+        };
+        response = response.add_messages(vec![mint_msg, tokenize_ownership_msg]);
+    }
+
+    // return response
+    Ok(response
+        .add_attribute("method", "reply_instantiate_vault")
+        .add_attribute("vault_address", vault_address.to_string())
+        .add_attribute("index_number", vault_info.config.index_number.to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Info {} => to_binary(&query_info(deps)?),
+        QueryMsg::Params {} => to_binary(&query_params(deps)?),
         QueryMsg::QueryVaultCodeList { start_after, limit } => {
             to_binary(&query_vault_code_info_list(deps, start_after, limit)?)
         }
+        QueryMsg::VaultsByOwner {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_vaults_by_owner(deps, owner, start_after, limit)?),
+        QueryMsg::VaultByIndex { index_number } => {
+            to_binary(&query_vault_by_index(deps, index_number)?)
+        }
+        QueryMsg::IsVault { address } => to_binary(&query_is_vault(deps, address)?),
     }
 }
 
@@ -252,13 +750,18 @@ pub fn query_info(_deps: Deps) -> StdResult<Config> {
     Ok(config)
 }
 
+pub fn query_params(deps: Deps) -> StdResult<ProtocolParams> {
+    PARAMS.load(deps.storage)
+}
+
 fn query_vault_code_info_list(
     deps: Deps,
     start_after: Option<u64>,
     limit: Option<u32>,
 ) -> StdResult<VaultCodeListResponse> {
     let start = start_after.map(Bound::exclusive);
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let params = PARAMS.load(deps.storage)?;
+    let limit = limit.unwrap_or(params.default_limit).min(params.max_limit) as usize;
 
     // Get the entries that matches the range
     let entries: StdResult<Vec<_>> = VAULT_CODE_LIST
@@ -272,3 +775,45 @@ fn query_vault_code_info_list(
 
     Ok(results)
 }
+
+fn query_vaults_by_owner(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VaultListResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let start = start_after.map(Bound::exclusive);
+    let params = PARAMS.load(deps.storage)?;
+    let limit = limit.unwrap_or(params.default_limit).min(params.max_limit) as usize;
+
+    // Get the entries owned by owner that matches the range
+    let entries: StdResult<Vec<_>> = vault_list()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    let results = VaultListResponse {
+        entries: entries?.into_iter().map(|l| l.1).collect(),
+    };
+
+    Ok(results)
+}
+
+fn query_vault_by_index(deps: Deps, index_number: u64) -> StdResult<VaultInfo> {
+    vault_list().load(deps.storage, index_number)
+}
+
+fn query_is_vault(deps: Deps, address: String) -> StdResult<IsVaultResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let is_vault = vault_list()
+        .idx
+        .address
+        .item(deps.storage, address)?
+        .is_some();
+
+    Ok(IsVaultResponse { is_vault })
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::{Coin, StdError};
+use cosmwasm_std::{Coin, Decimal, StdError};
+use cw_utils::ParseReplyError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Unknown reply id: {id:?}")]
+    UnknownReplyId { id: u64 },
+
     #[error("InsufficientBalance: Required {required:?}, Available {available:?}")]
     InsufficientBalance { required: Coin, available: Coin },
 
@@ -18,9 +25,33 @@ pub enum ContractError {
     #[error("Incorrect amount sent as token_creation_fee:  required: {required:?}, received: {received:?}")]
     IncorrectTokenCreationFee { required: Coin, received: Coin },
 
+    #[error("Liquidity commission: {commission:?} exceeds the maximum: {max:?}")]
+    LiquidityCommissionTooHigh { commission: Decimal, max: Decimal },
+
+    #[error("Invalid protocol params: {reason:?}")]
+    InvalidProtocolParams { reason: String },
+
     #[error("Please call SetVaultCodeId first")]
     VaultCodeIdNotSet {},
 
+    #[error("Vault is already running the latest code_id: {code_id:?}")]
+    VaultAlreadyUpToDate { code_id: u64 },
+
+    #[error("The active lender: {lender:?} must approve this vault upgrade")]
+    VaultUpgradeNotApprovedByLender { lender: String },
+
+    #[error("The lender position is shared by {lenders:?} lenders, who must all agree to this vault upgrade")]
+    LenderPositionIsSyndicated { lenders: u32 },
+
+    #[error("This contract must be the minter of collection: {collection:?}")]
+    NotCollectionMinter { collection: String },
+
+    #[error("This contract must be the transfer hook of collection: {collection:?}")]
+    NotCollectionTransferHook { collection: String },
+
+    #[error("Please call SetLenderPositionCollection first")]
+    LenderPositionCollectionNotSet {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
@@ -1,6 +1,13 @@
-use crate::state::MIN_VAULT_CODE_UPDATE_INTERVAL;
+use crate::state::{
+    vault_list, ProtocolParams, VaultCodeInfo, VaultInfo, MAX_COUNTER_OFFERS_UPPER_BOUND, PARAMS,
+    VAULT_CODE_LIST, VAULT_CODE_SEQ,
+};
 use crate::{state::CONFIG, ContractError};
-use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, StdResult, Uint128};
+use collection::msg::{MinterResponse, QueryMsg as CollectionQueryMsg, TransferHookResponse};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, StdResult, Storage, Uint128,
+};
+use vault_contract::msg::{InfoResponse, LenderSharesResponse, QueryMsg as VaultQueryMsg};
 
 pub fn get_bank_transfer_to_msg(recipient: &Addr, denom: &str, amount: Uint128) -> CosmosMsg {
     BankMsg::Send {
@@ -34,16 +41,17 @@ pub fn verify_caller_is_owner(info: &MessageInfo, deps: &DepsMut) -> Result<(),
 
 pub fn can_update_vault_code_info(deps: &DepsMut, env: &Env) -> Result<(), ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    let min_vault_code_update_interval = PARAMS.load(deps.storage)?.min_vault_code_update_interval;
 
     // Calculate duration_since_last_update
     let duration_since_last_update =
         if let Some(vault_code_info_updated_at) = config.vault_code_info_updated_at {
             env.block.time.seconds() - vault_code_info_updated_at.seconds()
         } else {
-            MIN_VAULT_CODE_UPDATE_INTERVAL
+            min_vault_code_update_interval
         };
 
-    if duration_since_last_update < MIN_VAULT_CODE_UPDATE_INTERVAL {
+    if duration_since_last_update < min_vault_code_update_interval {
         return Err(ContractError::MinVaultCodeUpdateIntervalNotReached {});
     }
 
@@ -51,6 +59,29 @@ pub fn can_update_vault_code_info(deps: &DepsMut, env: &Env) -> Result<(), Contr
     Ok(())
 }
 
+pub fn validate_protocol_params(params: &ProtocolParams) -> Result<(), ContractError> {
+    let invalid = |reason: &str| {
+        Err(ContractError::InvalidProtocolParams {
+            reason: reason.to_string(),
+        })
+    };
+
+    if params.unbonding_period == 0 {
+        return invalid("unbonding_period must be greater than zero");
+    }
+
+    if params.max_counter_offers == 0 || params.max_counter_offers > MAX_COUNTER_OFFERS_UPPER_BOUND
+    {
+        return invalid("max_counter_offers must be between 1 and 50");
+    }
+
+    if params.default_limit == 0 || params.default_limit > params.max_limit {
+        return invalid("default_limit must be between 1 and max_limit");
+    }
+
+    Ok(())
+}
+
 pub fn validate_vault_creation_fee(deps: &DepsMut, coins: &[Coin]) -> Result<(), ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -71,3 +102,86 @@ pub fn validate_vault_creation_fee(deps: &DepsMut, coins: &[Coin]) -> Result<(),
 
     Ok(())
 }
+
+pub fn get_latest_vault_code_info(storage: &dyn Storage) -> Result<VaultCodeInfo, ContractError> {
+    // Verify that VAULT_CODE_SEQ for setting vault_code_id is greater than 0
+    // Which indicates there is a vault_code_id already set
+    let vault_code_seq_id = VAULT_CODE_SEQ.load(storage)?;
+    if vault_code_seq_id.eq(&0u64) {
+        return Err(ContractError::VaultCodeIdNotSet {});
+    }
+
+    Ok(VAULT_CODE_LIST.load(storage, vault_code_seq_id)?)
+}
+
+pub fn query_vault_info(deps: &DepsMut, vault_address: &Addr) -> StdResult<InfoResponse> {
+    deps.querier
+        .query_wasm_smart(vault_address, &VaultQueryMsg::Info {})
+}
+
+pub fn query_vault_lender_shares(
+    deps: &DepsMut,
+    vault_address: &Addr,
+) -> StdResult<LenderSharesResponse> {
+    deps.querier
+        .query_wasm_smart(vault_address, &VaultQueryMsg::LenderShares {})
+}
+
+/// Verifies that the caller is a vault minted by this contract,
+/// and that token_id was issued by that vault for its lender positions
+pub fn verify_caller_issued_lender_position(
+    deps: &DepsMut,
+    info: &MessageInfo,
+    token_id: &str,
+) -> Result<VaultInfo, ContractError> {
+    let (_, vault_info) = vault_list()
+        .idx
+        .address
+        .item(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if !token_id.starts_with(&format!("{}/", vault_info.address)) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(vault_info)
+}
+
+/// Verifies that this contract can mint and burn tokens of collection
+pub fn verify_contract_is_collection_minter(
+    deps: &DepsMut,
+    env: &Env,
+    collection: &Addr,
+) -> Result<(), ContractError> {
+    let minter: MinterResponse = deps
+        .querier
+        .query_wasm_smart(collection, &CollectionQueryMsg::Minter {})?;
+    if minter.minter.ne(&Some(env.contract.address.to_string())) {
+        return Err(ContractError::NotCollectionMinter {
+            collection: collection.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Verifies that collection notifies this contract whenever one of its tokens changes owner
+pub fn verify_contract_is_collection_transfer_hook(
+    deps: &DepsMut,
+    env: &Env,
+    collection: &Addr,
+) -> Result<(), ContractError> {
+    let transfer_hook: TransferHookResponse = deps
+        .querier
+        .query_wasm_smart(collection, &CollectionQueryMsg::TransferHook {})?;
+    if transfer_hook
+        .transfer_hook
+        .ne(&Some(env.contract.address.to_string()))
+    {
+        return Err(ContractError::NotCollectionTransferHook {
+            collection: collection.to_string(),
+        });
+    }
+
+    Ok(())
+}
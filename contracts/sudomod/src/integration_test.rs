@@ -1,15 +1,21 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        msg::{ExecuteMsg, InstantiateMsg, QueryMsg, VaultCodeListResponse},
-        state::MIN_VAULT_CODE_UPDATE_INTERVAL,
-        state::{Config, VaultCodeInfo},
+        msg::{
+            ExecuteMsg, InstantiateMsg, IsVaultResponse, QueryMsg, VaultCodeListResponse,
+            VaultListResponse,
+        },
+        state::{Config, ProtocolParams, VaultCodeInfo, VaultInfo},
+        state::{
+            DEFAULT_LIQUIDITY_COMMISSION, MAX_LIQUIDITY_COMMISSION, MIN_VAULT_CODE_UPDATE_INTERVAL,
+        },
     };
     use cosmwasm_std::{testing::mock_env, Addr, Coin, Decimal, Empty, Uint128, Validator};
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor, StakingInfo};
 
     const USER: &str = "user";
     const LENDER: &str = "lender";
+    const FEE_COLLECTOR: &str = "fee_collector";
     const STAKING_DENOM: &str = "udenom";
     const IBC_DENOM_1: &str = "ibc/usdc_denom";
     const SUPPLY: u128 = 500_000_000u128;
@@ -89,18 +95,32 @@ mod tests {
     }
 
     fn sudomod_contract_template() -> Box<dyn Contract<Empty>> {
-        Box::new(ContractWrapper::new(
-            crate::contract::execute,
-            crate::contract::instantiate,
-            crate::contract::query,
-        ))
+        Box::new(
+            ContractWrapper::new(
+                crate::contract::execute,
+                crate::contract::instantiate,
+                crate::contract::query,
+            )
+            .with_reply(crate::contract::reply),
+        )
     }
 
     fn vault_contract_template() -> Box<dyn Contract<Empty>> {
+        Box::new(
+            ContractWrapper::new(
+                vault_contract::contract::execute,
+                vault_contract::contract::instantiate,
+                vault_contract::contract::query,
+            )
+            .with_migrate(vault_contract::contract::migrate),
+        )
+    }
+
+    fn collection_contract_template() -> Box<dyn Contract<Empty>> {
         Box::new(ContractWrapper::new(
-            vault_contract::contract::execute,
-            vault_contract::contract::instantiate,
-            vault_contract::contract::query,
+            collection::contract::execute,
+            collection::contract::instantiate,
+            collection::contract::query,
         ))
     }
 
@@ -109,6 +129,11 @@ mod tests {
         app.wrap().query_wasm_smart(contract_address, &msg).unwrap()
     }
 
+    fn get_params(app: &mut App, contract_address: &Addr) -> ProtocolParams {
+        let msg = QueryMsg::Params {};
+        app.wrap().query_wasm_smart(contract_address, &msg).unwrap()
+    }
+
     fn get_vault_code_id_list(app: &mut App, contract_address: &Addr) -> VaultCodeListResponse {
         let msg = QueryMsg::QueryVaultCodeList {
             start_after: None,
@@ -117,6 +142,21 @@ mod tests {
         app.wrap().query_wasm_smart(contract_address, &msg).unwrap()
     }
 
+    fn get_vaults_by_owner(
+        app: &mut App,
+        contract_address: &Addr,
+        owner: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> VaultListResponse {
+        let msg = QueryMsg::VaultsByOwner {
+            owner: owner.to_string(),
+            start_after,
+            limit,
+        };
+        app.wrap().query_wasm_smart(contract_address, &msg).unwrap()
+    }
+
     fn bank_balance(router: &mut App, addr: &Addr, denom: String) -> Coin {
         router
             .wrap()
@@ -136,16 +176,24 @@ mod tests {
         contract_addr
     }
 
-    fn setup_sudomod(app: &mut App) -> Addr {
-        // Create an instance of sudomod with contract_address = contract1,
-        // because this is what is hard coded as INSTANTIATOR_ADDR in the vault contract
-        // For testing purposes.
-        // ------------------------------------------------------------------------------
-        instantiate_sudomod(app); // contract0 not used
-        let sudomod_c_addr = instantiate_sudomod(app);
+    fn instantiate_collection(app: &mut App, minter: &Addr, transfer_hook: Option<&Addr>) -> Addr {
+        let code_id = app.store_code(collection_contract_template());
+        let msg = collection::msg::InstantiateMsg {
+            name: "Sudostake Lender Positions".to_string(),
+            symbol: "SLP".to_string(),
+            minter: minter.to_string(),
+            transfer_hook: transfer_hook.map(|hook| hook.to_string()),
+        };
 
-        // Return the contract_addr = contract1
-        sudomod_c_addr
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(USER),
+            &msg,
+            &[],
+            "lender_positions",
+            None,
+        )
+        .unwrap()
     }
 
     #[test]
@@ -154,7 +202,7 @@ mod tests {
         // Create an instance of sudomod
         // ------------------------------------------------------------------------------
         let mut app = mock_app();
-        let sudomod_c_addr = setup_sudomod(&mut app);
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
 
         // Step 2
         // Test error case ContractError::Unauthorized {}
@@ -226,8 +274,8 @@ mod tests {
             vault_code_list,
             VaultCodeListResponse {
                 entries: vec![
-                    VaultCodeInfo { id: 1, code_id: 4 },
-                    VaultCodeInfo { id: 2, code_id: 6 }
+                    VaultCodeInfo { id: 1, code_id: 3 },
+                    VaultCodeInfo { id: 2, code_id: 5 }
                 ]
             }
         );
@@ -239,7 +287,7 @@ mod tests {
         // Create an instance of sudomod
         // ------------------------------------------------------------------------------
         let mut app = mock_app();
-        let sudomod_c_addr = setup_sudomod(&mut app);
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
 
         // Step 2
         // Test error case ContractError::Unauthorized {}
@@ -280,126 +328,269 @@ mod tests {
     }
 
     #[test]
-    fn test_mint_vault() {
+    fn test_set_liquidity_commission_and_fee_collector() {
         // Step 1
         // Create an instance of sudomod
         // ------------------------------------------------------------------------------
         let mut app = mock_app();
-        let sudomod_c_addr = setup_sudomod(&mut app);
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
 
         // Step 2
-        // Test error case ContractError::VaultCodeIdNotSet {}
-        // by trying to call MintVault before setting vault code id
+        // Verify the default liquidity_commission and fee_collector
         // ------------------------------------------------------------------------------
-        let mint_vault_msg = ExecuteMsg::MintVault {};
+        let info = get_contract_info(&mut app, &sudomod_c_addr);
+        assert_eq!(info.liquidity_commission, DEFAULT_LIQUIDITY_COMMISSION);
+        assert_eq!(info.fee_collector, sudomod_c_addr);
+
+        // Step 3
+        // Test error case ContractError::Unauthorized {}
+        // by calling with the wrong contract owner
+        // -----------------------------------------------------------------------------
+        let wrong_owner = "wrong_owner".to_string();
+        let set_liquidity_commission_msg = ExecuteMsg::SetLiquidityCommission {
+            commission: Decimal::percent(1),
+        };
+        let set_fee_collector_msg = ExecuteMsg::SetFeeCollector {
+            address: FEE_COLLECTOR.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(wrong_owner.clone()),
+            sudomod_c_addr.clone(),
+            &set_liquidity_commission_msg,
+            &[],
+        )
+        .unwrap_err();
+        app.execute_contract(
+            Addr::unchecked(wrong_owner),
+            sudomod_c_addr.clone(),
+            &set_fee_collector_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 4
+        // Test error case ContractError::LiquidityCommissionTooHigh {}
+        // by setting a commission above MAX_LIQUIDITY_COMMISSION
+        // -----------------------------------------------------------------------------
         app.execute_contract(
             Addr::unchecked(USER),
             sudomod_c_addr.clone(),
-            &mint_vault_msg,
+            &ExecuteMsg::SetLiquidityCommission {
+                commission: MAX_LIQUIDITY_COMMISSION + Decimal::permille(1),
+            },
             &[],
         )
         .unwrap_err();
 
-        // Step 3
-        // set vault code id
-        // ------------------------------------------------------------------------------
-        let code_id = app.store_code(vault_contract_template());
-        let set_vault_code_id_msg = ExecuteMsg::SetVaultCodeId { code_id };
+        // Step 5
+        // Set liquidity_commission and fee_collector properly
+        // -----------------------------------------------------------------------------
         app.execute_contract(
             Addr::unchecked(USER),
             sudomod_c_addr.clone(),
-            &set_vault_code_id_msg,
+            &set_liquidity_commission_msg,
             &[],
         )
         .unwrap();
-
-        // Step 4
-        // Mint a free vault after setting vault code id
-        // ------------------------------------------------------------------------------
         app.execute_contract(
             Addr::unchecked(USER),
             sudomod_c_addr.clone(),
-            &mint_vault_msg,
+            &set_fee_collector_msg,
             &[],
         )
         .unwrap();
 
-        // Step 5
-        // Set vault creation fee
+        // Step 6
+        // Query sudomod info to verify the new liquidity_commission and fee_collector
         // ------------------------------------------------------------------------------
-        let vault_creation_fee = Coin {
-            amount: Uint128::new(10_000_000),
-            denom: IBC_DENOM_1.to_string(),
+        let info = get_contract_info(&mut app, &sudomod_c_addr);
+        assert_eq!(info.liquidity_commission, Decimal::percent(1));
+        assert_eq!(info.fee_collector, Addr::unchecked(FEE_COLLECTOR));
+    }
+
+    #[test]
+    fn test_vault_snapshots_liquidity_commission() {
+        // Step 1
+        // Create an instance of sudomod, set a commission of 1% and mint a vault
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let code_id = app.store_code(vault_contract_template());
+        for msg in [
+            ExecuteMsg::SetVaultCodeId { code_id },
+            ExecuteMsg::SetLiquidityCommission {
+                commission: Decimal::percent(1),
+            },
+            ExecuteMsg::SetFeeCollector {
+                address: FEE_COLLECTOR.to_string(),
+            },
+        ] {
+            app.execute_contract(Addr::unchecked(USER), sudomod_c_addr.clone(), &msg, &[])
+                .unwrap();
+        }
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+
+        // Step 2
+        // Delegate, then open and accept a FixedTermLoan on the vault
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+        let requested_amount = Uint128::new(100_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         app.execute_contract(
             Addr::unchecked(USER),
-            sudomod_c_addr.clone(),
-            &ExecuteMsg::SetVaultCreationFee {
-                amount: vault_creation_fee.clone(),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
             },
             &[],
         )
         .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }],
+        )
+        .unwrap();
 
-        // Step 6
-        // Test error case ContractError::IncorrectTokenCreationFee {}
-        // by calling MintVault with an incorrect vault_creation_fee
+        // Step 3
+        // Verify that FEE_COLLECTOR received 1% of the requested_amount
+        // ------------------------------------------------------------------------------
+        let commission = Coin {
+            denom: IBC_DENOM_1.to_string(),
+            amount: Uint128::new(1_000),
+        };
+        assert_eq!(
+            bank_balance(
+                &mut app,
+                &Addr::unchecked(FEE_COLLECTOR),
+                IBC_DENOM_1.into()
+            ),
+            commission
+        );
+
+        // Step 4
+        // Change the commission terms on sudomod
         // ------------------------------------------------------------------------------
         app.execute_contract(
             Addr::unchecked(USER),
             sudomod_c_addr.clone(),
-            &mint_vault_msg,
+            &ExecuteMsg::SetLiquidityCommission {
+                commission: Decimal::percent(2),
+            },
             &[],
         )
-        .unwrap_err();
+        .unwrap();
 
-        // Step 7
-        // Call MintVault with the correct vault_creation_fee
+        // Step 5
+        // Verify that the accepted option keeps the commission terms it was accepted with
+        // ------------------------------------------------------------------------------
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(
+            vault_info.liquidity_request.unwrap().liquidity_commission,
+            Some(vault_contract::types::LiquidityCommission {
+                rate: Decimal::percent(1),
+                amount: commission,
+                fee_collector: Addr::unchecked(FEE_COLLECTOR),
+            })
+        );
+    }
+
+    #[test]
+    fn test_vault_snapshots_protocol_params() {
+        const KEEPER: &str = "keeper";
+
+        // Step 1
+        // Create an instance of sudomod, allow keeper fees up to 1_000 and mint a vault
         // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let code_id = app.store_code(vault_contract_template());
+        let params = ProtocolParams {
+            max_keeper_fee: Uint128::new(1_000),
+            ..ProtocolParams::default()
+        };
+        for msg in [
+            ExecuteMsg::SetVaultCodeId { code_id },
+            ExecuteMsg::UpdateParams {
+                params: params.clone(),
+            },
+        ] {
+            app.execute_contract(Addr::unchecked(USER), sudomod_c_addr.clone(), &msg, &[])
+                .unwrap();
+        }
         let res = app
             .execute_contract(
                 Addr::unchecked(USER),
                 sudomod_c_addr.clone(),
-                &mint_vault_msg,
-                &[vault_creation_fee.clone()],
+                &ExecuteMsg::MintVault {},
+                &[],
             )
             .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
 
-        // Step 8
-        // Get vault_contract_addr from res
-        // ------------------------------------------------------------------------------
-        let vault_contract_addr = res.events[3].attributes[0].value.clone();
-
-        // Add e2e test to verify that fees accrue at _sudomod_c_addr
-        // for accepted liquidity requests on vault_contract_addr
-        // ------------------------------------------------------------------------------
-        // ------------------------------------------------------------------------------
-        //
-        // Step 1
-        // Delegate to VALIDATOR_ONE_ADDRESS on vault_contract_addr
+        // Step 2
+        // Delegate, then open a FixedTermLoan paying the maximum keeper_fee.
+        // Lower max_keeper_fee to 500 on sudomod, and accept the FixedTermLoan
         // ------------------------------------------------------------------------------
         let delegate_amount = Uint128::new(1_000_000);
-        let delegate_msg = vault_contract::msg::ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount: delegate_amount,
-        };
         app.execute_contract(
             Addr::unchecked(USER),
-            Addr::unchecked(vault_contract_addr.clone()),
-            &delegate_msg,
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
             &[Coin {
                 denom: STAKING_DENOM.into(),
                 amount: delegate_amount,
             }],
         )
         .unwrap();
-
-        // Step 2
-        // Create a valid FixedTermLoan liquidity request on vault_contract_addr
-        // ------------------------------------------------------------------------------
-        let duration_in_seconds = 60u64;
-        let requested_amount = Uint128::new(100_000_000);
-        let expected_liquidity_comission = Uint128::new(300_000);
+        let requested_amount = Uint128::new(100_000);
         let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
@@ -407,27 +598,39 @@ mod tests {
             },
             interest_amount: Uint128::zero(),
             collateral_amount: delegate_amount,
-            duration_in_seconds,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: params.max_keeper_fee,
         };
         app.execute_contract(
             Addr::unchecked(USER),
-            Addr::unchecked(vault_contract_addr.clone()),
+            vault_c_addr.clone(),
             &vault_contract::msg::ExecuteMsg::RequestLiquidity {
                 option: option.clone(),
             },
             &[],
         )
         .unwrap();
-
-        // Step 3
-        // Accept the open liquidity request on vault_contract_addr
-        // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg =
-            vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option };
+        let accepted_params = ProtocolParams {
+            max_keeper_fee: Uint128::new(500),
+            ..params.clone()
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::UpdateParams {
+                params: accepted_params.clone(),
+            },
+            &[],
+        )
+        .unwrap();
         app.execute_contract(
             Addr::unchecked(LENDER),
-            Addr::unchecked(vault_contract_addr),
-            &accept_liquidity_request_msg,
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
             &[Coin {
                 denom: IBC_DENOM_1.to_string(),
                 amount: requested_amount,
@@ -435,24 +638,1465 @@ mod tests {
         )
         .unwrap();
 
+        // Step 3
+        // Disable keeper fees on sudomod
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::UpdateParams {
+                params: ProtocolParams::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
         // Step 4
-        // Verify that liquidity_comission from vault_contract_addr accrue at sudomod_c_addr
+        // Verify that the accepted option keeps the params it was accepted with
         // ------------------------------------------------------------------------------
-        // ibc_denom_balance = SUPPLY - requested_amount +  vault_creation_fee
-        let balance = bank_balance(&mut app, &sudomod_c_addr, IBC_DENOM_1.to_string());
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
         assert_eq!(
-            balance.amount,
-            vault_creation_fee.amount + expected_liquidity_comission
+            vault_info.liquidity_request.unwrap().protocol_params,
+            Some(vault_contract::types::ProtocolParams {
+                unbonding_period: accepted_params.unbonding_period,
+                max_counter_offers: accepted_params.max_counter_offers,
+                min_vault_code_update_interval: accepted_params.min_vault_code_update_interval,
+                max_limit: accepted_params.max_limit,
+                default_limit: accepted_params.default_limit,
+                max_keeper_fee: accepted_params.max_keeper_fee,
+            })
+        );
+
+        // Step 5
+        // Fast forward the time so the option expires, have KEEPER liquidate the collateral,
+        // and verify that KEEPER is paid the keeper_fee capped by the max_keeper_fee
+        // in force when the option was accepted
+        // ------------------------------------------------------------------------------
+        app.update_block(|block| block.time = block.time.plus_seconds(60));
+        let liquidation_msg = vault_contract::msg::ExecuteMsg::LiquidateCollateral {};
+        app.execute_contract(
+            Addr::unchecked(KEEPER),
+            vault_c_addr.clone(),
+            &liquidation_msg,
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(10));
+        app.sudo(cw_multi_test::SudoMsg::Staking(
+            cw_multi_test::StakingSudo::ProcessQueue {},
+        ))
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(KEEPER),
+            vault_c_addr.clone(),
+            &liquidation_msg,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            bank_balance(&mut app, &Addr::unchecked(KEEPER), STAKING_DENOM.into()).amount,
+            accepted_params.max_keeper_fee
         );
     }
 
+    #[test]
+    fn test_lender_position_collection() {
+        // Step 1
+        // Create an instance of sudomod and a collection minted by sudomod
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let collection_c_addr = instantiate_collection(&mut app, &sudomod_c_addr, None);
+        let code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId { code_id },
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Test error case ContractError::NotCollectionMinter {}
+        // when sudomod can not mint tokens of the collection
+        // ------------------------------------------------------------------------------
+        let other_collection_c_addr =
+            instantiate_collection(&mut app, &Addr::unchecked(USER), None);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetLenderPositionCollection {
+                address: other_collection_c_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Test error case ContractError::Unauthorized {}
+        // when the caller is not the owner of sudomod
+        // ------------------------------------------------------------------------------
+        let set_collection_msg = ExecuteMsg::SetLenderPositionCollection {
+            address: collection_c_addr.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            sudomod_c_addr.clone(),
+            &set_collection_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 4
+        // Set the lender position collection and mint a vault
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &set_collection_msg,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_contract_info(&mut app, &sudomod_c_addr).lender_position_collection,
+            Some(collection_c_addr.clone())
+        );
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+
+        // Step 5
+        // Test error case ContractError::Unauthorized {}
+        // when the caller of the hook is not a vault minted by sudomod
+        // ------------------------------------------------------------------------------
+        let token_id = format!("{}/1", vault_c_addr);
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::LenderPositionOpened {
+                token_id: token_id.clone(),
+                lender: LENDER.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 6
+        // Delegate, then open and accept a FixedTermLoan on the vault
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+        let requested_amount = Uint128::new(100_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }],
+        )
+        .unwrap();
+
+        // Step 7
+        // Verify that the lender position was minted to LENDER
+        // with the vault_address and index_number of the vault
+        // ------------------------------------------------------------------------------
+        let nft_info: collection::msg::AllNftInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_c_addr.clone(),
+                &collection::msg::QueryMsg::AllNftInfo {
+                    token_id: token_id.clone(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(nft_info.access.owner, LENDER.to_string());
+        assert_eq!(
+            nft_info.info.extension.attributes,
+            vec![
+                collection::state::Trait {
+                    trait_type: "vault_address".to_string(),
+                    value: vault_c_addr.to_string(),
+                },
+                collection::state::Trait {
+                    trait_type: "index_number".to_string(),
+                    value: "1".to_string(),
+                },
+            ]
+        );
+
+        // Step 8
+        // Transfer the lender position to BUYER through the collection
+        // and verify that BUYER is reported as the lender on the vault
+        // ------------------------------------------------------------------------------
+        const BUYER: &str = "buyer";
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            collection_c_addr.clone(),
+            &collection::msg::ExecuteMsg::TransferNft {
+                recipient: BUYER.to_string(),
+                token_id: token_id.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        let active_option = vault_info.liquidity_request.unwrap();
+        assert_eq!(active_option.lender, Some(Addr::unchecked(BUYER)));
+        assert_eq!(
+            active_option.lender_position,
+            Some(vault_contract::types::LenderPosition {
+                collection: collection_c_addr.clone(),
+                token_id: token_id.clone(),
+            })
+        );
+
+        // Step 9
+        // Test error case ContractError::LenderPositionIsTokenized {}
+        // when the lender position is transferred outside the collection
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::TransferLenderPosition {
+                to_address: LENDER.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 10
+        // Repay the loan and verify that BUYER receives the repayment
+        // ------------------------------------------------------------------------------
+        let liquidity_comission = Uint128::new(300);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RepayLoan { amount: None },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: liquidity_comission,
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            bank_balance(&mut app, &Addr::unchecked(BUYER), IBC_DENOM_1.into()),
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }
+        );
+
+        // Step 11
+        // Verify that the lender position was burned as the option closed
+        // ------------------------------------------------------------------------------
+        let num_tokens: collection::msg::NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_c_addr.clone(),
+                &collection::msg::QueryMsg::NumTokens {},
+            )
+            .unwrap();
+        assert_eq!(num_tokens.count, 0);
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_vault_ownership_collection() {
+        // Step 1
+        // Create an instance of sudomod and set a vault ownership collection minted by sudomod,
+        // which notifies sudomod whenever a token changes owner
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let collection_c_addr =
+            instantiate_collection(&mut app, &sudomod_c_addr, Some(&sudomod_c_addr));
+        let code_id = app.store_code(vault_contract_template());
+        for msg in [
+            ExecuteMsg::SetVaultCodeId { code_id },
+            ExecuteMsg::SetVaultOwnershipCollection {
+                address: collection_c_addr.to_string(),
+            },
+        ] {
+            app.execute_contract(Addr::unchecked(USER), sudomod_c_addr.clone(), &msg, &[])
+                .unwrap();
+        }
+        assert_eq!(
+            get_contract_info(&mut app, &sudomod_c_addr).vault_ownership_collection,
+            Some(collection_c_addr.clone())
+        );
+
+        // Step 2
+        // Test error case ContractError::NotCollectionTransferHook {}
+        // when the collection does not notify sudomod of transfers
+        // ------------------------------------------------------------------------------
+        let other_collection_c_addr = instantiate_collection(&mut app, &sudomod_c_addr, None);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultOwnershipCollection {
+                address: other_collection_c_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Mint a vault and verify that its ownership was minted to USER
+        // with the index_number, code_id and address of the vault
+        // ------------------------------------------------------------------------------
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+        let token_id = "1".to_string();
+        let nft_info: collection::msg::AllNftInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_c_addr.clone(),
+                &collection::msg::QueryMsg::AllNftInfo {
+                    token_id: token_id.clone(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(nft_info.access.owner, USER.to_string());
+        assert_eq!(
+            nft_info.info.extension.attributes,
+            vec![
+                collection::state::Trait {
+                    trait_type: "index_number".to_string(),
+                    value: "1".to_string(),
+                },
+                collection::state::Trait {
+                    trait_type: "code_id".to_string(),
+                    value: code_id.to_string(),
+                },
+                collection::state::Trait {
+                    trait_type: "vault_address".to_string(),
+                    value: vault_c_addr.to_string(),
+                },
+            ]
+        );
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.config.owner, Addr::unchecked(USER));
+        assert_eq!(
+            vault_info.config.ownership_token,
+            Some(vault_contract::types::OwnershipToken {
+                collection: collection_c_addr.clone(),
+                token_id: token_id.clone(),
+            })
+        );
+
+        // Step 4
+        // Test error case ContractError::Unauthorized {}
+        // when the caller tokenizing the ownership of the vault is not sudomod
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::TokenizeOwnership {
+                collection: collection_c_addr.to_string(),
+                token_id: token_id.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 5
+        // Transfer the ownership of the vault to BUYER through the collection
+        // and verify that BUYER is reported as the vault owner,
+        // and that VaultsByOwner lists the vault under BUYER straight away
+        // ------------------------------------------------------------------------------
+        const BUYER: &str = "buyer";
+        app.execute_contract(
+            Addr::unchecked(USER),
+            collection_c_addr.clone(),
+            &collection::msg::ExecuteMsg::TransferNft {
+                recipient: BUYER.to_string(),
+                token_id: token_id.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.config.owner, Addr::unchecked(BUYER));
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, BUYER, None, None);
+        assert_eq!(vaults.entries.len(), 1);
+        assert_eq!(vaults.entries[0].address, vault_c_addr);
+        assert_eq!(vaults.entries[0].owner, Addr::unchecked(BUYER));
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, None, None);
+        assert_eq!(vaults.entries.len(), 0);
+
+        // Step 6
+        // Test error case ContractError::Unauthorized {}
+        // when NftTransferred is not called by a collection minted by sudomod
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::NftTransferred(collection::msg::NftTransferredMsg {
+                token_id: token_id.clone(),
+                owner: USER.to_string(),
+            }),
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 7
+        // Test error case ContractError::Unauthorized {}
+        // when the previous owner of the token manages the vault
+        // ------------------------------------------------------------------------------
+        let set_unbonding_strategy_msg = vault_contract::msg::ExecuteMsg::SetUnbondingStrategy {
+            strategy: vault_contract::types::UnbondingStrategy::ProRata,
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &set_unbonding_strategy_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 8
+        // Test error case ContractError::OwnershipIsTokenized {}
+        // when the ownership is transferred outside the collection
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::TransferOwnership {
+                to_address: USER.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 9
+        // Manage the vault as BUYER, and verify that VaultsByOwner
+        // still lists the vault under BUYER once the vault synced its owner
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(BUYER),
+            vault_c_addr.clone(),
+            &set_unbonding_strategy_msg,
+            &[],
+        )
+        .unwrap();
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, BUYER, None, None);
+        assert_eq!(vaults.entries.len(), 1);
+        assert_eq!(vaults.entries[0].address, vault_c_addr);
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, None, None);
+        assert_eq!(vaults.entries.len(), 0);
+    }
+
+    #[test]
+    fn test_update_params() {
+        // Step 1
+        // Create an instance of sudomod and verify the default params
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        assert_eq!(
+            get_params(&mut app, &sudomod_c_addr),
+            ProtocolParams::default()
+        );
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // by calling with the wrong contract owner
+        // -----------------------------------------------------------------------------
+        let params = ProtocolParams {
+            unbonding_period: 60 * 60 * 24 * 14,
+            max_counter_offers: 5,
+            min_vault_code_update_interval: 60,
+            max_limit: 20,
+            default_limit: 10,
+            max_keeper_fee: Uint128::new(1_000),
+        };
+        let update_params_msg = ExecuteMsg::UpdateParams {
+            params: params.clone(),
+        };
+        app.execute_contract(
+            Addr::unchecked("wrong_owner"),
+            sudomod_c_addr.clone(),
+            &update_params_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Test error case ContractError::InvalidProtocolParams {}
+        // by setting a default_limit greater than max_limit
+        // -----------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::UpdateParams {
+                params: ProtocolParams {
+                    default_limit: 21,
+                    ..params.clone()
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 4
+        // Update params properly and query them
+        // -----------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &update_params_msg,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_params(&mut app, &sudomod_c_addr), params);
+
+        // Step 5
+        // Verify that SetVaultCodeId uses the new min_vault_code_update_interval
+        // -----------------------------------------------------------------------------
+        let code_id = app.store_code(vault_contract_template());
+        let set_vault_code_id_msg = ExecuteMsg::SetVaultCodeId { code_id };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &set_vault_code_id_msg,
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| {
+            block.time = block
+                .time
+                .plus_seconds(params.min_vault_code_update_interval)
+        });
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &set_vault_code_id_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Step 6
+        // Mint a vault, and verify that it was instantiated with the new unbonding_period
+        // ------------------------------------------------------------------------------
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.config.unbonding_period, params.unbonding_period);
+
+        // Step 7
+        // Update params again, and verify that the minted vault keeps the unbonding_period
+        // it was instantiated with
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::UpdateParams {
+                params: ProtocolParams::default(),
+            },
+            &[],
+        )
+        .unwrap();
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.config.unbonding_period, params.unbonding_period);
+
+        // Step 8
+        // Delegate, undelegate part of it, and open and accept a FixedTermLoan backed by
+        // the rest of the delegation. Then liquidate the expired loan, and verify that both
+        // the undelegation and the liquidation unbondings mature after the unbonding_period
+        // of the vault
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+        let undelegate_amount = Uint128::new(100_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Undelegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: undelegate_amount,
+            },
+            &[],
+        )
+        .unwrap();
+        let requested_amount = Uint128::new(100_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount - undelegate_amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(61));
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::LiquidateCollateral {},
+            &[],
+        )
+        .unwrap();
+        let pending_unbondings: vault_contract::msg::PendingUnbondingsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::PendingUnbondings {},
+            )
+            .unwrap();
+        assert_eq!(pending_unbondings.unbondings.len(), 2);
+        for unbonding in pending_unbondings.unbondings {
+            assert_eq!(
+                unbonding.completion_time,
+                unbonding.start_time.plus_seconds(params.unbonding_period)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mint_vault() {
+        // Step 1
+        // Create an instance of sudomod
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+
+        // Step 2
+        // Test error case ContractError::VaultCodeIdNotSet {}
+        // by trying to call MintVault before setting vault code id
+        // ------------------------------------------------------------------------------
+        let mint_vault_msg = ExecuteMsg::MintVault {};
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &mint_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // set vault code id
+        // ------------------------------------------------------------------------------
+        let code_id = app.store_code(vault_contract_template());
+        let set_vault_code_id_msg = ExecuteMsg::SetVaultCodeId { code_id };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &set_vault_code_id_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Step 4
+        // Mint a free vault after setting vault code id
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &mint_vault_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Step 5
+        // Set vault creation fee
+        // ------------------------------------------------------------------------------
+        let vault_creation_fee = Coin {
+            amount: Uint128::new(10_000_000),
+            denom: IBC_DENOM_1.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCreationFee {
+                amount: vault_creation_fee.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 6
+        // Test error case ContractError::IncorrectTokenCreationFee {}
+        // by calling MintVault with an incorrect vault_creation_fee
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &mint_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 7
+        // Call MintVault with the correct vault_creation_fee
+        // ------------------------------------------------------------------------------
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &mint_vault_msg,
+                &[vault_creation_fee.clone()],
+            )
+            .unwrap();
+
+        // Step 8
+        // Get vault_contract_addr from res
+        // ------------------------------------------------------------------------------
+        let vault_contract_addr = res.events[3].attributes[0].value.clone();
+
+        // Add e2e test to verify that fees accrue at _sudomod_c_addr
+        // for accepted liquidity requests on vault_contract_addr
+        // ------------------------------------------------------------------------------
+        // ------------------------------------------------------------------------------
+        //
+        // Step 1
+        // Delegate to VALIDATOR_ONE_ADDRESS on vault_contract_addr
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        let delegate_msg = vault_contract::msg::ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount: delegate_amount,
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            Addr::unchecked(vault_contract_addr.clone()),
+            &delegate_msg,
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+
+        // Step 2
+        // Create a valid FixedTermLoan liquidity request on vault_contract_addr
+        // ------------------------------------------------------------------------------
+        let duration_in_seconds = 60u64;
+        let requested_amount = Uint128::new(100_000_000);
+        let expected_liquidity_comission = Uint128::new(300_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            Addr::unchecked(vault_contract_addr.clone()),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 3
+        // Accept the open liquidity request on vault_contract_addr
+        // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg =
+            vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option };
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            Addr::unchecked(vault_contract_addr),
+            &accept_liquidity_request_msg,
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }],
+        )
+        .unwrap();
+
+        // Step 4
+        // Verify that liquidity_comission from vault_contract_addr accrue at sudomod_c_addr
+        // ------------------------------------------------------------------------------
+        // ibc_denom_balance = SUPPLY - requested_amount +  vault_creation_fee
+        let balance = bank_balance(&mut app, &sudomod_c_addr, IBC_DENOM_1.to_string());
+        assert_eq!(
+            balance.amount,
+            vault_creation_fee.amount + expected_liquidity_comission
+        );
+    }
+
+    #[test]
+    fn test_query_minted_vaults() {
+        // Step 1
+        // Create an instance of sudomod and set vault code id
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId { code_id },
+            &[],
+        )
+        .unwrap();
+
+        // Step 2
+        // Mint two vaults for USER and one vault for LENDER
+        // ------------------------------------------------------------------------------
+        let mut vault_addresses = vec![];
+        for owner in [USER, LENDER, USER] {
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(owner),
+                    sudomod_c_addr.clone(),
+                    &ExecuteMsg::MintVault {},
+                    &[],
+                )
+                .unwrap();
+            vault_addresses.push(Addr::unchecked(res.events[3].attributes[0].value.clone()));
+        }
+
+        // Step 3
+        // Verify that VaultByIndex returns the vault info saved by the reply handler
+        // ------------------------------------------------------------------------------
+        let vault_info: VaultInfo = app
+            .wrap()
+            .query_wasm_smart(
+                sudomod_c_addr.clone(),
+                &QueryMsg::VaultByIndex { index_number: 2 },
+            )
+            .unwrap();
+        assert_eq!(
+            vault_info,
+            VaultInfo {
+                index_number: 2,
+                address: vault_addresses[1].clone(),
+                owner: Addr::unchecked(LENDER),
+                code_id,
+                created_at: app.block_info().time,
+            }
+        );
+
+        // Step 4
+        // Verify that VaultsByOwner returns only the vaults owned by USER
+        // ------------------------------------------------------------------------------
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, None, None);
+        assert_eq!(
+            vaults
+                .entries
+                .iter()
+                .map(|v| (v.index_number, v.address.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, vault_addresses[0].clone()),
+                (3, vault_addresses[2].clone())
+            ]
+        );
+
+        // Step 5
+        // Verify that VaultsByOwner paginates with start_after and limit
+        // ------------------------------------------------------------------------------
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, None, Some(1));
+        assert_eq!(vaults.entries.len(), 1);
+        assert_eq!(vaults.entries[0].index_number, 1);
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, Some(1), None);
+        assert_eq!(vaults.entries.len(), 1);
+        assert_eq!(vaults.entries[0].index_number, 3);
+
+        // Step 6
+        // Verify that IsVault is true only for vaults minted by sudomod
+        // ------------------------------------------------------------------------------
+        for (address, expected) in [
+            (vault_addresses[0].to_string(), true),
+            (sudomod_c_addr.to_string(), false),
+            (LENDER.to_string(), false),
+        ] {
+            let res: IsVaultResponse = app
+                .wrap()
+                .query_wasm_smart(sudomod_c_addr.clone(), &QueryMsg::IsVault { address })
+                .unwrap();
+            assert_eq!(res.is_vault, expected);
+        }
+    }
+
+    #[test]
+    fn test_vault_owner_changed() {
+        // Step 1
+        // Create an instance of sudomod, set vault code id and mint a vault for USER
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId { code_id },
+            &[],
+        )
+        .unwrap();
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+
+        // Step 2
+        // Test error case ContractError::Unauthorized {}
+        // When VaultOwnerChanged is called by an address that is not a minted vault
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::VaultOwnerChanged {
+                new_owner: USER.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 3
+        // Transfer ownership of the vault from USER to LENDER
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::TransferOwnership {
+                to_address: LENDER.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 4
+        // Verify that VaultsByOwner reflects the new owner of the vault
+        // ------------------------------------------------------------------------------
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, USER, None, None);
+        assert_eq!(vaults.entries, vec![]);
+        let vaults = get_vaults_by_owner(&mut app, &sudomod_c_addr, LENDER, None, None);
+        assert_eq!(
+            vaults.entries,
+            vec![VaultInfo {
+                index_number: 1,
+                address: vault_c_addr.clone(),
+                owner: Addr::unchecked(LENDER),
+                code_id,
+                created_at: app.block_info().time,
+            }]
+        );
+
+        // Step 5
+        // Verify that only the new owner can upgrade the vault through sudomod
+        // ------------------------------------------------------------------------------
+        app.update_block(|block| {
+            block.time = block.time.plus_seconds(MIN_VAULT_CODE_UPDATE_INTERVAL);
+        });
+        let new_code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId {
+                code_id: new_code_id,
+            },
+            &[],
+        )
+        .unwrap();
+        let upgrade_vault_msg = ExecuteMsg::UpgradeVault {
+            vault_address: vault_c_addr.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_vault() {
+        // Step 1
+        // Create an instance of sudomod, set vault code id and mint a vault
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let old_code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId {
+                code_id: old_code_id,
+            },
+            &[],
+        )
+        .unwrap();
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+        let upgrade_vault_msg = ExecuteMsg::UpgradeVault {
+            vault_address: vault_c_addr.to_string(),
+        };
+
+        // Step 2
+        // Verify that sudomod_c_addr was set as the admin of the vault
+        // ------------------------------------------------------------------------------
+        let contract_info = app
+            .wrap()
+            .query_wasm_contract_info(vault_c_addr.clone())
+            .unwrap();
+        assert_eq!(contract_info.admin, Some(sudomod_c_addr.to_string()));
+
+        // Step 3
+        // Test error case ContractError::VaultAlreadyUpToDate {}
+        // When the vault is already running the latest vault code id
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 4
+        // Set a new vault code id after MIN_VAULT_CODE_UPDATE_INTERVAL
+        // ------------------------------------------------------------------------------
+        app.update_block(|block| {
+            block.time = block.time.plus_seconds(MIN_VAULT_CODE_UPDATE_INTERVAL);
+        });
+        let new_code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId {
+                code_id: new_code_id,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 5
+        // Test error case ContractError::Unauthorized {}
+        // When UpgradeVault is called by a user who is not the vault owner
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 6
+        // Delegate, then open and accept a FixedTermLoan on the vault
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+        let requested_amount = Uint128::new(100_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            }],
+        )
+        .unwrap();
+
+        // Step 7
+        // Test error case ContractError::VaultUpgradeNotApprovedByLender {}
+        // When the vault owner tries to upgrade without the lender's approval
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 8
+        // Test error case ContractError::Unauthorized {}
+        // When ApproveVaultUpgrade is called by a user who is not the active lender
+        // ------------------------------------------------------------------------------
+        let approve_vault_upgrade_msg = ExecuteMsg::ApproveVaultUpgrade {
+            vault_address: vault_c_addr.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &approve_vault_upgrade_msg,
+            &[],
+        )
+        .unwrap_err();
+
+        // Step 9
+        // LENDER approves the upgrade, then the vault owner upgrades the vault
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            sudomod_c_addr.clone(),
+            &approve_vault_upgrade_msg,
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Step 10
+        // Verify that the vault now runs on new_code_id with the active loan intact
+        // ------------------------------------------------------------------------------
+        let contract_info = app
+            .wrap()
+            .query_wasm_contract_info(vault_c_addr.clone())
+            .unwrap();
+        assert_eq!(contract_info.code_id, new_code_id);
+        let vault_info: vault_contract::msg::InfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_c_addr.clone(),
+                &vault_contract::msg::QueryMsg::Info {},
+            )
+            .unwrap();
+        assert_eq!(vault_info.config.from_code_id, new_code_id);
+        assert_eq!(
+            vault_info.liquidity_request.unwrap().lender,
+            Some(Addr::unchecked(LENDER))
+        );
+
+        // Step 11
+        // Test error case ContractError::VaultAlreadyUpToDate {}
+        // When trying to upgrade the vault again
+        // ------------------------------------------------------------------------------
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &upgrade_vault_msg,
+            &[],
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_approve_syndicated_vault_upgrade() {
+        // Step 1
+        // Create an instance of sudomod, set vault code id and mint a vault
+        // ------------------------------------------------------------------------------
+        let mut app = mock_app();
+        let sudomod_c_addr = instantiate_sudomod(&mut app);
+        let code_id = app.store_code(vault_contract_template());
+        app.execute_contract(
+            Addr::unchecked(USER),
+            sudomod_c_addr.clone(),
+            &ExecuteMsg::SetVaultCodeId { code_id },
+            &[],
+        )
+        .unwrap();
+        let res = app
+            .execute_contract(
+                Addr::unchecked(USER),
+                sudomod_c_addr.clone(),
+                &ExecuteMsg::MintVault {},
+                &[],
+            )
+            .unwrap();
+        let vault_c_addr = Addr::unchecked(res.events[3].attributes[0].value.clone());
+
+        // Step 2
+        // Delegate, then open a FixedTermLoan on the vault
+        // ------------------------------------------------------------------------------
+        let delegate_amount = Uint128::new(1_000_000);
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::Delegate {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: delegate_amount,
+            },
+            &[Coin {
+                denom: STAKING_DENOM.into(),
+                amount: delegate_amount,
+            }],
+        )
+        .unwrap();
+        let requested_amount = Uint128::new(100_000);
+        let option = vault_contract::types::LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: delegate_amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        app.execute_contract(
+            Addr::unchecked(USER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::RequestLiquidity {
+                option: option.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Step 3
+        // co_lender funds 60% of the requested_amount, and LENDER accepts the rest
+        // ------------------------------------------------------------------------------
+        let co_lender = Addr::unchecked("co_lender");
+        let co_lender_amount = Uint128::new(60_000);
+        let co_lender_funds = vec![Coin {
+            denom: IBC_DENOM_1.to_string(),
+            amount: co_lender_amount,
+        }];
+        app.send_tokens(Addr::unchecked(USER), co_lender.clone(), &co_lender_funds)
+            .unwrap();
+        app.execute_contract(
+            co_lender.clone(),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::FundLiquidityRequest {
+                option: option.clone(),
+                amount: co_lender_amount,
+            },
+            &co_lender_funds,
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LENDER),
+            vault_c_addr.clone(),
+            &vault_contract::msg::ExecuteMsg::AcceptLiquidityRequest { option },
+            &[Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount - co_lender_amount,
+            }],
+        )
+        .unwrap();
+
+        // Step 4
+        // Test error case ContractError::LenderPositionIsSyndicated {}
+        // When LENDER, holding a minority of the shares, approves the upgrade.
+        // co_lender is not the active lender, so it can not approve either
+        // ------------------------------------------------------------------------------
+        let approve_vault_upgrade_msg = ExecuteMsg::ApproveVaultUpgrade {
+            vault_address: vault_c_addr.to_string(),
+        };
+        for caller in [Addr::unchecked(LENDER), co_lender] {
+            app.execute_contract(
+                caller,
+                sudomod_c_addr.clone(),
+                &approve_vault_upgrade_msg,
+                &[],
+            )
+            .unwrap_err();
+        }
+    }
+
     #[test]
     fn test_transfer_ownership() {
         // Step 1
         // Instantiate contract instance
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let sudomod_c_addr = setup_sudomod(&mut router);
+        let sudomod_c_addr = instantiate_sudomod(&mut router);
 
         // Step 2
         // Test error case  ContractError::Unauthorized {}
@@ -497,7 +2141,7 @@ mod tests {
         // Instantiate contract instance
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let sudomod_c_addr = setup_sudomod(&mut router);
+        let sudomod_c_addr = instantiate_sudomod(&mut router);
 
         // Step 2
         // Send some tokens to sudomod_c_addr
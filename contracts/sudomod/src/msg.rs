@@ -1,5 +1,6 @@
-use crate::state::VaultCodeInfo;
-use cosmwasm_std::Coin;
+use crate::state::{ProtocolParams, VaultCodeInfo, VaultInfo};
+use collection::msg::NftTransferredMsg;
+use cosmwasm_std::{Coin, Decimal};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,11 +18,64 @@ pub enum ExecuteMsg {
     /// when calling MintVault.
     SetVaultCreationFee { amount: Coin },
 
-    /// Ensures that all instances of vaults created from code_id
-    /// is paid for, as only the instance of sudomod contract set as INSTANTIATOR_ADDR in the
-    /// vault contract's source code can call the instantiate method of the VAULT_CONTRACT
+    /// Allows owner_address to set the commission charged on the requested amount
+    /// when a liquidity request is accepted on a vault minted by this contract.
+    /// Vaults snapshot the commission when an option is accepted.
+    SetLiquidityCommission { commission: Decimal },
+
+    /// Allows owner_address to set the address that receives liquidity request commissions.
+    SetFeeCollector { address: String },
+
+    /// Allows owner_address to set the cw721 collection in which lender positions
+    /// on the vaults minted by this contract are minted as tokens.
+    /// This contract must be the minter of the collection.
+    SetLenderPositionCollection { address: String },
+
+    /// Allows owner_address to set the cw721 collection in which the ownership
+    /// of the vaults minted by this contract is minted as tokens.
+    /// This contract must be the minter and the transfer hook of the collection.
+    SetVaultOwnershipCollection { address: String },
+
+    /// Allows owner_address to update the protocol params read by this contract
+    /// and the vaults it mints. Vaults snapshot the params when an option is accepted.
+    UpdateParams { params: ProtocolParams },
+
+    /// Creates a new instance of the vault contract from vault_code_id, owned by info.sender.
+    /// This contract is recorded as the vault's factory and collects its commissions.
+    /// When a vault ownership collection is set, the ownership of the vault is also
+    /// minted as a token to info.sender, and moves with the token from then on.
     MintVault {},
 
+    /// Allows the owner of a vault minted by this contract to migrate it
+    /// to the latest vault_code_id.
+    /// Vaults with an active liquidity request must first be approved by the lender.
+    UpgradeVault { vault_address: String },
+
+    /// Allows the lender on a vault's active liquidity request to co-sign
+    /// an upgrade of the vault to the latest vault_code_id.
+    ApproveVaultUpgrade { vault_address: String },
+
+    /// Called by a vault minted by this contract after its ownership is transferred,
+    /// either through TransferOwnership or the vault ownership collection,
+    /// so that VaultsByOwner stays in sync with the vault's owner.
+    VaultOwnerChanged { new_owner: String },
+
+    /// Called by the vault ownership collection whenever one of its tokens changes owner,
+    /// so that VaultsByOwner follows the token without waiting for the vault to sync.
+    /// Transfers of lender position tokens are ignored.
+    NftTransferred(NftTransferredMsg),
+
+    /// Called by a vault minted by this contract when a liquidity request is accepted,
+    /// to mint token_id of the lender position collection to lender.
+    LenderPositionOpened { token_id: String, lender: String },
+
+    /// Called by a vault minted by this contract once the option represented by
+    /// token_id of collection closes, to burn the token.
+    LenderPositionClosed {
+        collection: String,
+        token_id: String,
+    },
+
     /// Allows owner_address to withdraw funds from the contract account.
     WithdrawBalance {
         to_address: Option<String>,
@@ -39,14 +93,40 @@ pub enum QueryMsg {
     /// Returns Config
     Info {},
 
+    /// Returns ProtocolParams
+    Params {},
+
     /// Returns VaultCodeListResponse
     QueryVaultCodeList {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+
+    /// Returns VaultListResponse
+    VaultsByOwner {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns VaultInfo
+    VaultByIndex { index_number: u64 },
+
+    /// Returns IsVaultResponse
+    IsVault { address: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VaultCodeListResponse {
     pub entries: Vec<VaultCodeInfo>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultListResponse {
+    pub entries: Vec<VaultInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsVaultResponse {
+    pub is_vault: bool,
+}
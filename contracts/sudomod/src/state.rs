@@ -1,5 +1,5 @@
-use cosmwasm_std::{Addr, Coin, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +8,50 @@ pub struct Config {
     pub owner: Addr,
     pub vault_code_info_updated_at: Option<Timestamp>,
     pub vault_creation_fee: Option<Coin>,
+    pub liquidity_commission: Decimal,
+    pub fee_collector: Addr,
+    /// cw721 collection minted by this contract, holding the lender positions
+    /// of the liquidity requests accepted on its vaults
+    #[serde(default)]
+    pub lender_position_collection: Option<Addr>,
+    /// cw721 collection minted by this contract, holding the ownership of its vaults
+    #[serde(default)]
+    pub vault_ownership_collection: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProtocolParams {
+    /// Unbonding period in seconds of the staking module, passed to the vaults minted by sudomod
+    #[serde(default = "default_unbonding_period")]
+    pub unbonding_period: u64,
+    /// Maximum number of counter offers kept by a vault
+    pub max_counter_offers: u32,
+    /// Minimum duration in seconds between calls to SetVaultCodeId
+    pub min_vault_code_update_interval: u64,
+    /// Limits for the custom range queries on this contract
+    pub max_limit: u32,
+    pub default_limit: u32,
+    /// Maximum keeper_fee in bonded_denom a loan can pay to third parties
+    /// calling LiquidateCollateral on a vault
+    #[serde(default)]
+    pub max_keeper_fee: Uint128,
+}
+
+fn default_unbonding_period() -> u64 {
+    UNBONDING_PERIOD
+}
+
+impl Default for ProtocolParams {
+    fn default() -> Self {
+        ProtocolParams {
+            unbonding_period: UNBONDING_PERIOD,
+            max_counter_offers: MAX_COUNTER_OFFERS,
+            min_vault_code_update_interval: MIN_VAULT_CODE_UPDATE_INTERVAL,
+            max_limit: MAX_LIMIT,
+            default_limit: DEFAULT_LIMIT,
+            max_keeper_fee: MAX_KEEPER_FEE,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -16,6 +60,21 @@ pub struct VaultCodeInfo {
     pub code_id: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultUpgradeApproval {
+    pub lender: Addr,
+    pub code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultInfo {
+    pub index_number: u64,
+    pub address: Addr,
+    pub owner: Addr,
+    pub code_id: u64,
+    pub created_at: Timestamp,
+}
+
 // contract info
 pub const CONTRACT_NAME: &str = "sudomod";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -23,6 +82,15 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 // Stores the config variables during initialization of the contract
 pub const CONFIG: Item<Config> = Item::new("CONFIG");
 
+// Stores the protocol params read by this contract and the vaults it mints
+pub const PARAMS: Item<ProtocolParams> = Item::new("PARAMS");
+
+// Commission charged on liquidity requests accepted by vaults minted by this contract, 0.3%
+pub const DEFAULT_LIQUIDITY_COMMISSION: Decimal = Decimal::raw(3_000_000_000_000_000);
+
+// Maximum commission the owner can charge on liquidity requests, 5%
+pub const MAX_LIQUIDITY_COMMISSION: Decimal = Decimal::raw(50_000_000_000_000_000);
+
 // Keeps track of a mapping between the VAULT_CODE_SEQ : VaultCodeInfo
 pub const VAULT_CODE_LIST: Map<u64, VaultCodeInfo> = Map::new("VAULT_CODE_LIST");
 
@@ -32,9 +100,47 @@ pub const VAULT_CODE_SEQ: Item<u64> = Item::new("VAULT_CODE_SEQ");
 // Keeps count of vaults instantiated by this contract
 pub const VAULT_INSTANTIATION_SEQ: Item<u64> = Item::new("VAULT_INSTANTIATION_SEQ");
 
-// This is the minimum duration in seconds between calls to SetVaultCodeId
-pub const MIN_VAULT_CODE_UPDATE_INTERVAL: u64 = 60 * 60 * 24 * 30;
+// Keeps track of the lender approvals for upgrading vaults with an active liquidity request
+pub const VAULT_UPGRADE_APPROVALS: Map<&Addr, VaultUpgradeApproval> =
+    Map::new("VAULT_UPGRADE_APPROVALS");
+
+// Reply id for the submessage that instantiates a new vault
+pub const INSTANTIATE_VAULT_REPLY_ID: u64 = 1u64;
+
+// Define the indexes for vaults minted by this contract
+pub struct VaultIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, VaultInfo, u64>,
+    pub address: UniqueIndex<'a, Addr, VaultInfo, u64>,
+}
+
+// Implement IndexList for VaultIndexes
+impl<'a> IndexList<VaultInfo> for VaultIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<VaultInfo>> + '_> {
+        let v: Vec<&dyn Index<VaultInfo>> = vec![&self.owner, &self.address];
+        Box::new(v.into_iter())
+    }
+}
 
-// Limits for the custom range query for VAULT_CODE_LIST
+// Keeps track of a mapping between the index_number : VaultInfo
+// for every vault minted by this contract
+pub fn vault_list<'a>() -> IndexedMap<'a, u64, VaultInfo, VaultIndexes<'a>> {
+    IndexedMap::new(
+        "VAULTS",
+        VaultIndexes {
+            owner: MultiIndex::new(|_pk, d| d.owner.clone(), "VAULTS", "VAULTS__owner"),
+            address: UniqueIndex::new(|d| d.address.clone(), "VAULTS__address"),
+        },
+    )
+}
+
+// Default ProtocolParams set during initialization of the contract
+pub const UNBONDING_PERIOD: u64 = 60 * 60 * 24 * 21;
+pub const MAX_COUNTER_OFFERS: u32 = 10;
+pub const MIN_VAULT_CODE_UPDATE_INTERVAL: u64 = 60 * 60 * 24 * 30;
 pub const MAX_LIMIT: u32 = 36;
 pub const DEFAULT_LIMIT: u32 = 12;
+pub const MAX_KEEPER_FEE: Uint128 = Uint128::zero();
+
+// Upper bound for ProtocolParams.max_counter_offers,
+// as vaults refund every counter offer in a single transaction
+pub const MAX_COUNTER_OFFERS_UPPER_BOUND: u32 = 50;
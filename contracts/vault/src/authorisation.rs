@@ -1,4 +1,5 @@
 use crate::{
+    helpers,
     state::{CONFIG, LIQUIDITY_REQUEST_STATE},
     types::{ActionTypes, ActiveOption, LiquidityRequestStatus},
     ContractError,
@@ -6,7 +7,7 @@ use crate::{
 use cosmwasm_std::{Addr, DepsMut};
 
 // Applies to owner of vault
-const OWNER_AUTHORIZATIONS: [ActionTypes; 12] = [
+const OWNER_AUTHORIZATIONS: [ActionTypes; 16] = [
     ActionTypes::Delegate,
     ActionTypes::Redelegate,
     ActionTypes::Undelegate(LiquidityRequestStatus::Closed),
@@ -15,26 +16,46 @@ const OWNER_AUTHORIZATIONS: [ActionTypes; 12] = [
     ActionTypes::ClosePendingLiquidityRequest(LiquidityRequestStatus::Pending),
     ActionTypes::TransferOwnership,
     ActionTypes::RepayLoan(LiquidityRequestStatus::Active),
+    ActionTypes::ProposeLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::AcceptLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::TopUpCollateral(LiquidityRequestStatus::Active),
     ActionTypes::ClaimDelegatorRewards,
     ActionTypes::LiquidateCollateral(LiquidityRequestStatus::Active),
     ActionTypes::WithdrawBalance,
+    ActionTypes::SetUnbondingStrategy,
     ActionTypes::Vote,
 ];
 
 // Applies to the active lenders on the vault
-const LENDER_AUTHORIZATIONS: [ActionTypes; 4] = [
+const LENDER_AUTHORIZATIONS: [ActionTypes; 8] = [
     ActionTypes::Redelegate,
     ActionTypes::ClaimDelegatorRewards,
     ActionTypes::LiquidateCollateral(LiquidityRequestStatus::Active),
+    ActionTypes::ProposeLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::AcceptLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::MarginCall(LiquidityRequestStatus::Active),
+    ActionTypes::TransferLenderPosition(LiquidityRequestStatus::Active),
     ActionTypes::Vote,
 ];
 
-// Applies to any user trying to lend to the pending liquidity request option
-const OPEN_AUTHORIZATIONS: [ActionTypes; 4] = [
+// Applies to the active lender only while they hold every share of the option
+const SOLE_LENDER_AUTHORIZATIONS: [ActionTypes; 4] = [
+    ActionTypes::ProposeLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::AcceptLoanExtension(LiquidityRequestStatus::Active),
+    ActionTypes::MarginCall(LiquidityRequestStatus::Active),
+    ActionTypes::TransferLenderPosition(LiquidityRequestStatus::Active),
+];
+
+// Applies to any user trying to lend to the pending liquidity request option,
+// or to drive the liquidation of a past due loan
+const OPEN_AUTHORIZATIONS: [ActionTypes; 7] = [
     ActionTypes::AcceptLiquidityRequest(LiquidityRequestStatus::Pending),
+    ActionTypes::FundLiquidityRequest(LiquidityRequestStatus::Pending),
+    ActionTypes::WithdrawContribution(LiquidityRequestStatus::Pending),
     ActionTypes::OpenCounterOffer(LiquidityRequestStatus::Pending),
     ActionTypes::UpdateCounterOffer(LiquidityRequestStatus::Pending),
     ActionTypes::CancelCounterOffer(LiquidityRequestStatus::Pending),
+    ActionTypes::LiquidateCollateral(LiquidityRequestStatus::Active),
 ];
 
 pub fn authorize(
@@ -56,6 +77,9 @@ pub fn authorize(
     }) = liquidity_request
     {
         if caller.eq(&lender) && LENDER_AUTHORIZATIONS.contains(&action_type) {
+            if SOLE_LENDER_AUTHORIZATIONS.contains(&action_type) {
+                helpers::ensure_sole_lender(deps.storage)?;
+            }
             return Ok(());
         }
     }
@@ -1,42 +1,42 @@
 use crate::authorisation::authorize;
 use crate::error::ContractError;
 use crate::helpers;
+use crate::migrations;
 use crate::msg::{
     AllDelegationsResponse, CounterOfferListResponse, ExecuteMsg, InfoResponse, InstantiateMsg,
-    QueryMsg, StakingInfoResponse,
+    LenderSharesResponse, LoanHealthResponse, MigrateMsg, NextLiquidationStepResponse,
+    OraclePriceResponse, OracleQueryMsg, PendingUnbondingsResponse, QueryMsg,
+    SimulateRepaymentResponse, StakingInfoResponse, SudomodHookMsg,
 };
 use crate::state::counter_offer_list;
 use crate::types::CounterOfferOperator;
 use crate::{
-    state::{
-        CONFIG, CONTRACT_NAME, CONTRACT_VERSION, INSTANTIATOR_ADDR, LIQUIDITY_REQUEST_STATE,
-        STAKE_LIQUIDATION_INTERVAL,
-    },
+    state::{CONFIG, CONTRACT_NAME, CONTRACT_VERSION, LENDER_SHARES, LIQUIDITY_REQUEST_STATE},
     types::{
-        ActionTypes, ActiveOption, Config, CounterOfferProposal, LiquidityRequestMsg,
-        LiquidityRequestState,
+        ActionTypes, ActiveOption, Config, CounterOfferProposal, LiquidationPayees,
+        LiquidityRequestMsg, LiquidityRequestState, LoanExtensionProposal, OwnershipToken,
+        UnbondingStrategy,
     },
 };
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Addr, Binary, Coin, Deps, DepsMut, Env, GovMsg, MessageInfo,
-    Order, Response, StakingMsg, StdError, StdResult, Uint128, VoteOption,
+    attr, entry_point, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Env, GovMsg,
+    MessageInfo, Order, Response, StakingMsg, StdError, StdResult, Timestamp, Uint128, VoteOption,
 };
 
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    // Verify that info.sender.eq(INSTANTIATOR_ADDR)
-    if _info.sender.to_string().ne(INSTANTIATOR_ADDR) {
-        return Err(ContractError::Unauthorized {});
-    }
-
     // Validate the owner_address
     let owner = deps.api.addr_validate(&msg.owner_address)?;
 
+    // Validate the factory_address
+    let factory = deps.api.addr_validate(&msg.factory_address)?;
+    helpers::validate_factory(&deps, &env, &info, &factory)?;
+
     // Store the contract name and version
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -48,6 +48,10 @@ pub fn instantiate(
             owner,
             from_code_id: msg.from_code_id,
             index_number: msg.index_number,
+            factory,
+            unbonding_strategy: UnbondingStrategy::default(),
+            unbonding_period: msg.unbonding_period,
+            ownership_token: None,
         },
     )?;
 
@@ -57,6 +61,37 @@ pub fn instantiate(
 
 #[entry_point]
 pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    _msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // The owners of the ownership and lender position tokens, if any,
+    // act as the vault owner and the lender on the active option
+    let owner_changed_msg = helpers::sync_vault_owner(&mut deps)?;
+    if helpers::is_lender_msg(&_msg) {
+        helpers::sync_lender_position(&mut deps)?;
+    }
+    let lender_position = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .and_then(|option| option.lender_position);
+
+    let mut response = dispatch_execute_msg(deps.branch(), env, _info, _msg)?;
+    if let Some(hook_msg) = owner_changed_msg {
+        response = response.add_message(hook_msg);
+    }
+
+    // Burn the lender position token once the option it represents is closed
+    if let Some(position) = lender_position {
+        if let Some(hook_msg) = helpers::get_lender_position_closed_msg(&deps, position)? {
+            response = response.add_message(hook_msg);
+        }
+    }
+
+    Ok(response)
+}
+
+fn dispatch_execute_msg(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
@@ -112,16 +147,20 @@ pub fn execute(
         }
 
         ExecuteMsg::CancelCounterOffer {} => {
-            // Respond
-            Ok(Response::new())
+            let action_type =
+                ActionTypes::CancelCounterOffer(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_cancel_counter_offer(deps, &_info)
         }
 
         ExecuteMsg::AcceptCounterOffer {
             amount,
             proposed_by_address,
         } => {
-            // Respond
-            Ok(Response::new())
+            let action_type =
+                ActionTypes::AcceptCounterOffer(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_accept_counter_offer(deps, env, amount, proposed_by_address)
         }
 
         ExecuteMsg::ClosePendingLiquidityRequest {} => {
@@ -140,23 +179,69 @@ pub fn execute(
             execute_accept_liquidity_request(deps, env, &_info)
         }
 
+        ExecuteMsg::FundLiquidityRequest { option, amount } => {
+            let action_type =
+                ActionTypes::FundLiquidityRequest(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            helpers::ensure_option_is_exact_match(&deps, option)?;
+            execute_fund_liquidity_request(deps, env, &_info, amount)
+        }
+
+        ExecuteMsg::WithdrawContribution {} => {
+            let action_type =
+                ActionTypes::WithdrawContribution(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_withdraw_contribution(deps, &_info)
+        }
+
         ExecuteMsg::ClaimDelegatorRewards {} => {
             let action_type = ActionTypes::ClaimDelegatorRewards {};
             authorize(&deps, _info.sender.clone(), action_type)?;
             execute_claim_delegator_rewards(deps, env)
         }
 
-        ExecuteMsg::RepayLoan {} => {
+        ExecuteMsg::RepayLoan { amount } => {
             let action_type = ActionTypes::RepayLoan(helpers::get_liquidity_request_status(&deps)?);
             authorize(&deps, _info.sender.clone(), action_type)?;
-            execute_repay_loan(deps, env)
+            execute_repay_loan(deps, env, amount)
+        }
+
+        ExecuteMsg::ProposeLoanExtension {
+            new_end_time,
+            additional_interest,
+        } => {
+            let action_type =
+                ActionTypes::ProposeLoanExtension(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_propose_loan_extension(deps, env, &_info, new_end_time, additional_interest)
+        }
+
+        ExecuteMsg::AcceptLoanExtension {} => {
+            let action_type =
+                ActionTypes::AcceptLoanExtension(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_accept_loan_extension(deps, env, &_info)
+        }
+
+        ExecuteMsg::MarginCall {} => {
+            let action_type =
+                ActionTypes::MarginCall(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_margin_call(deps, env)
+        }
+
+        ExecuteMsg::TopUpCollateral { amount } => {
+            let action_type =
+                ActionTypes::TopUpCollateral(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_top_up_collateral(deps, env, amount)
         }
 
         ExecuteMsg::LiquidateCollateral {} => {
             let action_type =
                 ActionTypes::LiquidateCollateral(helpers::get_liquidity_request_status(&deps)?);
             authorize(&deps, _info.sender.clone(), action_type)?;
-            execute_liquidate_collateral(deps, env)
+            execute_liquidate_collateral(deps, env, &_info)
         }
 
         ExecuteMsg::TransferOwnership { to_address } => {
@@ -168,6 +253,18 @@ pub fn execute(
             execute_transfer_ownership(deps, to_address)
         }
 
+        ExecuteMsg::TokenizeOwnership {
+            collection,
+            token_id,
+        } => execute_tokenize_ownership(deps, &_info, collection, token_id),
+
+        ExecuteMsg::TransferLenderPosition { to_address } => {
+            let action_type =
+                ActionTypes::TransferLenderPosition(helpers::get_liquidity_request_status(&deps)?);
+            authorize(&deps, _info.sender.clone(), action_type)?;
+            execute_transfer_lender_position(deps, &_info, to_address)
+        }
+
         ExecuteMsg::Vote { proposal_id, vote } => {
             authorize(&deps, _info.sender.clone(), ActionTypes::Vote {})?;
             execute_vote(deps, env, &_info, proposal_id, vote)
@@ -177,6 +274,15 @@ pub fn execute(
             authorize(&deps, _info.sender.clone(), ActionTypes::WithdrawBalance)?;
             execute_withdraw_balance(deps, env, to_address, funds)
         }
+
+        ExecuteMsg::SetUnbondingStrategy { strategy } => {
+            authorize(
+                &deps,
+                _info.sender.clone(),
+                ActionTypes::SetUnbondingStrategy,
+            )?;
+            execute_set_unbonding_strategy(deps, strategy)
+        }
     }
 }
 
@@ -203,6 +309,9 @@ pub fn execute_delegate(
     // Process lender claims on claimed accumulated staking rewards from validator
     if let Some(ActiveOption {
         msg: _,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: Some(lender),
         state: Some(state),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
@@ -213,12 +322,10 @@ pub fn execute_delegate(
         if !accumulated_rewards.is_zero() {
             response = response.add_messages(distribute_msgs);
 
-            // Add msg for sending claimed rewards to the lender
-            if let Some(transfer_msgs) =
-                helpers::process_lender_claims(deps, &env, state, lender, accumulated_rewards)?
-            {
-                response = response.add_message(transfer_msgs);
-            }
+            // Add msgs for sending claimed rewards to the lenders
+            let transfer_msgs =
+                helpers::process_lender_claims(deps, &env, state, lender, accumulated_rewards)?;
+            response = response.add_messages(transfer_msgs);
         }
     }
 
@@ -262,6 +369,9 @@ pub fn execute_redelegate(
     // Process lender claims on claimed accumulated staking rewards from src_validator
     if let Some(ActiveOption {
         msg: _,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: Some(lender),
         state: Some(state),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
@@ -275,12 +385,10 @@ pub fn execute_redelegate(
         if !accumulated_rewards.is_zero() {
             response = response.add_messages(distribute_msgs);
 
-            // Add msg for sending claimed rewards to the lender
-            if let Some(transfer_msgs) =
-                helpers::process_lender_claims(deps, &env, state, lender, accumulated_rewards)?
-            {
-                response = response.add_message(transfer_msgs);
-            }
+            // Add msgs for sending claimed rewards to the lenders
+            let transfer_msgs =
+                helpers::process_lender_claims(deps, &env, state, lender, accumulated_rewards)?;
+            response = response.add_messages(transfer_msgs);
         }
     }
 
@@ -324,14 +432,24 @@ pub fn execute_undelegate(
         }
     }
 
-    // Create sdk_msg for un-staking tokens
+    // Create sdk_msg for un-staking tokens, and record the unbonding
+    // unless the validator has reached MAX_UNBONDING_ENTRIES
     let denom_str = deps.querier.query_bonded_denom()?;
+    let unbonding_amount = Coin {
+        denom: denom_str,
+        amount,
+    };
+    let unbonding_period = CONFIG.load(deps.storage)?.unbonding_period;
+    helpers::record_unbonding(
+        deps.storage,
+        &env,
+        &validator,
+        unbonding_amount.clone(),
+        unbonding_period,
+    )?;
     let sdk_msg = StakingMsg::Undelegate {
         validator: validator.clone(),
-        amount: Coin {
-            denom: denom_str,
-            amount,
-        },
+        amount: unbonding_amount,
     };
 
     // Respond
@@ -347,6 +465,18 @@ pub fn execute_request_liquidity(
     env: Env,
     option: LiquidityRequestMsg,
 ) -> Result<Response, ContractError> {
+    // Validate that the keeper_fee does not exceed the factory's max_keeper_fee
+    let keeper_fee = helpers::get_keeper_fee(&option);
+    if !keeper_fee.is_zero() {
+        let max_keeper_fee = helpers::query_protocol_params(&deps)?.max_keeper_fee;
+        if keeper_fee > max_keeper_fee {
+            return Err(ContractError::KeeperFeeTooHigh {
+                keeper_fee,
+                max: max_keeper_fee,
+            });
+        }
+    }
+
     // Validate liquidity request message to ensue that the correct data
     // was sent by the caller
     match option.clone() {
@@ -374,18 +504,95 @@ pub fn execute_request_liquidity(
             requested_amount,
             duration_in_seconds,
             collateral_amount,
-            interest_amount: _,
+            interest_amount,
+            prorate_interest_on_early_repayment: _,
+            min_interest_amount,
+            grace_period_in_seconds: _,
+            late_fee_per_day: _,
+            keeper_fee: _,
+        } => {
+            if helpers::query_total_delegations(&deps, &env)? < collateral_amount
+                || collateral_amount.is_zero()
+                || requested_amount.amount.is_zero()
+                || duration_in_seconds == 0u64
+                || min_interest_amount.unwrap_or_default() > interest_amount
+            {
+                return Err(ContractError::InvalidLiquidityRequestOption {});
+            }
+        }
+
+        LiquidityRequestMsg::InstallmentLoan {
+            requested_amount,
+            collateral_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            installment_amount,
+            ..
+        } => {
+            // The installments must at least pay back the requested_amount
+            let total_installments = installment_amount
+                .checked_mul(Uint128::from(number_of_installments))
+                .map_err(|_| ContractError::InvalidLiquidityRequestOption {})?;
+            if helpers::query_total_delegations(&deps, &env)? < collateral_amount
+                || collateral_amount.is_zero()
+                || requested_amount.amount.is_zero()
+                || number_of_installments == 0
+                || installment_period_in_seconds == 0u64
+                || total_installments < requested_amount.amount
+            {
+                return Err(ContractError::InvalidLiquidityRequestOption {});
+            }
+        }
+
+        LiquidityRequestMsg::RewardsBackedLoan {
+            requested_amount,
+            collateral_amount,
+            duration_in_seconds,
+            exchange_rate,
+            ..
+        } => {
+            if helpers::query_total_delegations(&deps, &env)? < collateral_amount
+                || collateral_amount.is_zero()
+                || requested_amount.amount.is_zero()
+                || duration_in_seconds == 0u64
+                || exchange_rate.is_zero()
+            {
+                return Err(ContractError::InvalidLiquidityRequestOption {});
+            }
+        }
+
+        LiquidityRequestMsg::LtvLoan {
+            requested_amount,
+            collateral_amount,
+            duration_in_seconds,
+            oracle,
+            margin_call_ltv,
+            ..
         } => {
             if helpers::query_total_delegations(&deps, &env)? < collateral_amount
                 || collateral_amount.is_zero()
                 || requested_amount.amount.is_zero()
                 || duration_in_seconds == 0u64
+                || margin_call_ltv.is_zero()
+                || margin_call_ltv > Decimal::one()
             {
                 return Err(ContractError::InvalidLiquidityRequestOption {});
             }
+
+            // Ensure the oracle can price the collateral in requested_amount.denom
+            let _: OraclePriceResponse = deps.querier.query_wasm_smart(
+                deps.api.addr_validate(&oracle)?,
+                &OracleQueryMsg::Price {
+                    base: deps.querier.query_bonded_denom()?,
+                    quote: requested_amount.denom,
+                },
+            )?;
         }
     };
 
+    // Clear the lender shares recorded for the previous liquidity request
+    LENDER_SHARES.clear(deps.storage);
+
     // Save liquidity request message
     LIQUIDITY_REQUEST_STATE.save(
         deps.storage,
@@ -393,6 +600,9 @@ pub fn execute_request_liquidity(
             lender: None,
             state: None,
             msg: option,
+            liquidity_commission: None,
+            lender_position: None,
+            protocol_params: None,
         }),
     )?;
 
@@ -442,9 +652,14 @@ pub fn execute_open_counter_offer(
         },
     )?;
 
+    // Reserve the escrowed funds on behalf of the proposer
+    helpers::reserve_balance(deps.storage, &requested_amount.denom, new_amount)?;
+
     // If there is a removed offer, refund the proposer
-    let removed_offer = helpers::prune_lowest_offer(deps.storage)?;
+    let max_counter_offers = helpers::query_protocol_params(&deps)?.max_counter_offers;
+    let removed_offer = helpers::prune_lowest_offer(deps.storage, max_counter_offers)?;
     if let Some(proposal) = removed_offer {
+        helpers::release_balance(deps.storage, &requested_amount.denom, proposal.amount)?;
         response = response.add_message(helpers::get_bank_transfer_to_msg(
             &proposal.proposer,
             &requested_amount.denom,
@@ -506,9 +721,11 @@ pub fn execute_update_counter_offer(
 
         match operator {
             CounterOfferOperator::Add {} => {
-                helpers::validate_exact_input_amount(&info.funds, by_amount, denom)?;
+                helpers::validate_exact_input_amount(&info.funds, by_amount, denom.clone())?;
+                helpers::reserve_balance(deps.storage, &denom, by_amount)?;
             }
             CounterOfferOperator::Sub {} => {
+                helpers::release_balance(deps.storage, &denom, by_amount)?;
                 let refund_msg = helpers::get_bank_transfer_to_msg(&info.sender, &denom, by_amount);
                 response = response.add_message(refund_msg);
             }
@@ -526,10 +743,87 @@ pub fn execute_update_counter_offer(
     Ok(response.add_attribute("method", "update_counter_offer"))
 }
 
+pub fn execute_cancel_counter_offer(
+    deps: DepsMut,
+    info: &MessageInfo,
+) -> Result<Response, ContractError> {
+    // Load caller's current offer
+    let caller_current_offer = counter_offer_list().load(deps.storage, info.sender.clone())?;
+
+    // Remove the caller's offer from the list of offers
+    counter_offer_list().remove(deps.storage, info.sender.clone())?;
+
+    // Refund the escrowed amount in the requested denom to the proposer
+    let option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let requested_amount = helpers::get_requested_amount(option.msg);
+    helpers::release_balance(
+        deps.storage,
+        &requested_amount.denom,
+        caller_current_offer.amount,
+    )?;
+    let refund_msg = helpers::get_bank_transfer_to_msg(
+        &caller_current_offer.proposer,
+        &requested_amount.denom,
+        caller_current_offer.amount,
+    );
+
+    // Respond
+    Ok(Response::new().add_message(refund_msg).add_attributes(vec![
+        attr("method", "cancel_counter_offer"),
+        attr("amount", caller_current_offer.amount.to_string()),
+    ]))
+}
+
+pub fn execute_accept_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    amount: Uint128,
+    proposed_by_address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Ensure the counter offer on record matches the amount accepted by the vault owner
+    let proposer = deps.api.addr_validate(&proposed_by_address)?;
+    let proposal = helpers::ensure_counter_offer_is_exact_match(&deps, proposer, amount)?;
+
+    // Update the requested_amount on the pending option to match the accepted offer
+    LIQUIDITY_REQUEST_STATE.update(deps.storage, |data| -> Result<_, ContractError> {
+        let mut option = data.unwrap();
+        option.msg = helpers::set_requested_amount(option.msg, proposal.amount);
+        Ok(Some(option))
+    })?;
+    let requested_amount =
+        helpers::get_requested_amount(LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap().msg);
+
+    // The accepted offer funds the whole requested_amount, so we refund every contribution
+    // and move the offer held by the vault to the proposer's share
+    let refund_msgs = helpers::refund_all_lender_shares(deps.storage, &requested_amount.denom)?;
+    counter_offer_list().remove(deps.storage, proposal.proposer.clone())?;
+    helpers::release_balance(deps.storage, &requested_amount.denom, proposal.amount)?;
+    helpers::add_lender_share(
+        deps.storage,
+        &proposal.proposer,
+        &requested_amount.denom,
+        proposal.amount,
+    )?;
+    let response = start_funded_liquidity_request(deps, env)?;
+
+    // respond
+    Ok(response.add_messages(refund_msgs).add_attributes(vec![
+        attr("method", "accept_counter_offer"),
+        attr("amount", requested_amount.amount.to_string()),
+        attr("lender", proposal.proposer.to_string()),
+        attr("vault_owner", config.owner.to_string()),
+    ]))
+}
+
 pub fn execute_close_pending_liquidity_request(deps: DepsMut) -> Result<Response, ContractError> {
     // If the liquidity request is already active, we return LiquidityRequestIsActive
     if let Some(ActiveOption {
         msg: _,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: Some(_lender),
         state: Some(_state),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
@@ -537,13 +831,25 @@ pub fn execute_close_pending_liquidity_request(deps: DepsMut) -> Result<Response
         return Err(ContractError::LiquidityRequestIsActive {});
     }
 
+    // Refund every counter offer and contribution made to the pending liquidity request
+    let option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let requested_amount = helpers::get_requested_amount(option.msg);
+    let mut refund_msgs =
+        helpers::refund_all_counter_offers(deps.storage, &requested_amount.denom)?;
+    refund_msgs.extend(helpers::refund_all_lender_shares(
+        deps.storage,
+        &requested_amount.denom,
+    )?);
+
     // Clear the pending liquidity request
     LIQUIDITY_REQUEST_STATE.update(deps.storage, |mut _data| -> Result<_, ContractError> {
         Ok(None)
     })?;
 
     // respond
-    Ok(Response::new().add_attributes(vec![attr("method", "close_liquidity_request")]))
+    Ok(Response::new()
+        .add_messages(refund_msgs)
+        .add_attributes(vec![attr("method", "close_liquidity_request")]))
 }
 
 pub fn execute_accept_liquidity_request(
@@ -551,18 +857,108 @@ pub fn execute_accept_liquidity_request(
     env: Env,
     info: &MessageInfo,
 ) -> Result<Response, ContractError> {
-    let mut response = Response::new();
     let config = CONFIG.load(deps.storage)?;
-    let (state, requested_amount) = helpers::map_liquidity_request_state(&deps, &env)?;
+    let option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let requested_amount = helpers::get_requested_amount(option.msg);
 
-    // Verify that the lender is sending the correct requested amount
+    // Verify that the lender is sending the part of the requested amount
+    // not yet funded by other lenders
+    let remaining_amount = requested_amount.amount - helpers::get_total_funded(deps.storage)?;
     helpers::validate_exact_input_amount(
         &info.funds,
-        requested_amount.amount,
+        remaining_amount,
         requested_amount.denom.clone(),
     )?;
 
-    // When the liquidity request option of type fixed term rental,
+    // Record the lender's share, and start the fully funded option
+    helpers::add_lender_share(
+        deps.storage,
+        &info.sender,
+        &requested_amount.denom,
+        remaining_amount,
+    )?;
+    let response = start_funded_liquidity_request(deps, env)?;
+
+    // respond
+    Ok(response.add_attributes(vec![
+        attr("method", "accept_liquidity_request"),
+        attr("amount", requested_amount.amount.to_string()),
+        attr("vault_owner", config.owner.to_string()),
+    ]))
+}
+
+pub fn execute_fund_liquidity_request(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let requested_amount = helpers::get_requested_amount(option.msg);
+
+    // Ensure the contribution does not exceed the part of the requested amount not yet funded
+    let total_funded = helpers::get_total_funded(deps.storage)?;
+    let remaining_amount = requested_amount.amount - total_funded;
+    if amount.is_zero() || amount > remaining_amount {
+        return Err(ContractError::InvalidContributionAmount {
+            amount,
+            remaining: Coin {
+                amount: remaining_amount,
+                denom: requested_amount.denom,
+            },
+        });
+    }
+
+    // Ensure caller is sending in the correct amount of funds for the contribution
+    helpers::validate_exact_input_amount(&info.funds, amount, requested_amount.denom.clone())?;
+
+    // Record the lender's share, and start the option once it is fully funded
+    helpers::add_lender_share(deps.storage, &info.sender, &requested_amount.denom, amount)?;
+    let response = if amount == remaining_amount {
+        start_funded_liquidity_request(deps, env)?
+    } else {
+        Response::new()
+    };
+
+    // respond
+    Ok(response.add_attributes(vec![
+        attr("method", "fund_liquidity_request"),
+        attr("amount", amount.to_string()),
+        attr("total_funded", (total_funded + amount).to_string()),
+    ]))
+}
+
+pub fn execute_withdraw_contribution(
+    deps: DepsMut,
+    info: &MessageInfo,
+) -> Result<Response, ContractError> {
+    // Load and remove caller's contribution
+    let amount = LENDER_SHARES.load(deps.storage, &info.sender)?;
+    LENDER_SHARES.remove(deps.storage, &info.sender);
+
+    // Refund the escrowed amount in the requested denom to the lender
+    let option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let requested_amount = helpers::get_requested_amount(option.msg);
+    helpers::release_balance(deps.storage, &requested_amount.denom, amount)?;
+    let refund_msg =
+        helpers::get_bank_transfer_to_msg(&info.sender, &requested_amount.denom, amount);
+
+    // Respond
+    Ok(Response::new().add_message(refund_msg).add_attributes(vec![
+        attr("method", "withdraw_contribution"),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+/// Starts the pending option once LENDER_SHARES covers its requested_amount,
+/// with its lead lender as lender,
+/// releasing the contributions to the vault owner and refunding every counter offer
+fn start_funded_liquidity_request(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut response = Response::new();
+    let lender = &helpers::get_lead_lender(deps.storage)?;
+    let (state, requested_amount) = helpers::map_liquidity_request_state(&deps, &env)?;
+
+    // When the liquidity request option of type fixed term rental or rewards backed loan,
     // We claim all pending staking rewards for the vault owner before the option starts counting.
     if let LiquidityRequestState::FixedTermRental {
         requested_amount: _,
@@ -570,7 +966,8 @@ pub fn execute_accept_liquidity_request(
         end_time: _,
         last_claim_time: _,
         can_cast_vote: _,
-    } = state
+    }
+    | LiquidityRequestState::RewardsBackedLoan { .. } = state
     {
         let (total_rewards_claimed, distribute_msgs) =
             helpers::accumulated_rewards(&deps, &env, None)?;
@@ -579,6 +976,20 @@ pub fn execute_accept_liquidity_request(
         }
     }
 
+    // Get the commission charged by the factory on the requested_amount,
+    // and the protocol params the option is accepted under
+    let commission = helpers::query_liquidity_commission(&deps, &requested_amount)?;
+    let protocol_params = helpers::query_protocol_params(&deps)?;
+
+    // Have sudomod mint the lender position token, when it has a collection for them
+    let lender_position = match helpers::open_lender_position(&mut deps, &env, lender)? {
+        Some((position, mint_msg)) => {
+            response = response.add_message(mint_msg);
+            Some(position)
+        }
+        None => None,
+    };
+
     // Update state
     LIQUIDITY_REQUEST_STATE.update(deps.storage, |data| -> Result<_, ContractError> {
         let mut option = data.unwrap();
@@ -587,24 +998,32 @@ pub fn execute_accept_liquidity_request(
         option.state = Some(state);
 
         // Update the lender info
-        option.lender = Some(info.sender.clone());
+        option.lender = Some(lender.clone());
+        option.lender_position = lender_position;
+
+        // Snapshot the commission terms and protocol params of the factory
+        option.liquidity_commission = Some(commission.clone());
+        option.protocol_params = Some(protocol_params);
 
         Ok(Some(option))
     })?;
 
-    // Add message to transfer liquidity request comission to INSTANTIATOR_ADDR
-    let transfer_msg = helpers::get_bank_transfer_to_msg(
-        &Addr::unchecked(INSTANTIATOR_ADDR),
-        &requested_amount.denom,
-        helpers::get_liquidity_comission(requested_amount.amount)?,
-    );
+    // The contributions held by the vault are released to the vault owner,
+    // and every counter offer opened against the pending liquidity request is refunded
+    let total_funded = helpers::get_total_funded(deps.storage)?;
+    helpers::release_balance(deps.storage, &requested_amount.denom, total_funded)?;
+    let refund_msgs = helpers::refund_all_counter_offers(deps.storage, &requested_amount.denom)?;
 
-    // respond
-    Ok(response.add_message(transfer_msg).add_attributes(vec![
-        attr("method", "accept_liquidity_request"),
-        attr("amount", requested_amount.amount.to_string()),
-        attr("vault_owner", config.owner.to_string()),
-    ]))
+    // Add message to transfer liquidity request comission to the fee_collector
+    if !commission.amount.amount.is_zero() {
+        response = response.add_message(helpers::get_bank_transfer_to_msg(
+            &commission.fee_collector,
+            &commission.amount.denom,
+            commission.amount.amount,
+        ));
+    }
+
+    Ok(response.add_messages(refund_msgs))
 }
 
 pub fn execute_claim_delegator_rewards(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
@@ -620,16 +1039,17 @@ pub fn execute_claim_delegator_rewards(deps: DepsMut, env: Env) -> Result<Respon
     // Process lender claims if there is an active rental option on the vault
     if let Some(ActiveOption {
         msg: _,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: Some(lender),
         state: Some(state),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
     {
-        if let Some(transfer_msgs) =
-            helpers::process_lender_claims(deps, &env, state, lender, total_rewards_claimed)?
-        {
-            // Add msg for sending claimed rewards to the lender
-            response = response.add_message(transfer_msgs);
-        }
+        // Add msgs for sending claimed rewards to the lenders
+        let transfer_msgs =
+            helpers::process_lender_claims(deps, &env, state, lender, total_rewards_claimed)?;
+        response = response.add_messages(transfer_msgs);
     }
 
     // respond
@@ -638,30 +1058,89 @@ pub fn execute_claim_delegator_rewards(deps: DepsMut, env: Env) -> Result<Respon
         .add_attribute("total_rewards_claimed", total_rewards_claimed.to_string()))
 }
 
-pub fn execute_repay_loan(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+pub fn execute_repay_loan(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
     // Init response object
     let mut response = Response::new();
 
+    // Installment loans are repaid one installment at a time
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::InstallmentLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_repay_installments(deps, env, amount);
+    }
+
+    // Rewards backed loans are topped up by the vault owner
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::RewardsBackedLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_repay_rewards_backed_loan(deps, env, amount);
+    }
+
+    // LtvLoans can be repaid partially to bring the loan back to health
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::LtvLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_repay_ltv_loan(deps, env, amount);
+    }
+
     // If the lender has already triggered a liquidation event, the vault owner can instead
     // call liquidate_collateral to pay-off the outstanding debt with the free vault balance
     if let Some(ActiveOption {
-        msg: _,
+        msg,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: Some(lender),
         state:
             Some(LiquidityRequestState::FixedTermLoan {
                 requested_amount,
                 interest_amount,
-                collateral_amount: _,
-                start_time: _,
-                end_time: _,
-                last_liquidation_date: _,
-                already_claimed: _,
+                collateral_amount,
+                start_time,
+                end_time,
+                last_liquidation_date,
+                already_claimed,
+                already_repaid,
                 processing_liquidation: false,
+                pending_extension,
+                keeper_fee_paid,
             }),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
     {
-        // Check if there is enough balance to repay requested_amount + interest_amount
-        let repayment_amount = requested_amount.amount + interest_amount;
+        // Repay the outstanding requested_amount + interest_due + late_fee_due
+        // unless amount is specified
+        let outstanding_amount = helpers::get_outstanding_amount(
+            &msg,
+            &requested_amount,
+            interest_amount,
+            start_time,
+            end_time,
+            already_repaid,
+            env.block.time,
+        )?;
+        let amount_due = outstanding_amount + already_repaid;
+        let repayment_amount = amount.unwrap_or(outstanding_amount);
+        if repayment_amount.is_zero() || repayment_amount > outstanding_amount {
+            return Err(ContractError::InvalidRepaymentAmount {
+                amount: repayment_amount,
+                outstanding: Coin {
+                    amount: outstanding_amount,
+                    denom: requested_amount.denom.clone(),
+                },
+            });
+        }
+
+        // Check if there is enough balance to repay repayment_amount
         let borrowed_denom_balance =
             helpers::get_balace_for_demon(&deps, &env, requested_amount.denom.clone())?;
         if borrowed_denom_balance.amount < repayment_amount {
@@ -677,17 +1156,44 @@ pub fn execute_repay_loan(deps: DepsMut, env: Env) -> Result<Response, ContractE
             });
         }
 
-        // Add funds_transfer_msg to send repayment_amount to the lender
-        response = response.add_message(helpers::get_bank_transfer_to_msg(
+        // Add funds_transfer_msgs to split repayment_amount between the lenders
+        response = response.add_messages(helpers::get_lender_transfer_msgs(
+            deps.storage,
             &lender,
-            &requested_amount.denom.clone(),
+            &requested_amount.denom,
             repayment_amount,
-        ));
+        )?);
+
+        // Record the repayment, and close the option once the loan is fully repaid
+        let updated_already_repaid = already_repaid + repayment_amount;
+        LIQUIDITY_REQUEST_STATE.update(deps.storage, |data| -> Result<_, ContractError> {
+            if updated_already_repaid < amount_due {
+                let mut option = data.unwrap();
+                option.state = Some(LiquidityRequestState::FixedTermLoan {
+                    requested_amount: requested_amount.clone(),
+                    interest_amount,
+                    collateral_amount,
+                    start_time,
+                    end_time,
+                    last_liquidation_date,
+                    already_claimed,
+                    already_repaid: updated_already_repaid,
+                    processing_liquidation: false,
+                    pending_extension,
+                    keeper_fee_paid,
+                });
 
-        // Close option as repayment has been processed successfully
-        LIQUIDITY_REQUEST_STATE.update(deps.storage, |mut _data| -> Result<_, ContractError> {
-            Ok(None)
+                Ok(Some(option))
+            } else {
+                // Close option as repayment has been processed successfully
+                Ok(None)
+            }
         })?;
+
+        response = response.add_attributes(vec![
+            attr("amount", repayment_amount.to_string()),
+            attr("already_repaid", updated_already_repaid.to_string()),
+        ]);
     } else {
         return Err(ContractError::Unauthorized {});
     }
@@ -696,93 +1202,818 @@ pub fn execute_repay_loan(deps: DepsMut, env: Env) -> Result<Response, ContractE
     Ok(response.add_attribute("method", "repay_loan"))
 }
 
-pub fn execute_liquidate_collateral(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    let mut response = Response::new();
-
-    // Check if there is an active FixedTermLoan loan on the vault
-    if let Some(ActiveOption {
-        msg: _,
-        lender: Some(lender),
-        state:
-            Some(LiquidityRequestState::FixedTermLoan {
-                requested_amount,
-                interest_amount,
-                collateral_amount,
-                start_time,
+pub fn execute_propose_loan_extension(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    new_end_time: Timestamp,
+    additional_interest: Uint128,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    if let Some(LiquidityRequestState::FixedTermLoan {
+        end_time,
+        processing_liquidation,
+        ref mut pending_extension,
+        ..
+    }) = option.state
+    {
+        // Loan extensions can only be proposed until the grace period expires
+        helpers::ensure_loan_can_be_extended(&option.msg, end_time, processing_liquidation, &env)?;
+        if new_end_time <= end_time {
+            return Err(ContractError::InvalidLoanExtension {
+                new_end_time,
                 end_time,
-                already_claimed,
-                last_liquidation_date,
-                processing_liquidation: _,
-            }),
-    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+            });
+        }
+
+        // Replace any pending extension with the new proposal
+        *pending_extension = Some(LoanExtensionProposal {
+            proposed_by: info.sender.clone(),
+            new_end_time,
+            additional_interest,
+        });
+    } else {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "propose_loan_extension")
+        .add_attribute("new_end_time", new_end_time.to_string())
+        .add_attribute("additional_interest", additional_interest.to_string()))
+}
+
+pub fn execute_accept_loan_extension(
+    deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+) -> Result<Response, ContractError> {
+    let owner = CONFIG.load(deps.storage)?.owner;
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let lender = option.lender.clone();
+    let extension = if let Some(LiquidityRequestState::FixedTermLoan {
+        ref requested_amount,
+        start_time,
+        ref mut end_time,
+        ref mut interest_amount,
+        already_repaid,
+        processing_liquidation,
+        ref mut pending_extension,
+        ..
+    }) = option.state
     {
-        // liquidation on fixed term loans can only happen on/after expiration date
-        // TODO make this error message more descriptive
-        if env.block.time < end_time {
+        helpers::ensure_loan_can_be_extended(&option.msg, *end_time, processing_liquidation, &env)?;
+
+        // Only the counterparty of the proposer can accept the extension
+        let extension = pending_extension
+            .take()
+            .ok_or(ContractError::NoPendingLoanExtension {})?;
+        let counterparty = if extension.proposed_by == owner {
+            lender
+        } else if Some(&extension.proposed_by) == lender.as_ref() {
+            Some(owner)
+        } else {
+            None
+        };
+        if counterparty != Some(info.sender.clone()) {
             return Err(ContractError::Unauthorized {});
         }
 
-        // Get available collateral balance
-        let denom_str = deps.querier.query_bonded_denom()?;
-        let available_collateral_balance =
-            helpers::get_balace_for_demon(&deps, &env, denom_str.clone())?;
+        // Extend the loan term
+        *end_time = extension.new_end_time;
+        *interest_amount += extension.additional_interest;
 
-        // Get available staking rewards
-        let (total_rewards_claimed, distribute_msgs) =
-            helpers::accumulated_rewards(&deps, &env, None)?;
+        // Interest pro rated over the longer term must still cover what was already repaid,
+        // after which the amount due only grows with time
+        helpers::get_outstanding_amount(
+            &option.msg,
+            requested_amount,
+            *interest_amount,
+            start_time,
+            *end_time,
+            already_repaid,
+            env.block.time,
+        )?;
+        extension
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "accept_loan_extension")
+        .add_attribute("new_end_time", extension.new_end_time.to_string())
+        .add_attribute(
+            "additional_interest",
+            extension.additional_interest.to_string(),
+        ))
+}
+
+pub fn execute_repay_installments(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let lender = option.lender.clone().unwrap();
+    let (repayment_amount, installments_paid, option_closed) =
+        if let Some(LiquidityRequestState::InstallmentLoan {
+            ref requested_amount,
+            installment_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            start_time,
+            ref mut next_due_date,
+            ref mut installments_paid,
+            installments_defaulted,
+            processing_liquidation,
+            already_claimed,
+            collateral_amount,
+            ..
+        }) = option.state
+        {
+            // Pay the next installment unless amount is specified,
+            // in which case amount must cover a whole number of installments
+            let installments_settled = *installments_paid + installments_defaulted;
+            let outstanding_amount =
+                installment_amount * Uint128::from(number_of_installments - installments_settled);
+            let repayment_amount = amount.unwrap_or(installment_amount);
+            if repayment_amount.is_zero()
+                || repayment_amount > outstanding_amount
+                || !(repayment_amount % installment_amount).is_zero()
+            {
+                return Err(ContractError::InvalidRepaymentAmount {
+                    amount: repayment_amount,
+                    outstanding: Coin {
+                        amount: outstanding_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
 
-        // Calculate total available collateral balance
-        let total_available_collateral_balance =
-            available_collateral_balance.amount + total_rewards_claimed;
+            // Check if there is enough balance to repay repayment_amount
+            let borrowed_denom_balance =
+                helpers::get_balace_for_demon(&deps, &env, requested_amount.denom.clone())?;
+            if borrowed_denom_balance.amount < repayment_amount {
+                return Err(ContractError::InsufficientBalance {
+                    required: Coin {
+                        amount: repayment_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                    available: Coin {
+                        amount: borrowed_denom_balance.amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
 
-        // Calculate amount_to_claim which is limited by total_available_collateral_balance
-        let outstanding_debt = collateral_amount - already_claimed;
-        let amount_to_claim = if outstanding_debt < total_available_collateral_balance {
-            outstanding_debt
+            // Record the installments paid, and close the option once every installment
+            // is settled and the collateral owed for defaulted installments is claimed
+            *installments_paid += (repayment_amount / installment_amount).u128() as u32;
+            let installments_settled = *installments_paid + installments_defaulted;
+            *next_due_date = helpers::get_next_due_date(
+                start_time,
+                installment_period_in_seconds,
+                installments_settled,
+            );
+            let collateral_due =
+                collateral_amount.multiply_ratio(installments_defaulted, number_of_installments);
+            let option_closed = installments_settled == number_of_installments
+                && !processing_liquidation
+                && already_claimed >= collateral_due;
+
+            (
+                Coin {
+                    denom: requested_amount.denom.clone(),
+                    amount: repayment_amount,
+                },
+                *installments_paid,
+                option_closed,
+            )
         } else {
-            total_available_collateral_balance
+            return Err(ContractError::Unauthorized {});
         };
 
-        // Calculate duration_since_last_liquidation
-        let duration_since_last_liquidation = if last_liquidation_date.is_some() {
-            env.block.time.seconds() - last_liquidation_date.unwrap().seconds()
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(helpers::get_lender_transfer_msgs(
+            deps.storage,
+            &lender,
+            &repayment_amount.denom,
+            repayment_amount.amount,
+        )?)
+        .add_attributes(vec![
+            attr("method", "repay_loan"),
+            attr("amount", repayment_amount.amount.to_string()),
+            attr("installments_paid", installments_paid.to_string()),
+        ]))
+}
+
+pub fn execute_liquidate_missed_installments(
+    mut deps: DepsMut,
+    env: Env,
+    mut payees: LiquidationPayees,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let (claim_msgs, option_closed) = if let Some(LiquidityRequestState::InstallmentLoan {
+        collateral_amount,
+        number_of_installments,
+        installment_period_in_seconds,
+        start_time,
+        ref mut next_due_date,
+        installments_paid,
+        ref mut installments_defaulted,
+        ref mut processing_liquidation,
+        ref mut already_claimed,
+        ref mut last_liquidation_date,
+        ref mut keeper_fee_paid,
+        ..
+    }) = option.state
+    {
+        // Every missed installment defaults on its slice of collateral_amount
+        let (updated_installments_defaulted, collateral_due) = helpers::get_defaulted_installments(
+            collateral_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            start_time,
+            installments_paid,
+            *installments_defaulted,
+            env.block.time,
+        );
+
+        // Liquidation can only happen once an installment has been missed
+        if collateral_due <= *already_claimed {
+            return Err(ContractError::LoanNotYetLiquidatable {
+                available_at: *next_due_date,
+            });
+        }
+
+        // Claim the collateral due to the lender, unbonding it from validators when needed
+        let (claim_msgs, updated_already_claimed, updated_last_liquidation_date) =
+            helpers::claim_collateral(
+                &mut deps,
+                &env,
+                &mut payees,
+                collateral_due,
+                *already_claimed,
+                *last_liquidation_date,
+            )?;
+
+        // Update the installment loan, and close the option once every installment
+        // is settled and the collateral owed for defaulted installments is claimed
+        let installments_settled = installments_paid + updated_installments_defaulted;
+        let claims_not_completed = updated_already_claimed < collateral_due;
+        *installments_defaulted = updated_installments_defaulted;
+        *next_due_date = helpers::get_next_due_date(
+            start_time,
+            installment_period_in_seconds,
+            installments_settled,
+        );
+        *processing_liquidation = claims_not_completed;
+        *already_claimed = updated_already_claimed;
+        *last_liquidation_date = updated_last_liquidation_date;
+        *keeper_fee_paid = payees.keeper_fee_paid;
+
+        (
+            claim_msgs,
+            installments_settled == number_of_installments && !claims_not_completed,
+        )
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(claim_msgs)
+        .add_attribute("method", "liquidate_collateral"))
+}
+
+pub fn execute_repay_rewards_backed_loan(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let lender = option.lender.clone().unwrap();
+    let (repayment_amount, updated_already_repaid, option_closed) =
+        if let Some(LiquidityRequestState::RewardsBackedLoan {
+            ref requested_amount,
+            interest_amount,
+            ref mut already_repaid,
+            processing_liquidation: false,
+            ..
+        }) = option.state
+        {
+            // Repay the outstanding requested_amount + interest_amount
+            // not yet covered by staking rewards unless amount is specified
+            let amount_due = requested_amount.amount + interest_amount;
+            let outstanding_amount = amount_due - *already_repaid;
+            let repayment_amount = amount.unwrap_or(outstanding_amount);
+            if repayment_amount.is_zero() || repayment_amount > outstanding_amount {
+                return Err(ContractError::InvalidRepaymentAmount {
+                    amount: repayment_amount,
+                    outstanding: Coin {
+                        amount: outstanding_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
+
+            // Check if there is enough balance to repay repayment_amount
+            let borrowed_denom_balance =
+                helpers::get_balace_for_demon(&deps, &env, requested_amount.denom.clone())?;
+            if borrowed_denom_balance.amount < repayment_amount {
+                return Err(ContractError::InsufficientBalance {
+                    required: Coin {
+                        amount: repayment_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                    available: Coin {
+                        amount: borrowed_denom_balance.amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
+
+            // Record the repayment, and close the option once the loan is fully repaid
+            *already_repaid += repayment_amount;
+
+            (
+                Coin {
+                    denom: requested_amount.denom.clone(),
+                    amount: repayment_amount,
+                },
+                *already_repaid,
+                *already_repaid >= amount_due,
+            )
         } else {
-            STAKE_LIQUIDATION_INTERVAL
+            return Err(ContractError::Unauthorized {});
         };
 
-        // Add messages to unbond outstanding collateral amount from staked tokens
-        // When total_available_collateral_balance is not enough to clear the debt
-        let updated_already_claimed = already_claimed + amount_to_claim;
-        let claims_not_completed = updated_already_claimed < collateral_amount;
-        let can_unstake = duration_since_last_liquidation >= STAKE_LIQUIDATION_INTERVAL;
-        let mut updated_last_liquidation_date = last_liquidation_date;
-        if claims_not_completed && can_unstake {
-            let undelegate_msgs = helpers::unbond_tokens_from_validators(
-                &deps,
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(helpers::get_lender_transfer_msgs(
+            deps.storage,
+            &lender,
+            &repayment_amount.denom,
+            repayment_amount.amount,
+        )?)
+        .add_attributes(vec![
+            attr("method", "repay_loan"),
+            attr("amount", repayment_amount.amount.to_string()),
+            attr("already_repaid", updated_already_repaid.to_string()),
+        ]))
+}
+
+pub fn execute_liquidate_rewards_backed_loan(
+    mut deps: DepsMut,
+    env: Env,
+    mut payees: LiquidationPayees,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let (claim_msgs, option_closed) = if let Some(LiquidityRequestState::RewardsBackedLoan {
+        ref requested_amount,
+        interest_amount,
+        collateral_amount,
+        end_time,
+        already_repaid,
+        ref mut processing_liquidation,
+        ref mut already_claimed,
+        ref mut last_liquidation_date,
+        ref mut keeper_fee_paid,
+        ..
+    }) = option.state
+    {
+        // liquidation on rewards backed loans can only happen on/after expiration date
+        if env.block.time < end_time {
+            return Err(ContractError::LoanNotYetLiquidatable {
+                available_at: end_time,
+            });
+        }
+
+        // Claim the collateral due on the part of the loan not repaid from staking rewards
+        let collateral_due = helpers::get_collateral_due(
+            collateral_amount,
+            requested_amount,
+            interest_amount,
+            already_repaid,
+        );
+        let (claim_msgs, updated_already_claimed, updated_last_liquidation_date) =
+            helpers::claim_collateral(
+                &mut deps,
                 &env,
-                collateral_amount - updated_already_claimed,
+                &mut payees,
+                collateral_due,
+                *already_claimed,
+                *last_liquidation_date,
             )?;
+        let claims_not_completed = updated_already_claimed < collateral_due;
+        *processing_liquidation = true;
+        *already_claimed = updated_already_claimed;
+        *last_liquidation_date = updated_last_liquidation_date;
+        *keeper_fee_paid = payees.keeper_fee_paid;
+
+        (claim_msgs, !claims_not_completed)
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(claim_msgs)
+        .add_attribute("method", "liquidate_collateral"))
+}
+
+pub fn execute_margin_call(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let health = helpers::get_loan_health(&deps.querier, option.state.as_ref().unwrap())?;
+    let deadline = if let Some(LiquidityRequestState::LtvLoan {
+        margin_call_ltv,
+        margin_call_period_in_seconds,
+        ref mut margin_call_deadline,
+        processing_liquidation: false,
+        ..
+    }) = option.state
+    {
+        if let Some(deadline) = *margin_call_deadline {
+            return Err(ContractError::MarginCallAlreadyActive { deadline });
+        }
+
+        // The lender can only call a margin once the ltv is above margin_call_ltv
+        if health.ltv <= margin_call_ltv {
+            return Err(ContractError::LoanIsHealthy {
+                ltv: health.ltv,
+                margin_call_ltv,
+            });
+        }
+
+        let deadline = env.block.time.plus_seconds(margin_call_period_in_seconds);
+        *margin_call_deadline = Some(deadline);
+        deadline
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+
+    // respond
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "margin_call"),
+        attr("ltv", health.ltv.to_string()),
+        attr("deadline", deadline.to_string()),
+    ]))
+}
+
+pub fn execute_top_up_collateral(
+    deps: DepsMut,
+    env: Env,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let updated_collateral_amount = if let Some(LiquidityRequestState::LtvLoan {
+        ref mut collateral_amount,
+        processing_liquidation: false,
+        ..
+    }) = option.state
+    {
+        // The vault must have enough tokens staked to back the new collateral_amount
+        let total_delegations = helpers::query_total_delegations(&deps, &env)?;
+        let denom_str = deps.querier.query_bonded_denom()?;
+        if amount.is_zero() || total_delegations < *collateral_amount + amount {
+            return Err(ContractError::InsufficientBalance {
+                required: Coin {
+                    denom: denom_str.clone(),
+                    amount: *collateral_amount + amount,
+                },
+                available: Coin {
+                    denom: denom_str,
+                    amount: total_delegations,
+                },
+            });
+        }
+
+        *collateral_amount += amount;
+        *collateral_amount
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    // Clear the margin call once the loan is healthy again
+    let health = helpers::get_loan_health(&deps.querier, option.state.as_ref().unwrap())?;
+    if let Some(LiquidityRequestState::LtvLoan {
+        margin_call_ltv,
+        ref mut margin_call_deadline,
+        ..
+    }) = option.state
+    {
+        if health.ltv <= margin_call_ltv {
+            *margin_call_deadline = None;
+        }
+    }
+
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+
+    // respond
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "top_up_collateral"),
+        attr("amount", amount.to_string()),
+        attr("collateral_amount", updated_collateral_amount.to_string()),
+        attr("ltv", health.ltv.to_string()),
+    ]))
+}
+
+pub fn execute_repay_ltv_loan(
+    deps: DepsMut,
+    env: Env,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let lender = option.lender.clone().unwrap();
+    let (repayment_amount, updated_already_repaid, option_closed) =
+        if let Some(LiquidityRequestState::LtvLoan {
+            ref requested_amount,
+            interest_amount,
+            ref mut already_repaid,
+            processing_liquidation: false,
+            ..
+        }) = option.state
+        {
+            // Repay the outstanding requested_amount + interest_amount unless amount is specified
+            let amount_due = requested_amount.amount + interest_amount;
+            let outstanding_amount = amount_due - *already_repaid;
+            let repayment_amount = amount.unwrap_or(outstanding_amount);
+            if repayment_amount.is_zero() || repayment_amount > outstanding_amount {
+                return Err(ContractError::InvalidRepaymentAmount {
+                    amount: repayment_amount,
+                    outstanding: Coin {
+                        amount: outstanding_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
 
-            if !undelegate_msgs.is_empty() {
-                updated_last_liquidation_date = Some(env.block.time);
-                response = response.add_messages(undelegate_msgs);
+            // Check if there is enough balance to repay repayment_amount
+            let borrowed_denom_balance =
+                helpers::get_balace_for_demon(&deps, &env, requested_amount.denom.clone())?;
+            if borrowed_denom_balance.amount < repayment_amount {
+                return Err(ContractError::InsufficientBalance {
+                    required: Coin {
+                        amount: repayment_amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                    available: Coin {
+                        amount: borrowed_denom_balance.amount,
+                        denom: requested_amount.denom.clone(),
+                    },
+                });
+            }
+
+            // Record the repayment, and close the option once the loan is fully repaid
+            *already_repaid += repayment_amount;
+
+            (
+                Coin {
+                    denom: requested_amount.denom.clone(),
+                    amount: repayment_amount,
+                },
+                *already_repaid,
+                *already_repaid >= amount_due,
+            )
+        } else {
+            return Err(ContractError::Unauthorized {});
+        };
+
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        // Clear the margin call once the loan is healthy again
+        let health = helpers::get_loan_health(&deps.querier, option.state.as_ref().unwrap())?;
+        if let Some(LiquidityRequestState::LtvLoan {
+            margin_call_ltv,
+            ref mut margin_call_deadline,
+            ..
+        }) = option.state
+        {
+            if health.ltv <= margin_call_ltv {
+                *margin_call_deadline = None;
             }
         }
 
-        // Add messages to claim available staking rewards
-        if !total_rewards_claimed.is_zero() {
-            response = response.add_messages(distribute_msgs);
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(helpers::get_lender_transfer_msgs(
+            deps.storage,
+            &lender,
+            &repayment_amount.denom,
+            repayment_amount.amount,
+        )?)
+        .add_attributes(vec![
+            attr("method", "repay_loan"),
+            attr("amount", repayment_amount.amount.to_string()),
+            attr("already_repaid", updated_already_repaid.to_string()),
+        ]))
+}
+
+pub fn execute_liquidate_ltv_loan(
+    mut deps: DepsMut,
+    env: Env,
+    mut payees: LiquidationPayees,
+) -> Result<Response, ContractError> {
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    let health = helpers::get_loan_health(&deps.querier, option.state.as_ref().unwrap())?;
+    let (claim_msgs, option_closed) = if let Some(LiquidityRequestState::LtvLoan {
+        ref requested_amount,
+        interest_amount,
+        collateral_amount,
+        margin_call_ltv,
+        end_time,
+        already_repaid,
+        margin_call_deadline,
+        ref mut processing_liquidation,
+        ref mut already_claimed,
+        ref mut last_liquidation_date,
+        ref mut keeper_fee_paid,
+        ..
+    }) = option.state
+    {
+        // liquidation on ltv loans can happen on/after expiration date, or earlier
+        // once a margin call expires while the loan is still above margin_call_ltv
+        if env.block.time < end_time && !*processing_liquidation {
+            match margin_call_deadline {
+                Some(deadline) if env.block.time >= deadline => {
+                    if health.ltv <= margin_call_ltv {
+                        return Err(ContractError::LoanIsHealthy {
+                            ltv: health.ltv,
+                            margin_call_ltv,
+                        });
+                    }
+                }
+                Some(deadline) => {
+                    return Err(ContractError::LoanNotYetLiquidatable {
+                        available_at: deadline,
+                    })
+                }
+                None => {
+                    return Err(ContractError::LoanNotYetLiquidatable {
+                        available_at: end_time,
+                    })
+                }
+            }
         }
 
-        // Add messages to send amount_to_claim to the lender
-        if !amount_to_claim.is_zero() {
-            response = response.add_message(helpers::get_bank_transfer_to_msg(
-                &lender,
-                &denom_str,
-                amount_to_claim,
-            ));
+        // Claim the collateral due on the part of the loan not yet repaid
+        let collateral_due = helpers::get_collateral_due(
+            collateral_amount,
+            requested_amount,
+            interest_amount,
+            already_repaid,
+        );
+        let (claim_msgs, updated_already_claimed, updated_last_liquidation_date) =
+            helpers::claim_collateral(
+                &mut deps,
+                &env,
+                &mut payees,
+                collateral_due,
+                *already_claimed,
+                *last_liquidation_date,
+            )?;
+        let claims_not_completed = updated_already_claimed < collateral_due;
+        *processing_liquidation = true;
+        *already_claimed = updated_already_claimed;
+        *last_liquidation_date = updated_last_liquidation_date;
+        *keeper_fee_paid = payees.keeper_fee_paid;
+
+        (claim_msgs, !claims_not_completed)
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    if option_closed {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &None)?;
+    } else {
+        LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+    }
+
+    // respond
+    Ok(Response::new()
+        .add_messages(claim_msgs)
+        .add_attribute("method", "liquidate_collateral"))
+}
+
+pub fn execute_liquidate_collateral(
+    mut deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut response = Response::new();
+
+    // Third parties driving the liquidation are paid the keeper_fee from the recovered collateral
+    let mut payees = helpers::get_liquidation_payees(&deps, &info.sender)?;
+
+    // Installment loans are liquidated one missed installment at a time
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::InstallmentLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_liquidate_missed_installments(deps, env, payees);
+    }
+
+    // Rewards backed loans are liquidated for the part not repaid at maturity
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::RewardsBackedLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_liquidate_rewards_backed_loan(deps, env, payees);
+    }
+
+    // LtvLoans can also be liquidated early after an expired margin call
+    if let Some(ActiveOption {
+        state: Some(LiquidityRequestState::LtvLoan { .. }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        return execute_liquidate_ltv_loan(deps, env, payees);
+    }
+
+    // Check if there is an active FixedTermLoan loan on the vault
+    if let Some(ActiveOption {
+        msg,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
+        lender: Some(_),
+        state:
+            Some(LiquidityRequestState::FixedTermLoan {
+                requested_amount,
+                interest_amount,
+                collateral_amount,
+                start_time,
+                end_time,
+                already_claimed,
+                already_repaid,
+                last_liquidation_date,
+                processing_liquidation: _,
+                pending_extension: _,
+                keeper_fee_paid: _,
+            }),
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        // liquidation on fixed term loans can only happen once the grace period has expired
+        let available_at = helpers::get_grace_period_end(&msg, end_time);
+        if env.block.time < available_at {
+            return Err(ContractError::LoanNotYetLiquidatable { available_at });
         }
 
+        // Claim the collateral due to the lender, unbonding it from validators when needed
+        let collateral_due = helpers::get_collateral_due(
+            collateral_amount,
+            &requested_amount,
+            interest_amount,
+            already_repaid,
+        );
+        let (claim_msgs, updated_already_claimed, updated_last_liquidation_date) =
+            helpers::claim_collateral(
+                &mut deps,
+                &env,
+                &mut payees,
+                collateral_due,
+                already_claimed,
+                last_liquidation_date,
+            )?;
+        let claims_not_completed = updated_already_claimed < collateral_due;
+        response = response.add_messages(claim_msgs);
+
         // Update the liquidity request state
         LIQUIDITY_REQUEST_STATE.update(deps.storage, |data| -> Result<_, ContractError> {
             if claims_not_completed {
@@ -795,7 +2026,10 @@ pub fn execute_liquidate_collateral(deps: DepsMut, env: Env) -> Result<Response,
                     end_time,
                     last_liquidation_date: updated_last_liquidation_date,
                     already_claimed: updated_already_claimed,
+                    already_repaid,
                     processing_liquidation: true,
+                    pending_extension: None,
+                    keeper_fee_paid: payees.keeper_fee_paid,
                 });
 
                 Ok(Some(option))
@@ -830,11 +2064,23 @@ pub fn execute_withdraw_balance(
         });
     }
 
+    // Funds reserved on behalf of lenders and proposers can not be withdrawn by the vault owner
+    let (withdrawable_balance, reserved_balance) =
+        helpers::get_withdrawable_balance_for_denom(&deps, &env, funds.denom.clone())?;
+    if withdrawable_balance.amount < funds.amount {
+        return Err(ContractError::InsufficientWithdrawableBalance {
+            denom: funds.denom,
+            required: funds.amount,
+            available: withdrawable_balance.amount,
+            reserved: reserved_balance.amount,
+        });
+    }
+
     // Check if user is trying to withdraw staking balance, as it is the token used as collateral,
-    // we also check to make sure there is no outstandinding debt from a defaulted fixed term loan
+    // we also check to make sure there is no outstandinding debt from a defaulted loan
     // on the vault else we return ContractError::ClearOutstandingDebt {amount: outstanding_amount}
     let staking_denom = deps.querier.query_bonded_denom()?;
-    let outstanding_debt = helpers::outstanding_fixed_term_loan_debt(&deps, &env)?;
+    let outstanding_debt = helpers::outstanding_loan_debt(&deps, &env)?;
     if staking_denom.eq(&funds.denom.clone()) && outstanding_debt.gt(&Uint128::zero()) {
         return Err(ContractError::ClearOutstandingDebt {
             amount: Coin {
@@ -869,21 +2115,132 @@ pub fn execute_transfer_ownership(
     deps: DepsMut,
     to_address: String,
 ) -> Result<Response, ContractError> {
+    // Tokenized ownership moves with the token, as its owner owns the vault
+    if let Some(token) = CONFIG.load(deps.storage)?.ownership_token {
+        return Err(ContractError::OwnershipIsTokenized {
+            collection: token.collection.to_string(),
+            token_id: token.token_id,
+        });
+    }
+
     // validate the new owner_address
     let new_owner = deps.api.addr_validate(&to_address)?;
 
     // Set the new owner of this vault
-    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
-        data.owner = new_owner;
+    let config = CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.owner = new_owner.clone();
         Ok(data)
     })?;
+    helpers::clear_pending_loan_extension_on_vault(deps.storage)?;
 
-    Ok(Response::new().add_attributes(vec![
+    // Notify the factory (sudomod) of the new owner of this vault
+    let mut response = Response::new();
+    if let Some(hook_msg) = helpers::get_sudomod_hook_msg(
+        &deps,
+        &config.factory,
+        &SudomodHookMsg::VaultOwnerChanged {
+            new_owner: new_owner.to_string(),
+        },
+    )? {
+        response = response.add_message(hook_msg);
+    }
+
+    Ok(response.add_attributes(vec![
         attr("method", "transfer_ownership"),
         attr("to_address", to_address.to_string()),
     ]))
 }
 
+pub fn execute_tokenize_ownership(
+    deps: DepsMut,
+    info: &MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // Only the factory can tokenize the ownership of this vault, once
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender.ne(&config.factory) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(token) = config.ownership_token {
+        return Err(ContractError::OwnershipIsTokenized {
+            collection: token.collection.to_string(),
+            token_id: token.token_id,
+        });
+    }
+
+    // Verify that the token was minted to the current owner
+    let collection = deps.api.addr_validate(&collection)?;
+    let token_owner = helpers::query_token_owner(&deps.querier, &collection, &token_id)?;
+    if token_owner.ne(&config.owner) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.ownership_token = Some(OwnershipToken {
+        collection: collection.clone(),
+        token_id: token_id.clone(),
+    });
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "tokenize_ownership"),
+        attr("collection", collection.to_string()),
+        attr("token_id", token_id),
+    ]))
+}
+
+pub fn execute_transfer_lender_position(
+    deps: DepsMut,
+    info: &MessageInfo,
+    to_address: String,
+) -> Result<Response, ContractError> {
+    // The position can not be transferred to the vault owner, or to the current lender
+    let config = CONFIG.load(deps.storage)?;
+    let new_lender = deps.api.addr_validate(&to_address)?;
+    if new_lender.eq(&config.owner) || new_lender.eq(&info.sender) {
+        return Err(ContractError::InvalidLenderPositionRecipient {
+            address: to_address,
+        });
+    }
+
+    // Tokenized positions move with the token, as its owner acts as the lender
+    let mut option = LIQUIDITY_REQUEST_STATE.load(deps.storage)?.unwrap();
+    if let Some(position) = option.lender_position {
+        return Err(ContractError::LenderPositionIsTokenized {
+            collection: position.collection.to_string(),
+            token_id: position.token_id,
+        });
+    }
+
+    // Hand the active option and the current lender's share to the new lender
+    let share =
+        helpers::move_lender_position(deps.storage, &mut option, &info.sender, &new_lender)?;
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "transfer_lender_position"),
+        attr("from_address", info.sender.to_string()),
+        attr("to_address", new_lender.to_string()),
+        attr("share", share.to_string()),
+    ]))
+}
+
+pub fn execute_set_unbonding_strategy(
+    deps: DepsMut,
+    strategy: UnbondingStrategy,
+) -> Result<Response, ContractError> {
+    // Set the strategy used to unbond collateral during liquidation
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.unbonding_strategy = strategy;
+        Ok(data)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "set_unbonding_strategy"),
+        attr("strategy", format!("{:?}", strategy)),
+    ]))
+}
+
 // Test on testnet, until we figure out how to create
 // a test proposal using multi-test
 pub fn execute_vote(
@@ -912,6 +2269,51 @@ pub fn execute_vote(
     Ok(response.add_attribute("method", "vote"))
 }
 
+#[entry_point]
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Verify that we are migrating from an instance of the vault contract
+    let stored = cw2::get_contract_version(deps.storage)?;
+    if stored.contract.ne(CONTRACT_NAME) {
+        return Err(ContractError::InvalidMigrationContract {
+            contract: stored.contract,
+        });
+    }
+
+    // Refuse to migrate to an older version of the vault contract
+    let stored_version = migrations::parse_version(&stored.version)?;
+    let new_version = migrations::parse_version(CONTRACT_VERSION)?;
+    if stored_version > new_version {
+        return Err(ContractError::MigrationDowngradeNotAllowed {
+            stored_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Run the state migrations for every version after stored_version
+    let contract_info = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address.clone())?;
+    migrations::migrate_state(&mut deps, &env, &stored_version, &contract_info)?;
+
+    // Point from_code_id to the code_id this vault now runs on
+    let code_id = contract_info.code_id;
+    CONFIG.update(deps.storage, |mut data| -> Result<_, ContractError> {
+        data.from_code_id = code_id;
+        Ok(data)
+    })?;
+
+    // Store the new contract version
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Respond
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "migrate"),
+        attr("from_version", stored_version.to_string()),
+        attr("to_version", CONTRACT_VERSION),
+        attr("from_code_id", code_id.to_string()),
+    ]))
+}
+
 #[entry_point]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -919,12 +2321,41 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::StakingInfo {} => to_binary(&query_staking_info(deps, env)?),
         QueryMsg::AllDelegations {} => to_binary(&query_all_delegations(deps, env)?),
         QueryMsg::CounterOfferList {} => to_binary(&query_all_counter_offers(deps)?),
+        QueryMsg::SimulateRepayment {} => to_binary(&query_simulate_repayment(deps, env)?),
+        QueryMsg::NextLiquidationStep {} => to_binary(&query_next_liquidation_step(deps, env)?),
+        QueryMsg::LoanHealth {} => to_binary(&query_loan_health(deps)?),
+        QueryMsg::LenderShares {} => to_binary(&query_lender_shares(deps)?),
+        QueryMsg::PendingUnbondings {} => to_binary(&query_pending_unbondings(deps, env)?),
     }
 }
 
 pub fn query_info(_deps: Deps) -> StdResult<InfoResponse> {
-    let config = CONFIG.load(_deps.storage)?;
-    let liquidity_request = LIQUIDITY_REQUEST_STATE.load(_deps.storage)?;
+    let mut config = CONFIG.load(_deps.storage)?;
+    let mut liquidity_request = LIQUIDITY_REQUEST_STATE.load(_deps.storage)?;
+
+    // Report the owner of the ownership token as the vault owner
+    if let Some(token) = &config.ownership_token {
+        if let Some(owner) =
+            helpers::may_query_token_owner(&_deps.querier, &token.collection, &token.token_id)
+        {
+            config.owner = owner;
+        }
+    }
+
+    // Report the owner of the lender position token as the lender
+    if let Some(ActiveOption {
+        lender: ref mut lender @ Some(_),
+        lender_position: Some(ref position),
+        ..
+    }) = liquidity_request
+    {
+        if let Some(owner) =
+            helpers::may_query_token_owner(&_deps.querier, &position.collection, &position.token_id)
+        {
+            *lender = Some(owner);
+        }
+    }
+
     Ok(InfoResponse {
         config,
         liquidity_request,
@@ -945,7 +2376,185 @@ pub fn query_all_delegations(deps: Deps, env: Env) -> StdResult<AllDelegationsRe
     Ok(AllDelegationsResponse { data })
 }
 
+pub fn query_loan_health(deps: Deps) -> StdResult<LoanHealthResponse> {
+    let state = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .and_then(|option| option.state)
+        .ok_or_else(|| StdError::generic_err("No active LtvLoan"))?;
+    helpers::get_loan_health(&deps.querier, &state)
+}
+
+pub fn query_lender_shares(deps: Deps) -> StdResult<LenderSharesResponse> {
+    // Shares are only relevant to the pending or active liquidity request
+    let option = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No liquidity request"))?;
+    let shares = helpers::get_lender_shares(deps.storage)?;
+    Ok(LenderSharesResponse {
+        total_funded: Coin {
+            denom: helpers::get_requested_amount(option.msg).denom,
+            amount: shares.iter().map(|share| share.amount).sum(),
+        },
+        shares,
+    })
+}
+
+pub fn query_pending_unbondings(deps: Deps, env: Env) -> StdResult<PendingUnbondingsResponse> {
+    let unbondings = helpers::get_all_pending_unbondings(deps.storage, &env)?;
+    Ok(PendingUnbondingsResponse { unbondings })
+}
+
 pub fn query_all_counter_offers(deps: Deps) -> StdResult<CounterOfferListResponse> {
     let data = helpers::query_all_counter_offers(deps)?;
     Ok(CounterOfferListResponse { data })
 }
+
+pub fn query_simulate_repayment(deps: Deps, env: Env) -> StdResult<SimulateRepaymentResponse> {
+    if let Some(ActiveOption {
+        msg,
+        lender: Some(_),
+        state:
+            Some(LiquidityRequestState::FixedTermLoan {
+                requested_amount,
+                interest_amount,
+                start_time,
+                end_time,
+                already_repaid,
+                ..
+            }),
+        ..
+    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
+    {
+        // Get the amount the vault owner must repay at the current block time
+        let interest_due =
+            helpers::get_interest_due(&msg, interest_amount, start_time, end_time, env.block.time);
+        let late_fee_due = helpers::get_late_fee_due(&msg, end_time, env.block.time);
+        let amount_due = helpers::get_outstanding_amount(
+            &msg,
+            &requested_amount,
+            interest_amount,
+            start_time,
+            end_time,
+            already_repaid,
+            env.block.time,
+        )
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+        let coin = |amount| Coin {
+            denom: requested_amount.denom.clone(),
+            amount,
+        };
+
+        Ok(SimulateRepaymentResponse {
+            interest_due: coin(interest_due),
+            late_fee_due: coin(late_fee_due),
+            already_repaid: coin(already_repaid),
+            amount_due: coin(amount_due),
+        })
+    } else {
+        Err(StdError::generic_err("No active fixed term loan to repay"))
+    }
+}
+
+pub fn query_next_liquidation_step(deps: Deps, env: Env) -> StdResult<NextLiquidationStepResponse> {
+    // Get when the active loan becomes liquidatable
+    let option = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .filter(|option| option.lender.is_some())
+        .ok_or_else(|| StdError::generic_err("No active loan to liquidate"))?;
+    let (available_at, last_liquidation_date) = match option.state {
+        Some(LiquidityRequestState::FixedTermLoan {
+            end_time,
+            last_liquidation_date,
+            ..
+        }) => (
+            helpers::get_grace_period_end(&option.msg, end_time),
+            last_liquidation_date,
+        ),
+
+        Some(LiquidityRequestState::InstallmentLoan {
+            collateral_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            start_time,
+            next_due_date,
+            installments_paid,
+            installments_defaulted,
+            already_claimed,
+            last_liquidation_date,
+            ..
+        }) => {
+            let (_, collateral_due) = helpers::get_defaulted_installments(
+                collateral_amount,
+                number_of_installments,
+                installment_period_in_seconds,
+                start_time,
+                installments_paid,
+                installments_defaulted,
+                env.block.time,
+            );
+            let available_at = if collateral_due > already_claimed {
+                env.block.time
+            } else {
+                next_due_date
+            };
+            (available_at, last_liquidation_date)
+        }
+
+        Some(LiquidityRequestState::RewardsBackedLoan {
+            end_time,
+            last_liquidation_date,
+            ..
+        }) => (end_time, last_liquidation_date),
+
+        Some(LiquidityRequestState::LtvLoan {
+            end_time,
+            margin_call_deadline,
+            processing_liquidation,
+            last_liquidation_date,
+            ..
+        }) => {
+            let available_at = match margin_call_deadline {
+                _ if processing_liquidation => env.block.time,
+                Some(deadline) => deadline.min(end_time),
+                None => end_time,
+            };
+            (available_at, last_liquidation_date)
+        }
+
+        _ => return Err(StdError::generic_err("No active loan to liquidate")),
+    };
+
+    // Once the loan is liquidatable, the next step can run immediately if there is
+    // collateral to claim, otherwise it has to wait for the next pending unbonding to mature
+    let denom_str = deps.querier.query_bonded_denom()?;
+    let executable_at = if env.block.time < available_at {
+        available_at
+    } else {
+        let available_collateral_balance = deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom_str.clone())?;
+        let (_, accumulated_rewards) = helpers::query_staking_info(&deps, &env)?;
+        let next_completion_time = helpers::get_all_pending_unbondings(deps.storage, &env)?
+            .iter()
+            .map(|unbonding| unbonding.completion_time)
+            .min();
+        match (last_liquidation_date, next_completion_time) {
+            (Some(_), Some(next_completion_time))
+                if available_collateral_balance.amount.is_zero()
+                    && accumulated_rewards.is_zero() =>
+            {
+                next_completion_time
+            }
+            _ => env.block.time,
+        }
+    };
+
+    Ok(NextLiquidationStepResponse {
+        executable_at,
+        keeper_fee: Coin {
+            denom: denom_str,
+            amount: helpers::get_option_keeper_fee(&option)
+                .saturating_sub(helpers::get_keeper_fee_paid(&option.state)),
+        },
+    })
+}
@@ -1,5 +1,5 @@
 use crate::types::LiquidityRequestMsg;
-use cosmwasm_std::{Coin, StdError, Uint128};
+use cosmwasm_std::{Coin, Decimal, StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -25,6 +25,24 @@ pub enum ContractError {
     #[error("LiquidityRequestIsActive")]
     LiquidityRequestIsActive {},
 
+    #[error("Lender position can not be transferred to {address:?}")]
+    InvalidLenderPositionRecipient { address: String },
+
+    #[error("Lender position is shared by {lenders:?} lenders, who must all agree to this action")]
+    LenderPositionIsSyndicated { lenders: u32 },
+
+    #[error("Lender position is held as token_id: {token_id:?} of collection: {collection:?}")]
+    LenderPositionIsTokenized {
+        collection: String,
+        token_id: String,
+    },
+
+    #[error("Vault ownership is held as token_id: {token_id:?} of collection: {collection:?}")]
+    OwnershipIsTokenized {
+        collection: String,
+        token_id: String,
+    },
+
     #[error("InvalidLiquidityRequestOption")]
     InvalidLiquidityRequestOption {},
 
@@ -34,16 +52,74 @@ pub enum ContractError {
         validator_delegation: Uint128,
     },
 
+    #[error("MaxUnbondingEntriesReached: validator: {validator:?}")]
+    MaxUnbondingEntriesReached { validator: String },
+
     #[error("InsufficientBalance: Required {required:?}, Available {available:?}")]
     InsufficientBalance { required: Coin, available: Coin },
 
+    #[error("ReservedBalanceUnderflow: releasing {amount:?}, reserved {reserved:?}")]
+    ReservedBalanceUnderflow { reserved: Coin, amount: Uint128 },
+
+    #[error("InsufficientWithdrawableBalance: Required {required:?}, Available {available:?}, Reserved {reserved:?} of {denom:?}")]
+    InsufficientWithdrawableBalance {
+        denom: String,
+        required: Uint128,
+        available: Uint128,
+        reserved: Uint128,
+    },
+
     #[error("Repay: {amount:?}, owed to the lender for the defaulted fixed term loan")]
     ClearOutstandingDebt { amount: Coin },
 
+    #[error("LoanNotYetLiquidatable: available_at: {available_at}")]
+    LoanNotYetLiquidatable { available_at: Timestamp },
+
+    #[error("KeeperFeeTooHigh: keeper_fee: {keeper_fee}, max: {max}")]
+    KeeperFeeTooHigh { keeper_fee: Uint128, max: Uint128 },
+
+    #[error(
+        "InvalidLoanExtension: new_end_time: {new_end_time} must be after end_time: {end_time}"
+    )]
+    InvalidLoanExtension {
+        new_end_time: Timestamp,
+        end_time: Timestamp,
+    },
+
+    #[error("LoanExtensionWindowClosed: closed_at: {closed_at}")]
+    LoanExtensionWindowClosed { closed_at: Timestamp },
+
+    #[error("NoPendingLoanExtension")]
+    NoPendingLoanExtension {},
+
+    #[error("LoanIsHealthy: ltv: {ltv}, margin_call_ltv: {margin_call_ltv}")]
+    LoanIsHealthy {
+        ltv: Decimal,
+        margin_call_ltv: Decimal,
+    },
+
+    #[error("MarginCallAlreadyActive: deadline: {deadline}")]
+    MarginCallAlreadyActive { deadline: Timestamp },
+
+    #[error("AlreadyRepaidExceedsAmountDue: already_repaid: {already_repaid:?}, amount_due: {amount_due:?}")]
+    AlreadyRepaidExceedsAmountDue {
+        already_repaid: Uint128,
+        amount_due: Coin,
+    },
+
+    #[error("InvalidRepaymentAmount: amount: {amount:?}, outstanding: {outstanding:?}")]
+    InvalidRepaymentAmount { amount: Uint128, outstanding: Coin },
+
     #[error("Option not exact match. Required: {required:?}, On Record: {on_record:?}")]
     OptionNotExactMatch {
-        required: LiquidityRequestMsg,
-        on_record: LiquidityRequestMsg,
+        required: Box<LiquidityRequestMsg>,
+        on_record: Box<LiquidityRequestMsg>,
+    },
+
+    #[error("Counter offer not exact match. Required: {required:?}, On Record: {on_record:?}")]
+    CounterOfferNotExactMatch {
+        required: Uint128,
+        on_record: Uint128,
     },
 
     #[error("Caller already has a pending counter offer")]
@@ -58,6 +134,21 @@ pub enum ContractError {
         highest_offer: Uint128,
     },
 
+    #[error("Contribution of {amount:?} must be greater than zero and not exceed the remaining {remaining:?}")]
+    InvalidContributionAmount { amount: Uint128, remaining: Coin },
+
+    #[error("InvalidFactory: {factory:?} must instantiate the vault as its admin")]
+    InvalidFactory { factory: String },
+
+    #[error("Cannot migrate from contract: {contract:?}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("Cannot migrate from version {stored_version:?} to older version {new_version:?}")]
+    MigrationDowngradeNotAllowed {
+        stored_version: String,
+        new_version: String,
+    },
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
@@ -1,14 +1,24 @@
 use crate::{
-    state::{counter_offer_list, LIQUIDITY_REQUEST_STATE, MAX_COUNTER_OFFERS},
+    msg::{
+        Cw721OwnerOfResponse, Cw721QueryMsg, ExecuteMsg, LoanHealthResponse, OraclePriceResponse,
+        OracleQueryMsg, SudomodConfigResponse, SudomodHookMsg, SudomodParamsResponse,
+        SudomodQueryMsg,
+    },
+    state::{
+        counter_offer_list, CONFIG, LENDER_POSITION_SEQ, LENDER_SHARES, LIQUIDITY_REQUEST_STATE,
+        MAX_UNBONDING_ENTRIES, RESERVED_BALANCES, SECONDS_PER_DAY, UNBONDINGS,
+    },
     types::{
-        ActiveOption, CounterOfferProposal, LiquidityRequestMsg, LiquidityRequestState,
-        LiquidityRequestStatus,
+        ActiveOption, CounterOfferProposal, LenderPosition, LenderShare, LiquidationPayees,
+        LiquidityCommission, LiquidityRequestMsg, LiquidityRequestState, LiquidityRequestStatus,
+        Unbonding, UnbondingStrategy,
     },
     ContractError,
 };
 use cosmwasm_std::{
-    Addr, BankMsg, Coin, CosmosMsg, Delegation, Deps, DepsMut, DistributionMsg, Env, StakingMsg,
-    StdError, StdResult, Storage, Uint128,
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Delegation, Deps, DepsMut, DistributionMsg,
+    Env, MessageInfo, Order, QuerierWrapper, StakingMsg, StdError, StdResult, Storage, Timestamp,
+    Uint128, WasmMsg,
 };
 
 pub fn ensure_validator_is_active(deps: &DepsMut, validator: &str) -> Result<(), ContractError> {
@@ -58,6 +68,62 @@ pub fn get_balace_for_demon(
     })
 }
 
+pub fn get_reserved_balance_for_denom(storage: &dyn Storage, denom: &str) -> StdResult<Uint128> {
+    Ok(RESERVED_BALANCES
+        .may_load(storage, denom)?
+        .unwrap_or_default())
+}
+
+pub fn reserve_balance(storage: &mut dyn Storage, denom: &str, amount: Uint128) -> StdResult<()> {
+    RESERVED_BALANCES.update(storage, denom, |reserved| -> StdResult<_> {
+        Ok(reserved.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(())
+}
+
+pub fn release_balance(
+    storage: &mut dyn Storage,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    // Releasing more than was reserved means the ledger is out of sync with the escrowed funds
+    RESERVED_BALANCES.update(storage, denom, |reserved| -> Result<_, ContractError> {
+        let reserved = reserved.unwrap_or_default();
+        reserved
+            .checked_sub(amount)
+            .map_err(|_| ContractError::ReservedBalanceUnderflow {
+                reserved: Coin {
+                    denom: denom.to_string(),
+                    amount: reserved,
+                },
+                amount,
+            })
+    })?;
+
+    Ok(())
+}
+
+pub fn get_withdrawable_balance_for_denom(
+    deps: &DepsMut,
+    env: &Env,
+    denom_str: String,
+) -> Result<(Coin, Coin), ContractError> {
+    let balance = get_balace_for_demon(deps, env, denom_str.clone())?;
+    let reserved = get_reserved_balance_for_denom(deps.storage, &denom_str)?;
+
+    Ok((
+        Coin {
+            amount: balance.amount.saturating_sub(reserved),
+            denom: denom_str.clone(),
+        },
+        Coin {
+            amount: reserved,
+            denom: denom_str,
+        },
+    ))
+}
+
 pub fn validate_exact_input_amount(
     coins: &[Coin],
     given_amount: Uint128,
@@ -162,7 +228,7 @@ pub fn validate_amount_to_delegate(
     amount_to_delegate: Uint128,
     denom_str: String,
 ) -> Result<(), ContractError> {
-    let balance = get_balace_for_demon(deps, env, denom_str.clone())?;
+    let (balance, _) = get_withdrawable_balance_for_denom(deps, env, denom_str.clone())?;
     if balance.amount < amount_to_delegate {
         return Err(ContractError::InsufficientBalance {
             available: balance,
@@ -187,6 +253,261 @@ pub fn get_bank_transfer_to_msg(recipient: &Addr, denom: &str, amount: Uint128)
     .into()
 }
 
+/// Verifies that factory is the contract instantiating the vault as its wasm admin,
+/// and that it answers the sudomod queries read by the vault
+pub fn validate_factory(
+    deps: &DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    factory: &Addr,
+) -> Result<(), ContractError> {
+    let invalid_factory = || ContractError::InvalidFactory {
+        factory: factory.to_string(),
+    };
+
+    let vault_info = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address.to_string())?;
+    if info.sender.ne(factory) || vault_info.admin.ne(&Some(factory.to_string())) {
+        return Err(invalid_factory());
+    }
+
+    deps.querier
+        .query_wasm_smart::<SudomodConfigResponse>(factory, &SudomodQueryMsg::Info {})
+        .map_err(|_| invalid_factory())?;
+
+    Ok(())
+}
+
+pub fn get_sudomod_hook_msg(
+    deps: &DepsMut,
+    sudomod: &Addr,
+    msg: &SudomodHookMsg,
+) -> StdResult<Option<CosmosMsg>> {
+    // Vaults instantiated directly by an account have no sudomod contract to notify
+    if deps
+        .querier
+        .query_wasm_contract_info(sudomod.to_string())
+        .is_err()
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        WasmMsg::Execute {
+            contract_addr: sudomod.to_string(),
+            msg: to_binary(msg)?,
+            funds: vec![],
+        }
+        .into(),
+    ))
+}
+
+pub fn query_token_owner(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    token_id: &str,
+) -> StdResult<Addr> {
+    let response: Cw721OwnerOfResponse = querier.query_wasm_smart(
+        collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.to_string(),
+            include_expired: None,
+        },
+    )?;
+    Ok(Addr::unchecked(response.owner))
+}
+
+/// Returns the owner of token_id, or None when collection can not tell,
+/// e.g. once the token was burned
+pub fn may_query_token_owner(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    token_id: &str,
+) -> Option<Addr> {
+    query_token_owner(querier, collection, token_id).ok()
+}
+
+/// Makes the owner of the ownership token, if any, the owner of the vault.
+/// The last known owner is kept when the collection can not tell who owns the token.
+/// Returns the hook message keeping sudomod in sync when the owner changed.
+pub fn sync_vault_owner(deps: &mut DepsMut) -> StdResult<Option<CosmosMsg>> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let owner = match &config.ownership_token {
+        Some(token) => {
+            match may_query_token_owner(&deps.querier, &token.collection, &token.token_id) {
+                Some(owner) => owner,
+                None => return Ok(None),
+            }
+        }
+        None => return Ok(None),
+    };
+    if owner.eq(&config.owner) {
+        return Ok(None);
+    }
+
+    config.owner = owner;
+    CONFIG.save(deps.storage, &config)?;
+    clear_pending_loan_extension_on_vault(deps.storage)?;
+    get_sudomod_hook_msg(
+        deps,
+        &config.factory,
+        &SudomodHookMsg::VaultOwnerChanged {
+            new_owner: config.owner.to_string(),
+        },
+    )
+}
+
+/// Reserves the token_id of the lender position minted by sudomod for lender,
+/// when sudomod has a lender position collection.
+/// Returns the position with the hook message minting it.
+pub fn open_lender_position(
+    deps: &mut DepsMut,
+    env: &Env,
+    lender: &Addr,
+) -> StdResult<Option<(LenderPosition, CosmosMsg)>> {
+    let factory = CONFIG.load(deps.storage)?.factory;
+    let factory_config: SudomodConfigResponse = deps
+        .querier
+        .query_wasm_smart(&factory, &SudomodQueryMsg::Info {})?;
+    let collection = match factory_config.lender_position_collection {
+        Some(collection) => collection,
+        None => return Ok(None),
+    };
+
+    // Token ids are unique to this vault, so sudomod can tell which vault owns them
+    let seq = LENDER_POSITION_SEQ
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    LENDER_POSITION_SEQ.save(deps.storage, &seq)?;
+    let token_id = format!("{}/{}", env.contract.address, seq);
+
+    let hook_msg = get_sudomod_hook_msg(
+        deps,
+        &factory,
+        &SudomodHookMsg::LenderPositionOpened {
+            token_id: token_id.clone(),
+            lender: lender.to_string(),
+        },
+    )?;
+    Ok(hook_msg.map(|msg| {
+        (
+            LenderPosition {
+                collection,
+                token_id,
+            },
+            msg,
+        )
+    }))
+}
+
+/// Returns the hook message burning position once the option it represents is closed
+pub fn get_lender_position_closed_msg(
+    deps: &DepsMut,
+    position: LenderPosition,
+) -> StdResult<Option<CosmosMsg>> {
+    let is_open = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .and_then(|option| option.lender_position)
+        .is_some_and(|open_position| open_position == position);
+    if is_open {
+        return Ok(None);
+    }
+
+    let factory = CONFIG.load(deps.storage)?.factory;
+    get_sudomod_hook_msg(
+        deps,
+        &factory,
+        &SudomodHookMsg::LenderPositionClosed {
+            collection: position.collection.to_string(),
+            token_id: position.token_id,
+        },
+    )
+}
+
+/// Drops any loan extension pending on option, as its terms were agreed
+/// between parties that no longer both hold the loan.
+pub fn clear_pending_loan_extension(option: &mut ActiveOption) {
+    if let Some(LiquidityRequestState::FixedTermLoan {
+        ref mut pending_extension,
+        ..
+    }) = option.state
+    {
+        *pending_extension = None;
+    }
+}
+
+/// Drops any loan extension pending on the active option of the vault,
+/// called when the owner of the vault changes.
+pub fn clear_pending_loan_extension_on_vault(storage: &mut dyn Storage) -> StdResult<()> {
+    if let Some(mut option) = LIQUIDITY_REQUEST_STATE.may_load(storage)?.flatten() {
+        clear_pending_loan_extension(&mut option);
+        LIQUIDITY_REQUEST_STATE.save(storage, &Some(option))?;
+    }
+    Ok(())
+}
+
+/// Hands option from lender to new_lender, along with the share of lender.
+/// Any pending loan extension is dropped. Returns the share moved.
+pub fn move_lender_position(
+    storage: &mut dyn Storage,
+    option: &mut ActiveOption,
+    lender: &Addr,
+    new_lender: &Addr,
+) -> StdResult<Uint128> {
+    option.lender = Some(new_lender.clone());
+    clear_pending_loan_extension(option);
+
+    // Move the share of lender, if any, to new_lender
+    let share = LENDER_SHARES.may_load(storage, lender)?.unwrap_or_default();
+    if !share.is_zero() {
+        LENDER_SHARES.remove(storage, lender);
+        LENDER_SHARES.update(storage, new_lender, |amount| -> StdResult<_> {
+            Ok(amount.unwrap_or_default() + share)
+        })?;
+    }
+
+    Ok(share)
+}
+
+/// Returns whether msg is sent by the lender or pays the lender,
+/// so the lender must first be synced with the owner of the lender position token
+pub fn is_lender_msg(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Delegate { .. }
+            | ExecuteMsg::Redelegate { .. }
+            | ExecuteMsg::ClaimDelegatorRewards {}
+            | ExecuteMsg::RepayLoan { .. }
+            | ExecuteMsg::ProposeLoanExtension { .. }
+            | ExecuteMsg::AcceptLoanExtension {}
+            | ExecuteMsg::MarginCall {}
+            | ExecuteMsg::LiquidateCollateral {}
+            | ExecuteMsg::Vote { .. }
+            | ExecuteMsg::TransferLenderPosition { .. }
+    )
+}
+
+/// Lets the owner of the lender position token, if any, act as the lender on the active option.
+/// The lender is left unchanged when the collection can not tell who owns the token.
+pub fn sync_lender_position(deps: &mut DepsMut) -> StdResult<()> {
+    let mut option = match LIQUIDITY_REQUEST_STATE.load(deps.storage)? {
+        Some(option) => option,
+        None => return Ok(()),
+    };
+
+    if let (Some(lender), Some(position)) = (option.lender.clone(), &option.lender_position) {
+        let owner = may_query_token_owner(&deps.querier, &position.collection, &position.token_id);
+        if let Some(owner) = owner.filter(|owner| owner.ne(&lender)) {
+            move_lender_position(deps.storage, &mut option, &lender, &owner)?;
+            LIQUIDITY_REQUEST_STATE.save(deps.storage, &Some(option))?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn get_liquidity_request_status(deps: &DepsMut) -> StdResult<LiquidityRequestStatus> {
     let liquidity_request = LIQUIDITY_REQUEST_STATE.load(deps.storage)?;
     let status = match liquidity_request {
@@ -197,13 +518,191 @@ pub fn get_liquidity_request_status(deps: &DepsMut) -> StdResult<LiquidityReques
     Ok(status)
 }
 
-pub fn get_liquidity_comission(amount: Uint128) -> StdResult<Uint128> {
-    // Here we hardcode the liquidity_comission as 3/1000 or 0.3% of amount
+pub fn get_collateral_due(
+    collateral_amount: Uint128,
+    requested_amount: &Coin,
+    interest_amount: Uint128,
+    already_repaid: Uint128,
+) -> Uint128 {
+    // Reduce the collateral owed to the lender pro rata by what was already repaid
+    let repayment_amount = requested_amount.amount + interest_amount;
+    collateral_amount.multiply_ratio(
+        repayment_amount.saturating_sub(already_repaid),
+        repayment_amount,
+    )
+}
+
+pub fn get_interest_due(
+    option: &LiquidityRequestMsg,
+    interest_amount: Uint128,
+    start_time: Timestamp,
+    end_time: Timestamp,
+    now: Timestamp,
+) -> Uint128 {
+    match option {
+        // Charge interest pro rata to the time elapsed since start_time, but never below
+        // min_interest_amount, when a loan is repaid early
+        LiquidityRequestMsg::FixedTermLoan {
+            prorate_interest_on_early_repayment: true,
+            min_interest_amount,
+            ..
+        } if now < end_time => {
+            let elapsed = now.seconds() - start_time.seconds();
+            let duration = end_time.seconds() - start_time.seconds();
+            interest_amount
+                .multiply_ratio(elapsed, duration)
+                .max(min_interest_amount.unwrap_or_default())
+        }
+        LiquidityRequestMsg::FixedTermLoan { .. } => interest_amount,
+        _ => Uint128::zero(),
+    }
+}
+
+/// Returns the requested_amount + interest_due + late_fee_due owed at now
+/// on a FixedTermLoan, less already_repaid
+pub fn get_outstanding_amount(
+    option: &LiquidityRequestMsg,
+    requested_amount: &Coin,
+    interest_amount: Uint128,
+    start_time: Timestamp,
+    end_time: Timestamp,
+    already_repaid: Uint128,
+    now: Timestamp,
+) -> Result<Uint128, ContractError> {
+    let interest_due = get_interest_due(option, interest_amount, start_time, end_time, now);
+    let late_fee_due = get_late_fee_due(option, end_time, now);
+    let amount_due = requested_amount.amount + interest_due + late_fee_due;
+    amount_due.checked_sub(already_repaid).map_err(|_| {
+        ContractError::AlreadyRepaidExceedsAmountDue {
+            already_repaid,
+            amount_due: Coin {
+                denom: requested_amount.denom.clone(),
+                amount: amount_due,
+            },
+        }
+    })
+}
+
+pub fn get_late_fee_due(
+    option: &LiquidityRequestMsg,
+    end_time: Timestamp,
+    now: Timestamp,
+) -> Uint128 {
+    match option {
+        // Charge late_fee_per_day for every started day past end_time, up to the grace period
+        LiquidityRequestMsg::FixedTermLoan {
+            grace_period_in_seconds,
+            late_fee_per_day,
+            ..
+        } if now > end_time => {
+            let seconds_late = (now.seconds() - end_time.seconds()).min(*grace_period_in_seconds);
+            let days_late = seconds_late.div_ceil(SECONDS_PER_DAY);
+            late_fee_per_day * Uint128::from(days_late)
+        }
+        _ => Uint128::zero(),
+    }
+}
+
+pub fn get_grace_period_end(option: &LiquidityRequestMsg, end_time: Timestamp) -> Timestamp {
+    match option {
+        LiquidityRequestMsg::FixedTermLoan {
+            grace_period_in_seconds,
+            ..
+        } => end_time.plus_seconds(*grace_period_in_seconds),
+        _ => end_time,
+    }
+}
+
+pub fn ensure_loan_can_be_extended(
+    option: &LiquidityRequestMsg,
+    end_time: Timestamp,
+    processing_liquidation: bool,
+    env: &Env,
+) -> Result<(), ContractError> {
+    let grace_period_end = get_grace_period_end(option, end_time);
+    if processing_liquidation || env.block.time >= grace_period_end {
+        return Err(ContractError::LoanExtensionWindowClosed {
+            closed_at: grace_period_end,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn get_next_due_date(
+    start_time: Timestamp,
+    installment_period_in_seconds: u64,
+    installments_settled: u32,
+) -> Timestamp {
+    start_time.plus_seconds(installment_period_in_seconds * (u64::from(installments_settled) + 1))
+}
+
+/// Returns the installments defaulted as of now, including the installments that are past due
+/// but not yet paid, and the slice of collateral_amount owed to the lender for them
+pub fn get_defaulted_installments(
+    collateral_amount: Uint128,
+    number_of_installments: u32,
+    installment_period_in_seconds: u64,
+    start_time: Timestamp,
+    installments_paid: u32,
+    installments_defaulted: u32,
+    now: Timestamp,
+) -> (u32, Uint128) {
+    let elapsed_periods = (now.seconds() - start_time.seconds()) / installment_period_in_seconds;
+    let installments_due = elapsed_periods.min(u64::from(number_of_installments)) as u32;
+    let installments_missed =
+        installments_due.saturating_sub(installments_paid + installments_defaulted);
+    let updated_installments_defaulted = installments_defaulted + installments_missed;
+    let collateral_due =
+        collateral_amount.multiply_ratio(updated_installments_defaulted, number_of_installments);
+
+    (updated_installments_defaulted, collateral_due)
+}
+
+/// Returns the staking rewards that, valued at exchange_rate, cover amount
+pub fn get_rewards_needed(amount: Uint128, exchange_rate: Decimal) -> Uint128 {
+    let rewards_needed = amount.multiply_ratio(Decimal::one().atomics(), exchange_rate.atomics());
+    if rewards_needed * exchange_rate < amount {
+        rewards_needed + Uint128::one()
+    } else {
+        rewards_needed
+    }
+}
+
+pub fn get_liquidity_comission(amount: Uint128, rate: Decimal) -> StdResult<Uint128> {
     amount
-        .checked_mul(Uint128::from(3u128))
-        .map_err(StdError::overflow)?
-        .checked_div(Uint128::from(1000u128))
-        .map_err(StdError::divide_by_zero)
+        .checked_multiply_ratio(rate.atomics(), Decimal::one().atomics())
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+pub fn query_protocol_params(deps: &DepsMut) -> StdResult<SudomodParamsResponse> {
+    // Query the protocol params currently set on the factory of this vault
+    let factory = CONFIG.load(deps.storage)?.factory;
+    deps.querier
+        .query_wasm_smart(factory, &SudomodQueryMsg::Params {})
+}
+
+pub fn query_liquidity_commission(
+    deps: &DepsMut,
+    requested_amount: &Coin,
+) -> StdResult<LiquidityCommission> {
+    // Query the commission terms currently set on the factory of this vault
+    let factory = CONFIG.load(deps.storage)?.factory;
+    let factory_config: SudomodConfigResponse = deps
+        .querier
+        .query_wasm_smart(factory, &SudomodQueryMsg::Info {})?;
+
+    Ok(LiquidityCommission {
+        rate: factory_config.liquidity_commission,
+        amount: Coin {
+            denom: requested_amount.denom.clone(),
+            amount: get_liquidity_comission(
+                requested_amount.amount,
+                factory_config.liquidity_commission,
+            )?,
+        },
+        fee_collector: factory_config.fee_collector,
+    })
 }
 
 pub fn process_lender_claims(
@@ -212,7 +711,7 @@ pub fn process_lender_claims(
     liquidity_request_state: LiquidityRequestState,
     lender: Addr,
     total_rewards_claimed: Uint128,
-) -> Result<Option<CosmosMsg>, ContractError> {
+) -> Result<Vec<CosmosMsg>, ContractError> {
     let denom_str = deps.querier.query_bonded_denom()?;
     match liquidity_request_state {
         LiquidityRequestState::FixedInterestRental {
@@ -247,12 +746,13 @@ pub fn process_lender_claims(
                 }
             })?;
 
-            // Return cosmos_msg to transfer funds to the lender
-            Ok(Some(get_bank_transfer_to_msg(
+            // Return cosmos_msgs to transfer funds to the lenders
+            Ok(get_lender_transfer_msgs(
+                deps.storage,
                 &lender,
                 &denom_str,
                 amount_to_send_to_lender,
-            )))
+            )?)
         }
 
         LiquidityRequestState::FixedTermRental {
@@ -299,63 +799,466 @@ pub fn process_lender_claims(
                 }
             })?;
 
-            // Return cosmos_msg to transfer funds to the lender
-            Ok(Some(get_bank_transfer_to_msg(
+            // Return cosmos_msgs to transfer funds to the lenders
+            Ok(get_lender_transfer_msgs(
+                deps.storage,
                 &lender,
                 &denom_str,
                 amount_to_send_to_lender,
-            )))
+            )?)
         }
 
-        // FixedTermLoan does not currently allow sharing delegator rewards with lender
-        _default => Ok(None),
+        LiquidityRequestState::RewardsBackedLoan {
+            requested_amount,
+            interest_amount,
+            collateral_amount,
+            exchange_rate,
+            start_time,
+            end_time,
+            rewards_claimed,
+            already_repaid,
+            processing_liquidation: false,
+            already_claimed,
+            last_liquidation_date,
+            keeper_fee_paid,
+        } => {
+            // Route just enough rewards to the lender to cover the outstanding
+            // requested_amount + interest_amount, valued at exchange_rate
+            let outstanding_amount = requested_amount.amount + interest_amount - already_repaid;
+            let (amount_to_send_to_lender, amount_repaid) =
+                if total_rewards_claimed * exchange_rate < outstanding_amount {
+                    (total_rewards_claimed, total_rewards_claimed * exchange_rate)
+                } else {
+                    (
+                        get_rewards_needed(outstanding_amount, exchange_rate),
+                        outstanding_amount,
+                    )
+                };
+            if amount_to_send_to_lender.is_zero() {
+                return Ok(vec![]);
+            }
+
+            // Update the liquidity request state
+            let updated_already_repaid = already_repaid + amount_repaid;
+            LIQUIDITY_REQUEST_STATE.update(deps.storage, |data| -> Result<_, ContractError> {
+                if updated_already_repaid < requested_amount.amount + interest_amount {
+                    let mut option = data.unwrap();
+                    option.state = Some(LiquidityRequestState::RewardsBackedLoan {
+                        requested_amount,
+                        interest_amount,
+                        collateral_amount,
+                        exchange_rate,
+                        start_time,
+                        end_time,
+                        rewards_claimed: rewards_claimed + amount_to_send_to_lender,
+                        already_repaid: updated_already_repaid,
+                        processing_liquidation: false,
+                        already_claimed,
+                        last_liquidation_date,
+                        keeper_fee_paid,
+                    });
+
+                    Ok(Some(option))
+                } else {
+                    // Close option as the loan has been repaid from staking rewards
+                    Ok(None)
+                }
+            })?;
+
+            // Return cosmos_msgs to transfer funds to the lenders
+            Ok(get_lender_transfer_msgs(
+                deps.storage,
+                &lender,
+                &denom_str,
+                amount_to_send_to_lender,
+            )?)
+        }
+
+        // FixedTermLoan and LtvLoan do not currently allow sharing delegator rewards
+        // with lender, and rewards on a liquidating RewardsBackedLoan are claimed as collateral
+        _default => Ok(vec![]),
     }
 }
 
+/// Returns the unbondings between the vault and validator that are yet to mature
+pub fn get_pending_unbondings(
+    storage: &dyn Storage,
+    env: &Env,
+    validator: &str,
+) -> StdResult<Vec<Unbonding>> {
+    Ok(UNBONDINGS
+        .may_load(storage, validator)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|unbonding| unbonding.completion_time.gt(&env.block.time))
+        .collect())
+}
+
+/// Returns the unbondings started by the vault that are yet to mature,
+/// across all validators
+pub fn get_all_pending_unbondings(storage: &dyn Storage, env: &Env) -> StdResult<Vec<Unbonding>> {
+    let mut pending_unbondings = vec![];
+    for item in UNBONDINGS.range(storage, None, None, Order::Ascending) {
+        let (_, unbondings) = item?;
+        pending_unbondings.extend(
+            unbondings
+                .into_iter()
+                .filter(|unbonding| unbonding.completion_time.gt(&env.block.time)),
+        );
+    }
+
+    Ok(pending_unbondings)
+}
+
+/// Records a new unbonding between the vault and validator, maturing after unbonding_period,
+/// and prunes the unbondings that have matured
+pub fn record_unbonding(
+    storage: &mut dyn Storage,
+    env: &Env,
+    validator: &str,
+    amount: Coin,
+    unbonding_period: u64,
+) -> Result<(), ContractError> {
+    let mut unbondings = get_pending_unbondings(storage, env, validator)?;
+    if unbondings.len() >= MAX_UNBONDING_ENTRIES {
+        return Err(ContractError::MaxUnbondingEntriesReached {
+            validator: validator.to_string(),
+        });
+    }
+
+    unbondings.push(Unbonding {
+        validator: validator.to_string(),
+        amount,
+        start_time: env.block.time,
+        completion_time: env.block.time.plus_seconds(unbonding_period),
+    });
+    UNBONDINGS.save(storage, validator, &unbondings)?;
+
+    Ok(())
+}
+
+/// Unbonds up to max_amount from the validators that are below MAX_UNBONDING_ENTRIES,
+/// following the unbonding_strategy of the vault
 pub fn unbond_tokens_from_validators(
-    deps: &DepsMut,
+    deps: &mut DepsMut,
     env: &Env,
     max_amount: Uint128,
+    unbonding_period: u64,
 ) -> Result<Vec<StakingMsg>, ContractError> {
-    let mut accumulated_unbonding_amount = Uint128::zero();
-    let mut unstaking_msgs = vec![];
+    let strategy = CONFIG.load(deps.storage)?.unbonding_strategy;
 
-    // Query all delegations, and unbond enough amount to payback max_amount if available
-    deps.querier
+    // Query all delegations, skipping validators that cannot take another unbonding entry
+    let mut delegations = vec![];
+    for d in deps
+        .querier
         .query_all_delegations(env.contract.address.clone())?
-        .iter()
-        .for_each(|d| {
-            if accumulated_unbonding_amount < max_amount {
-                let outstanding_amount = max_amount - accumulated_unbonding_amount;
-                let amount_to_unstake = if outstanding_amount < d.amount.amount {
-                    outstanding_amount
-                } else {
-                    d.amount.amount
-                };
+    {
+        let pending_unbondings = get_pending_unbondings(deps.storage, env, &d.validator)?;
+        if !d.amount.amount.is_zero() && pending_unbondings.len() < MAX_UNBONDING_ENTRIES {
+            delegations.push(d);
+        }
+    }
 
-                // add unbonding msg
-                unstaking_msgs.push(StakingMsg::Undelegate {
-                    validator: d.validator.clone(),
-                    amount: Coin {
-                        denom: d.amount.denom.clone(),
-                        amount: amount_to_unstake,
-                    },
-                });
-
-                // update amount unbonded so far
-                accumulated_unbonding_amount += amount_to_unstake;
-            }
+    // Order the largest delegations first, they also absorb the rounding of ProRata
+    if strategy != UnbondingStrategy::Sequential {
+        delegations.sort_by_key(|d| std::cmp::Reverse(d.amount.amount));
+    }
+
+    let amounts_to_unstake = match strategy {
+        UnbondingStrategy::ProRata => get_pro_rata_unbonding_amounts(&delegations, max_amount),
+        UnbondingStrategy::Sequential | UnbondingStrategy::LargestFirst => {
+            get_sequential_unbonding_amounts(&delegations, max_amount)
+        }
+    };
+
+    // Add unbonding msgs, and record the unbondings they start
+    let mut unstaking_msgs = vec![];
+    for (d, amount_to_unstake) in delegations.into_iter().zip(amounts_to_unstake) {
+        if amount_to_unstake.is_zero() {
+            continue;
+        }
+
+        let amount = Coin {
+            denom: d.amount.denom,
+            amount: amount_to_unstake,
+        };
+        record_unbonding(
+            deps.storage,
+            env,
+            &d.validator,
+            amount.clone(),
+            unbonding_period,
+        )?;
+        unstaking_msgs.push(StakingMsg::Undelegate {
+            validator: d.validator,
+            amount,
         });
+    }
 
     // Respond
     Ok(unstaking_msgs)
 }
 
+/// Drains delegations one by one until max_amount is unbonded
+fn get_sequential_unbonding_amounts(
+    delegations: &[Delegation],
+    max_amount: Uint128,
+) -> Vec<Uint128> {
+    let mut outstanding_amount = max_amount;
+    delegations
+        .iter()
+        .map(|d| {
+            let amount_to_unstake = outstanding_amount.min(d.amount.amount);
+            outstanding_amount -= amount_to_unstake;
+            amount_to_unstake
+        })
+        .collect()
+}
+
+/// Splits max_amount across delegations in proportion to their size
+fn get_pro_rata_unbonding_amounts(delegations: &[Delegation], max_amount: Uint128) -> Vec<Uint128> {
+    let total_delegated: Uint128 = delegations.iter().map(|d| d.amount.amount).sum();
+    if total_delegated <= max_amount {
+        return delegations.iter().map(|d| d.amount.amount).collect();
+    }
+
+    let mut amounts_to_unstake: Vec<Uint128> = delegations
+        .iter()
+        .map(|d| d.amount.amount.multiply_ratio(max_amount, total_delegated))
+        .collect();
+
+    // Rounding down leaves less than a token per delegation,
+    // which is unbonded from the delegations in order
+    let mut remainder = max_amount - amounts_to_unstake.iter().sum::<Uint128>();
+    for (amount_to_unstake, d) in amounts_to_unstake.iter_mut().zip(delegations) {
+        let extra_amount = remainder.min(d.amount.amount - *amount_to_unstake);
+        *amount_to_unstake += extra_amount;
+        remainder -= extra_amount;
+    }
+
+    amounts_to_unstake
+}
+
+/// Values the collateral of an LtvLoan at the oracle price,
+/// and returns its loan-to-value ratio and health factor
+pub fn get_loan_health(
+    querier: &QuerierWrapper,
+    state: &LiquidityRequestState,
+) -> StdResult<LoanHealthResponse> {
+    if let LiquidityRequestState::LtvLoan {
+        requested_amount,
+        interest_amount,
+        collateral_amount,
+        oracle,
+        margin_call_ltv,
+        already_repaid,
+        margin_call_deadline,
+        ..
+    } = state
+    {
+        let oracle_price: OraclePriceResponse = querier.query_wasm_smart(
+            oracle,
+            &OracleQueryMsg::Price {
+                base: querier.query_bonded_denom()?,
+                quote: requested_amount.denom.clone(),
+            },
+        )?;
+        let collateral_value = *collateral_amount * oracle_price.price;
+        let debt = (requested_amount.amount + interest_amount).saturating_sub(*already_repaid);
+
+        // A worthless collateral has an unbounded ltv, and a repaid loan an unbounded health
+        let ltv = Decimal::checked_from_ratio(debt, collateral_value).unwrap_or(Decimal::MAX);
+        let health_factor = margin_call_ltv.checked_div(ltv).unwrap_or(Decimal::MAX);
+
+        Ok(LoanHealthResponse {
+            collateral_value: Coin {
+                denom: requested_amount.denom.clone(),
+                amount: collateral_value,
+            },
+            debt: Coin {
+                denom: requested_amount.denom.clone(),
+                amount: debt,
+            },
+            ltv,
+            health_factor,
+            margin_call_deadline: *margin_call_deadline,
+        })
+    } else {
+        Err(StdError::generic_err("No active LtvLoan"))
+    }
+}
+
+pub fn get_keeper_fee(option: &LiquidityRequestMsg) -> Uint128 {
+    match option {
+        LiquidityRequestMsg::FixedTermLoan { keeper_fee, .. }
+        | LiquidityRequestMsg::InstallmentLoan { keeper_fee, .. }
+        | LiquidityRequestMsg::RewardsBackedLoan { keeper_fee, .. }
+        | LiquidityRequestMsg::LtvLoan { keeper_fee, .. } => *keeper_fee,
+        _ => Uint128::zero(),
+    }
+}
+
+/// Returns the keeper_fee of an accepted option, capped by the max_keeper_fee
+/// of the protocol params it was accepted under
+pub fn get_option_keeper_fee(option: &ActiveOption) -> Uint128 {
+    let keeper_fee = get_keeper_fee(&option.msg);
+    match &option.protocol_params {
+        Some(params) => keeper_fee.min(params.max_keeper_fee),
+        None => keeper_fee,
+    }
+}
+
+pub fn get_keeper_fee_paid(state: &Option<LiquidityRequestState>) -> Uint128 {
+    match state {
+        Some(LiquidityRequestState::FixedTermLoan {
+            keeper_fee_paid, ..
+        })
+        | Some(LiquidityRequestState::InstallmentLoan {
+            keeper_fee_paid, ..
+        })
+        | Some(LiquidityRequestState::RewardsBackedLoan {
+            keeper_fee_paid, ..
+        })
+        | Some(LiquidityRequestState::LtvLoan {
+            keeper_fee_paid, ..
+        }) => *keeper_fee_paid,
+        _ => Uint128::zero(),
+    }
+}
+
+pub fn get_liquidation_payees(
+    deps: &DepsMut,
+    caller: &Addr,
+) -> Result<LiquidationPayees, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let option = LIQUIDITY_REQUEST_STATE
+        .load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    let lender = option
+        .lender
+        .clone()
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // Only third parties driving the liquidation are paid the keeper_fee,
+    // which excludes every lender holding a share of the option
+    let keeper = if caller.ne(&config.owner)
+        && caller.ne(&lender)
+        && !LENDER_SHARES.has(deps.storage, caller)
+    {
+        Some(caller.clone())
+    } else {
+        None
+    };
+
+    Ok(LiquidationPayees {
+        lender,
+        keeper,
+        keeper_fee: get_option_keeper_fee(&option),
+        keeper_fee_paid: get_keeper_fee_paid(&option.state),
+    })
+}
+
+/// Claims up to collateral_due - already_claimed for the lender from the free collateral
+/// balance and staking rewards, and unbonds the remainder from validators
+/// when it is not already covered by the pending unbondings.
+/// The keeper, if any, is paid what is left of the keeper_fee out of the claimed collateral,
+/// which is added to payees.keeper_fee_paid.
+/// Returns the messages to dispatch with the updated already_claimed and last_liquidation_date.
+pub fn claim_collateral(
+    deps: &mut DepsMut,
+    env: &Env,
+    payees: &mut LiquidationPayees,
+    collateral_due: Uint128,
+    already_claimed: Uint128,
+    last_liquidation_date: Option<Timestamp>,
+) -> Result<(Vec<CosmosMsg>, Uint128, Option<Timestamp>), ContractError> {
+    let mut msgs: Vec<CosmosMsg> = vec![];
+
+    // Get available collateral balance
+    let denom_str = deps.querier.query_bonded_denom()?;
+    let available_collateral_balance = get_balace_for_demon(deps, env, denom_str.clone())?;
+
+    // Get available staking rewards
+    let (total_rewards_claimed, distribute_msgs) = accumulated_rewards(deps, env, None)?;
+
+    // Calculate total available collateral balance
+    let total_available_collateral_balance =
+        available_collateral_balance.amount + total_rewards_claimed;
+
+    // Calculate amount_to_claim which is limited by total_available_collateral_balance
+    let outstanding_debt = collateral_due - already_claimed;
+    let amount_to_claim = if outstanding_debt < total_available_collateral_balance {
+        outstanding_debt
+    } else {
+        total_available_collateral_balance
+    };
+
+    // Get the amount that will return to the vault balance as pending unbondings mature
+    let pending_unbonding_amount: Uint128 = get_all_pending_unbondings(deps.storage, env)?
+        .iter()
+        .map(|unbonding| unbonding.amount.amount)
+        .sum();
+
+    // Add messages to unbond outstanding collateral amount from staked tokens
+    // When total_available_collateral_balance and the pending unbondings are not
+    // enough to clear the debt
+    let updated_already_claimed = already_claimed + amount_to_claim;
+    let outstanding_collateral = collateral_due - updated_already_claimed;
+    let mut updated_last_liquidation_date = last_liquidation_date;
+    if outstanding_collateral > pending_unbonding_amount {
+        let unbonding_period = CONFIG.load(deps.storage)?.unbonding_period;
+        let undelegate_msgs = unbond_tokens_from_validators(
+            deps,
+            env,
+            outstanding_collateral - pending_unbonding_amount,
+            unbonding_period,
+        )?;
+
+        if !undelegate_msgs.is_empty() {
+            updated_last_liquidation_date = Some(env.block.time);
+            msgs.extend(undelegate_msgs.into_iter().map(CosmosMsg::from));
+        }
+    }
+
+    // Add messages to claim available staking rewards
+    if !total_rewards_claimed.is_zero() {
+        msgs.extend(distribute_msgs.into_iter().map(CosmosMsg::from));
+    }
+
+    // Add messages to send the keeper_fee to the keeper, and the rest of amount_to_claim
+    // to the lenders
+    let mut amount_to_lender = amount_to_claim;
+    if let Some(keeper) = &payees.keeper {
+        let keeper_fee = payees
+            .keeper_fee
+            .saturating_sub(payees.keeper_fee_paid)
+            .min(amount_to_claim);
+        if !keeper_fee.is_zero() {
+            amount_to_lender -= keeper_fee;
+            payees.keeper_fee_paid += keeper_fee;
+            msgs.push(get_bank_transfer_to_msg(keeper, &denom_str, keeper_fee));
+        }
+    }
+    if !amount_to_lender.is_zero() {
+        msgs.extend(get_lender_transfer_msgs(
+            deps.storage,
+            &payees.lender,
+            &denom_str,
+            amount_to_lender,
+        )?);
+    }
+
+    Ok((msgs, updated_already_claimed, updated_last_liquidation_date))
+}
+
 pub fn current_lender_can_cast_vote(deps: &DepsMut, env: &Env) -> Result<bool, ContractError> {
     let mut lender_can_cast_vote = false;
 
     if let Some(ActiveOption {
         msg: _,
+        liquidity_commission: _,
+        lender_position: _,
+        protocol_params: _,
         lender: _,
         state: Some(liquidity_request_state),
     }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
@@ -392,31 +1295,106 @@ pub fn current_lender_can_cast_vote(deps: &DepsMut, env: &Env) -> Result<bool, C
     Ok(lender_can_cast_vote)
 }
 
-pub fn outstanding_fixed_term_loan_debt(
-    deps: &DepsMut,
-    env: &Env,
-) -> Result<Uint128, ContractError> {
+pub fn outstanding_loan_debt(deps: &DepsMut, env: &Env) -> Result<Uint128, ContractError> {
     let mut outstanding_debt = Uint128::zero();
 
-    if let Some(ActiveOption {
-        msg: _,
-        lender: _,
-        state:
-            Some(LiquidityRequestState::FixedTermLoan {
-                requested_amount: _,
-                interest_amount: _,
+    match LIQUIDITY_REQUEST_STATE.load(deps.storage)? {
+        Some(ActiveOption {
+            state:
+                Some(LiquidityRequestState::FixedTermLoan {
+                    requested_amount,
+                    interest_amount,
+                    collateral_amount,
+                    end_time,
+                    already_claimed,
+                    already_repaid,
+                    ..
+                }),
+            ..
+        }) if env.block.time >= end_time => {
+            let collateral_due = get_collateral_due(
                 collateral_amount,
-                start_time: _,
-                end_time,
-                already_claimed,
-                last_liquidation_date: _,
-                processing_liquidation: _,
-            }),
-    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
-    {
-        if env.block.time >= end_time {
-            outstanding_debt = collateral_amount - already_claimed;
+                &requested_amount,
+                interest_amount,
+                already_repaid,
+            );
+            outstanding_debt = collateral_due - already_claimed;
         }
+
+        Some(ActiveOption {
+            state:
+                Some(LiquidityRequestState::RewardsBackedLoan {
+                    requested_amount,
+                    interest_amount,
+                    collateral_amount,
+                    end_time,
+                    already_claimed,
+                    already_repaid,
+                    ..
+                }),
+            ..
+        }) if env.block.time >= end_time => {
+            let collateral_due = get_collateral_due(
+                collateral_amount,
+                &requested_amount,
+                interest_amount,
+                already_repaid,
+            );
+            outstanding_debt = collateral_due - already_claimed;
+        }
+
+        // LtvLoans are also owed once their liquidation starts after an expired margin call
+        Some(ActiveOption {
+            state:
+                Some(LiquidityRequestState::LtvLoan {
+                    requested_amount,
+                    interest_amount,
+                    collateral_amount,
+                    end_time,
+                    already_claimed,
+                    already_repaid,
+                    processing_liquidation,
+                    ..
+                }),
+            ..
+        }) if env.block.time >= end_time || processing_liquidation => {
+            let collateral_due = get_collateral_due(
+                collateral_amount,
+                &requested_amount,
+                interest_amount,
+                already_repaid,
+            );
+            outstanding_debt = collateral_due - already_claimed;
+        }
+
+        // Missed installments are owed to the lender as soon as they are due
+        Some(ActiveOption {
+            state:
+                Some(LiquidityRequestState::InstallmentLoan {
+                    collateral_amount,
+                    number_of_installments,
+                    installment_period_in_seconds,
+                    start_time,
+                    installments_paid,
+                    installments_defaulted,
+                    already_claimed,
+                    ..
+                }),
+            ..
+        }) => {
+            let (_, collateral_due) = get_defaulted_installments(
+                collateral_amount,
+                number_of_installments,
+                installment_period_in_seconds,
+                start_time,
+                installments_paid,
+                installments_defaulted,
+                env.block.time,
+            );
+            outstanding_debt = collateral_due - already_claimed;
+        }
+
+        _ => {}
     }
 
     Ok(outstanding_debt)
@@ -426,30 +1404,14 @@ pub fn can_delegate_with_active_liquidity_request(
     deps: &DepsMut,
     env: &Env,
 ) -> Result<(), ContractError> {
-    if let Some(ActiveOption {
-        msg: _,
-        lender: _,
-        state:
-            Some(LiquidityRequestState::FixedTermLoan {
-                requested_amount: _,
-                interest_amount: _,
-                collateral_amount,
-                start_time: _,
-                end_time,
-                already_claimed,
-                last_liquidation_date: _,
-                processing_liquidation: _,
-            }),
-    }) = LIQUIDITY_REQUEST_STATE.load(deps.storage)?
-    {
-        if env.block.time >= end_time {
-            return Err(ContractError::ClearOutstandingDebt {
-                amount: Coin {
-                    amount: collateral_amount - already_claimed,
-                    denom: deps.querier.query_bonded_denom()?,
-                },
-            });
-        }
+    let outstanding_debt = outstanding_loan_debt(deps, env)?;
+    if !outstanding_debt.is_zero() {
+        return Err(ContractError::ClearOutstandingDebt {
+            amount: Coin {
+                amount: outstanding_debt,
+                denom: deps.querier.query_bonded_denom()?,
+            },
+        });
     }
 
     Ok(())
@@ -495,6 +1457,7 @@ pub fn map_liquidity_request_state(
                 interest_amount,
                 duration_in_seconds,
                 collateral_amount,
+                ..
             } => (
                 LiquidityRequestState::FixedTermLoan {
                     requested_amount: requested_amount.clone(),
@@ -503,8 +1466,95 @@ pub fn map_liquidity_request_state(
                     start_time: env.block.time,
                     end_time: env.block.time.plus_seconds(duration_in_seconds),
                     last_liquidation_date: None,
+                    keeper_fee_paid: Uint128::zero(),
                     already_claimed: Uint128::zero(),
+                    already_repaid: Uint128::zero(),
+                    processing_liquidation: false,
+                    pending_extension: None,
+                },
+                requested_amount,
+            ),
+
+            LiquidityRequestMsg::InstallmentLoan {
+                requested_amount,
+                collateral_amount,
+                number_of_installments,
+                installment_period_in_seconds,
+                installment_amount,
+                ..
+            } => (
+                LiquidityRequestState::InstallmentLoan {
+                    requested_amount: requested_amount.clone(),
+                    collateral_amount,
+                    installment_amount,
+                    number_of_installments,
+                    installment_period_in_seconds,
+                    start_time: env.block.time,
+                    next_due_date: get_next_due_date(
+                        env.block.time,
+                        installment_period_in_seconds,
+                        0,
+                    ),
+                    installments_paid: 0,
+                    installments_defaulted: 0,
                     processing_liquidation: false,
+                    already_claimed: Uint128::zero(),
+                    last_liquidation_date: None,
+                    keeper_fee_paid: Uint128::zero(),
+                },
+                requested_amount,
+            ),
+
+            LiquidityRequestMsg::RewardsBackedLoan {
+                requested_amount,
+                interest_amount,
+                collateral_amount,
+                duration_in_seconds,
+                exchange_rate,
+                ..
+            } => (
+                LiquidityRequestState::RewardsBackedLoan {
+                    requested_amount: requested_amount.clone(),
+                    interest_amount,
+                    collateral_amount,
+                    exchange_rate,
+                    start_time: env.block.time,
+                    end_time: env.block.time.plus_seconds(duration_in_seconds),
+                    rewards_claimed: Uint128::zero(),
+                    already_repaid: Uint128::zero(),
+                    processing_liquidation: false,
+                    already_claimed: Uint128::zero(),
+                    last_liquidation_date: None,
+                    keeper_fee_paid: Uint128::zero(),
+                },
+                requested_amount,
+            ),
+
+            LiquidityRequestMsg::LtvLoan {
+                requested_amount,
+                interest_amount,
+                collateral_amount,
+                duration_in_seconds,
+                oracle,
+                margin_call_ltv,
+                margin_call_period_in_seconds,
+                ..
+            } => (
+                LiquidityRequestState::LtvLoan {
+                    requested_amount: requested_amount.clone(),
+                    interest_amount,
+                    collateral_amount,
+                    oracle: deps.api.addr_validate(&oracle)?,
+                    margin_call_ltv,
+                    margin_call_period_in_seconds,
+                    start_time: env.block.time,
+                    end_time: env.block.time.plus_seconds(duration_in_seconds),
+                    already_repaid: Uint128::zero(),
+                    margin_call_deadline: None,
+                    processing_liquidation: false,
+                    already_claimed: Uint128::zero(),
+                    last_liquidation_date: None,
+                    keeper_fee_paid: Uint128::zero(),
                 },
                 requested_amount,
             ),
@@ -525,6 +1575,134 @@ pub fn get_requested_amount(option: LiquidityRequestMsg) -> Coin {
         LiquidityRequestMsg::FixedTermLoan {
             requested_amount, ..
         } => requested_amount,
+
+        LiquidityRequestMsg::InstallmentLoan {
+            requested_amount, ..
+        } => requested_amount,
+
+        LiquidityRequestMsg::RewardsBackedLoan {
+            requested_amount, ..
+        } => requested_amount,
+
+        LiquidityRequestMsg::LtvLoan {
+            requested_amount, ..
+        } => requested_amount,
+    }
+}
+
+pub fn set_requested_amount(option: LiquidityRequestMsg, amount: Uint128) -> LiquidityRequestMsg {
+    match option {
+        LiquidityRequestMsg::FixedInterestRental {
+            requested_amount,
+            claimable_tokens,
+            can_cast_vote,
+        } => LiquidityRequestMsg::FixedInterestRental {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            claimable_tokens,
+            can_cast_vote,
+        },
+
+        LiquidityRequestMsg::FixedTermRental {
+            requested_amount,
+            duration_in_seconds,
+            can_cast_vote,
+        } => LiquidityRequestMsg::FixedTermRental {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            duration_in_seconds,
+            can_cast_vote,
+        },
+
+        LiquidityRequestMsg::FixedTermLoan {
+            requested_amount,
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment,
+            min_interest_amount,
+            grace_period_in_seconds,
+            late_fee_per_day,
+            keeper_fee,
+        } => LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment,
+            min_interest_amount,
+            grace_period_in_seconds,
+            late_fee_per_day,
+            keeper_fee,
+        },
+
+        LiquidityRequestMsg::InstallmentLoan {
+            requested_amount,
+            collateral_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            installment_amount,
+            keeper_fee,
+        } => LiquidityRequestMsg::InstallmentLoan {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            collateral_amount,
+            number_of_installments,
+            installment_period_in_seconds,
+            installment_amount,
+            keeper_fee,
+        },
+
+        LiquidityRequestMsg::RewardsBackedLoan {
+            requested_amount,
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            exchange_rate,
+            keeper_fee,
+        } => LiquidityRequestMsg::RewardsBackedLoan {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            exchange_rate,
+            keeper_fee,
+        },
+
+        LiquidityRequestMsg::LtvLoan {
+            requested_amount,
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            oracle,
+            margin_call_ltv,
+            margin_call_period_in_seconds,
+            keeper_fee,
+        } => LiquidityRequestMsg::LtvLoan {
+            requested_amount: Coin {
+                denom: requested_amount.denom,
+                amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            oracle,
+            margin_call_ltv,
+            margin_call_period_in_seconds,
+            keeper_fee,
+        },
     }
 }
 
@@ -536,14 +1714,31 @@ pub fn ensure_option_is_exact_match(
 
     if option_on_record.ne(&option) {
         return Err(ContractError::OptionNotExactMatch {
-            required: option,
-            on_record: option_on_record,
+            required: Box::new(option),
+            on_record: Box::new(option_on_record),
         });
     }
 
     Ok(())
 }
 
+pub fn ensure_counter_offer_is_exact_match(
+    deps: &DepsMut,
+    proposer: Addr,
+    amount: Uint128,
+) -> Result<CounterOfferProposal, ContractError> {
+    let proposal_on_record = counter_offer_list().load(deps.storage, proposer)?;
+
+    if proposal_on_record.amount.ne(&amount) {
+        return Err(ContractError::CounterOfferNotExactMatch {
+            required: amount,
+            on_record: proposal_on_record.amount,
+        });
+    }
+
+    Ok(proposal_on_record)
+}
+
 pub fn get_highest_offer(store: &dyn Storage) -> Uint128 {
     counter_offer_list()
         .idx
@@ -554,13 +1749,16 @@ pub fn get_highest_offer(store: &dyn Storage) -> Uint128 {
         .unwrap_or(Uint128::zero())
 }
 
-pub fn prune_lowest_offer(storage: &mut dyn Storage) -> StdResult<Option<CounterOfferProposal>> {
+pub fn prune_lowest_offer(
+    storage: &mut dyn Storage,
+    max_counter_offers: u32,
+) -> StdResult<Option<CounterOfferProposal>> {
     // Fetch the lowest counteroffer beyond the max allowed
     let lowest_provider = counter_offer_list()
         .idx
         .amount
         .range(storage, None, None, cosmwasm_std::Order::Descending)
-        .nth(MAX_COUNTER_OFFERS)
+        .nth(max_counter_offers as usize)
         .transpose()?;
 
     // If we have an offer to prune, remove it from storage
@@ -572,6 +1770,139 @@ pub fn prune_lowest_offer(storage: &mut dyn Storage) -> StdResult<Option<Counter
     Ok(lowest_provider.map(|(_, proposal)| proposal))
 }
 
+pub fn refund_all_counter_offers(
+    storage: &mut dyn Storage,
+    denom: &str,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    // Collect every pending counter offer on the list
+    let counter_offers: Vec<CounterOfferProposal> = counter_offer_list()
+        .range(storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|res| res.map(|(_, proposal)| proposal))
+        .collect::<StdResult<_>>()?;
+
+    // Remove each offer from storage and refund the escrowed amount to its proposer
+    let mut refund_msgs = vec![];
+    for proposal in counter_offers {
+        counter_offer_list().remove(storage, proposal.proposer.clone())?;
+        release_balance(storage, denom, proposal.amount)?;
+        refund_msgs.push(get_bank_transfer_to_msg(
+            &proposal.proposer,
+            denom,
+            proposal.amount,
+        ));
+    }
+
+    Ok(refund_msgs)
+}
+
+pub fn get_lender_shares(storage: &dyn Storage) -> StdResult<Vec<LenderShare>> {
+    LENDER_SHARES
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(lender, amount)| LenderShare { lender, amount }))
+        .collect()
+}
+
+/// Returns the lead lender of a funded option, who holds its lender position and acts
+/// on behalf of every lender in LENDER_SHARES. The lead lender is the lender with
+/// the largest share, ties going to the lowest address.
+pub fn get_lead_lender(storage: &dyn Storage) -> StdResult<Addr> {
+    let mut lead_lender: Option<LenderShare> = None;
+    for share in get_lender_shares(storage)? {
+        match &lead_lender {
+            Some(lead) if lead.amount >= share.amount => {}
+            _ => lead_lender = Some(share),
+        }
+    }
+
+    lead_lender
+        .map(|lead| lead.lender)
+        .ok_or_else(|| StdError::not_found("LenderShare"))
+}
+
+/// Fails when option is funded by more than one lender, as lender then only holds
+/// a share of the position and can not act on behalf of the other lenders
+pub fn ensure_sole_lender(storage: &dyn Storage) -> Result<(), ContractError> {
+    let lenders = LENDER_SHARES
+        .keys(storage, None, None, Order::Ascending)
+        .count() as u32;
+    if lenders > 1 {
+        return Err(ContractError::LenderPositionIsSyndicated { lenders });
+    }
+
+    Ok(())
+}
+
+pub fn get_total_funded(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(get_lender_shares(storage)?
+        .iter()
+        .map(|share| share.amount)
+        .sum())
+}
+
+pub fn add_lender_share(
+    storage: &mut dyn Storage,
+    lender: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    // Record the contribution, and reserve it on behalf of the lender until the option starts
+    LENDER_SHARES.update(storage, lender, |share| -> StdResult<_> {
+        Ok(share.unwrap_or_default().checked_add(amount)?)
+    })?;
+    reserve_balance(storage, denom, amount)
+}
+
+pub fn refund_all_lender_shares(
+    storage: &mut dyn Storage,
+    denom: &str,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    // Remove each contribution from storage and refund the escrowed amount to its lender
+    let mut refund_msgs = vec![];
+    for share in get_lender_shares(storage)? {
+        LENDER_SHARES.remove(storage, &share.lender);
+        release_balance(storage, denom, share.amount)?;
+        refund_msgs.push(get_bank_transfer_to_msg(&share.lender, denom, share.amount));
+    }
+
+    Ok(refund_msgs)
+}
+
+/// Splits amount between the lenders in LENDER_SHARES pro rata to their share,
+/// with the rounding remainder going to lender.
+/// Options funded before shares were recorded pay lender in full.
+pub fn get_lender_transfer_msgs(
+    storage: &dyn Storage,
+    lender: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let shares = get_lender_shares(storage)?;
+    let total_funded: Uint128 = shares.iter().map(|share| share.amount).sum();
+    if total_funded.is_zero() {
+        return Ok(vec![get_bank_transfer_to_msg(lender, denom, amount)]);
+    }
+
+    // Floor each lender's portion, then hand the remainder to lender
+    let mut payouts: Vec<(Addr, Uint128)> = shares
+        .into_iter()
+        .map(|share| {
+            let portion = amount.multiply_ratio(share.amount, total_funded);
+            (share.lender, portion)
+        })
+        .collect();
+    let remainder = amount - payouts.iter().map(|(_, portion)| *portion).sum::<Uint128>();
+    match payouts.iter_mut().find(|(addr, _)| addr.eq(lender)) {
+        Some((_, portion)) => *portion += remainder,
+        None => payouts.push((lender.clone(), remainder)),
+    }
+
+    Ok(payouts
+        .into_iter()
+        .filter(|(_, portion)| !portion.is_zero())
+        .map(|(addr, portion)| get_bank_transfer_to_msg(&addr, denom, portion))
+        .collect())
+}
+
 pub fn query_all_counter_offers(deps: Deps) -> StdResult<Vec<CounterOfferProposal>> {
     let counter_offers: StdResult<Vec<CounterOfferProposal>> = counter_offer_list()
         .idx
@@ -2,25 +2,37 @@
 mod tests {
     use crate::{
         msg::{
-            AllDelegationsResponse, CounterOfferListResponse, ExecuteMsg, InfoResponse,
-            InstantiateMsg, QueryMsg, StakingInfoResponse,
+            AllDelegationsResponse, CounterOfferListResponse, Cw721OwnerOfResponse, Cw721QueryMsg,
+            ExecuteMsg, InfoResponse, InstantiateMsg, LenderSharesResponse, LoanHealthResponse,
+            MigrateMsg, NextLiquidationStepResponse, OraclePriceResponse, OracleQueryMsg,
+            PendingUnbondingsResponse, QueryMsg, SimulateRepaymentResponse, StakingInfoResponse,
+            SudomodConfigResponse, SudomodHookMsg, SudomodParamsResponse, SudomodQueryMsg,
         },
-        state::INSTANTIATOR_ADDR,
+        state::{counter_offer_list, CONTRACT_NAME, MAX_UNBONDING_ENTRIES, UNBONDING_PERIOD},
         types::{
-            ActiveOption, Config, CounterOfferOperator, CounterOfferProposal, LiquidityRequestMsg,
-            LiquidityRequestState,
+            ActiveOption, Config, CounterOfferOperator, CounterOfferProposal, LiquidityCommission,
+            LiquidityRequestMsg, LiquidityRequestState, Unbonding, UnbondingStrategy,
         },
     };
     use cosmwasm_std::{
-        testing::mock_env, Addr, Coin, Decimal, Delegation, Empty, Uint128, Validator,
+        testing::mock_env, to_binary, Addr, Binary, Coin, Decimal, Delegation, Deps, DepsMut,
+        Empty, Env, Event, MessageInfo, Response, StakingMsg, StdError, StdResult, Timestamp,
+        Uint128, Validator, WasmMsg,
     };
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor, StakingInfo};
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
 
     const USER: &str = "user";
+    const FACTORY: &str = "factory";
+    const FEE_COLLECTOR: &str = "fee_collector";
     const LENDER: &str = "lender";
+    const KEEPER: &str = "keeper";
     const STAKING_DENOM: &str = "TOKEN";
     const IBC_DENOM_1: &str = "ibc/usdc_denom";
     const SUPPLY: u128 = 500_000_000u128;
+    const MAX_KEEPER_FEE: u128 = 5_000;
+    const UNBONDING_TIME: u64 = 1; // in seconds
     const VALIDATOR_ONE_ADDRESS: &str = "validator_one";
     const VALIDATOR_TWO_ADDRESS: &str = "validator_two";
     const COUNTER_OFFER_PROPOSERS: [&str; 11] = [
@@ -97,7 +109,7 @@ mod tests {
                     storage,
                     StakingInfo {
                         bonded_denom: STAKING_DENOM.to_string(),
-                        unbonding_time: 1, // in seconds
+                        unbonding_time: UNBONDING_TIME,
                         apr: Decimal::percent(10),
                     },
                 )
@@ -138,13 +150,377 @@ mod tests {
     }
 
     fn contract_template() -> Box<dyn Contract<Empty>> {
+        Box::new(
+            ContractWrapper::new(
+                crate::contract::execute,
+                crate::contract::instantiate,
+                crate::contract::query,
+            )
+            .with_migrate(crate::contract::migrate),
+        )
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct LegacyInstantiateMsg {
+        contract: String,
+        version: String,
+        // Raw CONFIG and LIQUIDITY_REQUEST_STATE, in the layout used by version
+        config: Option<String>,
+        liquidity_request: Option<String>,
+        counter_offers: Vec<CounterOfferProposal>,
+    }
+
+    // Stores an arbitrary cw2 contract name and version, used to test migrations
+    fn legacy_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: LegacyInstantiateMsg,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, msg.contract, msg.version)?;
+
+        // Save the state of a vault on an older version
+        if let Some(config) = msg.config {
+            deps.storage.set(b"CONFIG", config.as_bytes());
+            let liquidity_request = msg.liquidity_request.unwrap_or_else(|| "null".to_string());
+            deps.storage
+                .set(b"LIQUIDITY_REQUEST_STATE", liquidity_request.as_bytes());
+        }
+        for proposal in msg.counter_offers {
+            counter_offer_list().save(deps.storage, proposal.proposer.clone(), &proposal)?;
+        }
+
+        Ok(Response::new())
+    }
+
+    // CONFIG of a vault prior to v0.4.0, before factory and unbonding_period were recorded
+    fn legacy_config(from_code_id: u64) -> String {
+        format!(
+            r#"{{"owner":"{}","from_code_id":{},"index_number":1}}"#,
+            USER, from_code_id
+        )
+    }
+
+    // LIQUIDITY_REQUEST_STATE of a vault prior to v0.4.0 holding a FixedTermLoan
+    // of 300_000 IBC_DENOM_1 for 30_000 interest against 1_000_000 collateral over 60 seconds,
+    // before the repayment, grace period and keeper fee fields were added
+    fn legacy_fixed_term_loan(lender: Option<&str>, state: Option<String>) -> String {
+        let lender = lender.map_or("null".to_string(), |lender| format!(r#""{}""#, lender));
+        format!(
+            r#"{{"lender":{},"msg":{{"fixed_term_loan":{{"requested_amount":{{"denom":"{}","amount":"300000"}},"interest_amount":"30000","collateral_amount":"1000000","duration_in_seconds":60}}}},"state":{}}}"#,
+            lender,
+            IBC_DENOM_1,
+            state.unwrap_or_else(|| "null".to_string())
+        )
+    }
+
+    // State of the legacy FixedTermLoan once accepted at start_time
+    fn legacy_fixed_term_loan_state(
+        start_time: Timestamp,
+        processing_liquidation: bool,
+        already_claimed: Uint128,
+        last_liquidation_date: Option<Timestamp>,
+    ) -> String {
+        let last_liquidation_date = last_liquidation_date
+            .map_or("null".to_string(), |date| format!(r#""{}""#, date.nanos()));
+        format!(
+            r#"{{"FixedTermLoan":{{"requested_amount":{{"denom":"{}","amount":"300000"}},"collateral_amount":"1000000","interest_amount":"30000","start_time":"{}","end_time":"{}","processing_liquidation":{},"already_claimed":"{}","last_liquidation_date":{}}}}}"#,
+            IBC_DENOM_1,
+            start_time.nanos(),
+            start_time.plus_seconds(60).nanos(),
+            processing_liquidation,
+            already_claimed,
+            last_liquidation_date
+        )
+    }
+
+    fn legacy_contract_template() -> Box<dyn Contract<Empty>> {
         Box::new(ContractWrapper::new(
             crate::contract::execute,
-            crate::contract::instantiate,
+            legacy_instantiate,
             crate::contract::query,
         ))
     }
 
+    // Collection minting the lender position tokens of the mock factory, if any
+    const MOCK_COLLECTION: Item<Addr> = Item::new("MOCK_COLLECTION");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct MockFactoryInstantiateMsg {
+        collection: Option<Addr>,
+    }
+
+    // Responds to the queries and hooks a vault sends to sudomod, its factory
+    fn mock_factory_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: MockFactoryInstantiateMsg,
+    ) -> StdResult<Response> {
+        if let Some(collection) = msg.collection {
+            MOCK_COLLECTION.save(deps.storage, &collection)?;
+        }
+
+        Ok(Response::new())
+    }
+
+    fn mock_factory_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: SudomodHookMsg,
+    ) -> StdResult<Response> {
+        // Mint and burn the lender position tokens on the mock collection
+        let collection_msg = match msg {
+            SudomodHookMsg::LenderPositionOpened { token_id, lender } => {
+                MockCollectionExecuteMsg::Mint {
+                    token_id,
+                    owner: lender,
+                }
+            }
+            SudomodHookMsg::LenderPositionClosed { token_id, .. } => {
+                MockCollectionExecuteMsg::Burn { token_id }
+            }
+            _ => return Ok(Response::new()),
+        };
+
+        Ok(Response::new().add_message(WasmMsg::Execute {
+            contract_addr: MOCK_COLLECTION.load(deps.storage)?.to_string(),
+            msg: to_binary(&collection_msg)?,
+            funds: vec![],
+        }))
+    }
+
+    fn mock_factory_query(deps: Deps, _env: Env, msg: SudomodQueryMsg) -> StdResult<Binary> {
+        match msg {
+            SudomodQueryMsg::Info {} => to_binary(&SudomodConfigResponse {
+                liquidity_commission: Decimal::permille(3),
+                fee_collector: Addr::unchecked(FEE_COLLECTOR),
+                lender_position_collection: MOCK_COLLECTION.may_load(deps.storage)?,
+            }),
+            SudomodQueryMsg::Params {} => to_binary(&mock_protocol_params()),
+        }
+    }
+
+    // Protocol params of the mock factory
+    fn mock_protocol_params() -> SudomodParamsResponse {
+        SudomodParamsResponse {
+            unbonding_period: UNBONDING_TIME,
+            max_counter_offers: 10,
+            min_vault_code_update_interval: 0,
+            max_limit: 30,
+            default_limit: 10,
+            max_keeper_fee: Uint128::new(MAX_KEEPER_FEE),
+        }
+    }
+
+    fn mock_factory_template() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            mock_factory_execute,
+            mock_factory_instantiate,
+            mock_factory_query,
+        ))
+    }
+
+    fn instantiate_factory(app: &mut App) -> Addr {
+        instantiate_factory_with_collection(app, None)
+    }
+
+    fn instantiate_factory_with_collection(app: &mut App, collection: Option<Addr>) -> Addr {
+        let code_id = app.store_code(mock_factory_template());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(USER),
+            &MockFactoryInstantiateMsg { collection },
+            &[],
+            "factory",
+            None,
+        )
+        .unwrap()
+    }
+
+    // Owners of the tokens minted on the mock collection
+    const TOKEN_OWNERS: Map<&str, Addr> = Map::new("TOKEN_OWNERS");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum MockCollectionExecuteMsg {
+        Mint { token_id: String, owner: String },
+        TransferNft { recipient: String, token_id: String },
+        Burn { token_id: String },
+    }
+
+    // Answers the OwnerOf queries a vault sends to the collection of its tokens
+    fn mock_collection_instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn mock_collection_execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: MockCollectionExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            MockCollectionExecuteMsg::Mint { token_id, owner } => {
+                TOKEN_OWNERS.save(deps.storage, &token_id, &Addr::unchecked(owner))?;
+            }
+            MockCollectionExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            } => {
+                if TOKEN_OWNERS.load(deps.storage, &token_id)? != info.sender {
+                    return Err(StdError::generic_err("Unauthorized"));
+                }
+                TOKEN_OWNERS.save(deps.storage, &token_id, &Addr::unchecked(recipient))?;
+            }
+            MockCollectionExecuteMsg::Burn { token_id } => {
+                TOKEN_OWNERS.remove(deps.storage, &token_id);
+            }
+        }
+
+        Ok(Response::new())
+    }
+
+    fn mock_collection_query(deps: Deps, _env: Env, msg: Cw721QueryMsg) -> StdResult<Binary> {
+        match msg {
+            Cw721QueryMsg::OwnerOf { token_id, .. } => to_binary(&Cw721OwnerOfResponse {
+                owner: TOKEN_OWNERS.load(deps.storage, &token_id)?.to_string(),
+            }),
+        }
+    }
+
+    fn mock_collection_template() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            mock_collection_execute,
+            mock_collection_instantiate,
+            mock_collection_query,
+        ))
+    }
+
+    fn instantiate_collection(app: &mut App) -> Addr {
+        let code_id = app.store_code(mock_collection_template());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(USER),
+            &Empty {},
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn get_token_owner(app: &mut App, collection: &Addr, token_id: &str) -> StdResult<Addr> {
+        let response: Cw721OwnerOfResponse = app.wrap().query_wasm_smart(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )?;
+        Ok(Addr::unchecked(response.owner))
+    }
+
+    // Prices of one base token in quote tokens, set on the mock oracle
+    const ORACLE_PRICES: Map<(&str, &str), Decimal> = Map::new("ORACLE_PRICES");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum MockOracleExecuteMsg {
+        SetPrice {
+            base: String,
+            quote: String,
+            price: Decimal,
+        },
+    }
+
+    // Answers the price queries a vault sends to the oracle of an LtvLoan
+    fn mock_oracle_instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn mock_oracle_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: MockOracleExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            MockOracleExecuteMsg::SetPrice { base, quote, price } => {
+                ORACLE_PRICES.save(deps.storage, (&base, &quote), &price)?;
+            }
+        }
+
+        Ok(Response::new())
+    }
+
+    fn mock_oracle_query(deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
+        match msg {
+            OracleQueryMsg::Price { base, quote } => to_binary(&OraclePriceResponse {
+                price: ORACLE_PRICES.load(deps.storage, (&base, &quote))?,
+            }),
+        }
+    }
+
+    fn mock_oracle_template() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            mock_oracle_execute,
+            mock_oracle_instantiate,
+            mock_oracle_query,
+        ))
+    }
+
+    fn instantiate_oracle(app: &mut App) -> Addr {
+        let code_id = app.store_code(mock_oracle_template());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(USER),
+            &Empty {},
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap()
+    }
+
+    // Sets the price of one STAKING_DENOM token in IBC_DENOM_1 on the mock oracle
+    fn set_oracle_price(app: &mut App, oracle: &Addr, price: Decimal) {
+        app.execute_contract(
+            Addr::unchecked(USER),
+            oracle.clone(),
+            &MockOracleExecuteMsg::SetPrice {
+                base: STAKING_DENOM.to_string(),
+                quote: IBC_DENOM_1.to_string(),
+                price,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The liquidity_commission snapshot for a requested_amount accepted through the mock factory
+    fn expected_liquidity_commission(requested_amount: Uint128) -> Option<LiquidityCommission> {
+        Some(LiquidityCommission {
+            rate: Decimal::permille(3),
+            amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount * Decimal::permille(3),
+            },
+            fee_collector: Addr::unchecked(FEE_COLLECTOR),
+        })
+    }
+
     fn bank_balance(router: &mut App, addr: &Addr, denom: String) -> Coin {
         router
             .wrap()
@@ -153,19 +529,37 @@ mod tests {
     }
 
     fn instantiate_vault(app: &mut App) -> (Addr, u64) {
+        instantiate_vault_with_unbonding_period(app, UNBONDING_TIME)
+    }
+
+    fn instantiate_vault_with_unbonding_period(
+        app: &mut App,
+        unbonding_period: u64,
+    ) -> (Addr, u64) {
+        let factory = instantiate_factory(app);
+        instantiate_vault_with_factory(app, factory, unbonding_period)
+    }
+
+    fn instantiate_vault_with_factory(
+        app: &mut App,
+        factory: Addr,
+        unbonding_period: u64,
+    ) -> (Addr, u64) {
         let code_id = app.store_code(contract_template());
         let template_contract_addr = app
             .instantiate_contract(
                 code_id,
-                Addr::unchecked(INSTANTIATOR_ADDR),
+                factory.clone(),
                 &InstantiateMsg {
                     owner_address: USER.to_string(),
+                    factory_address: factory.to_string(),
                     from_code_id: code_id,
                     index_number: 1u64,
+                    unbonding_period,
                 },
                 &[],
                 "vault",
-                None,
+                Some(factory.to_string()),
             )
             .unwrap();
 
@@ -196,6 +590,14 @@ mod tests {
         result
     }
 
+    fn get_lender_shares(app: &mut App, contract_address: &Addr) -> LenderSharesResponse {
+        let msg = QueryMsg::LenderShares {};
+        let result: LenderSharesResponse =
+            app.wrap().query_wasm_smart(contract_address, &msg).unwrap();
+
+        result
+    }
+
     fn get_all_counter_offers(app: &mut App, contract_address: &Addr) -> CounterOfferListResponse {
         let msg = QueryMsg::CounterOfferList {};
         let result: CounterOfferListResponse =
@@ -204,6 +606,133 @@ mod tests {
         result
     }
 
+    // Adds count validators to the staking module, after VALIDATOR_ONE_ADDRESS and
+    // VALIDATOR_TWO_ADDRESS in the order delegations are returned
+    fn add_validators(app: &mut App, count: usize) -> Vec<String> {
+        let block = app.block_info();
+        app.init_modules(|router, api, storage| {
+            (1..=count)
+                .map(|index| {
+                    let address = format!("validator_{}", index);
+                    router
+                        .staking
+                        .add_validator(
+                            api,
+                            storage,
+                            &block,
+                            Validator {
+                                address: address.clone(),
+                                commission: Decimal::zero(),
+                                max_commission: Decimal::one(),
+                                max_change_rate: Decimal::one(),
+                            },
+                        )
+                        .unwrap();
+                    address
+                })
+                .collect()
+        })
+    }
+
+    fn get_delegated_amount(app: &mut App, contract_address: &Addr, validator: &str) -> Uint128 {
+        get_all_delegations(app, contract_address)
+            .data
+            .into_iter()
+            .find(|d| d.validator == validator)
+            .map(|d| d.amount.amount)
+            .unwrap_or_default()
+    }
+
+    // Delegates delegated_amounts across new validators, then liquidates a defaulted
+    // FixedTermLoan for collateral_amount with the given unbonding strategy.
+    // Returns the amounts left delegated to each validator.
+    fn liquidate_with_unbonding_strategy(
+        strategy: UnbondingStrategy,
+        delegated_amounts: &[u128],
+        collateral_amount: Uint128,
+    ) -> Vec<Uint128> {
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let validators = add_validators(&mut router, delegated_amounts.len());
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::SetUnbondingStrategy { strategy },
+                &[],
+            )
+            .unwrap();
+        for (validator, amount) in validators.iter().zip(delegated_amounts) {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::Delegate {
+                        validator: validator.clone(),
+                        amount: Uint128::new(*amount),
+                    },
+                    &[Coin {
+                        denom: STAKING_DENOM.into(),
+                        amount: Uint128::new(*amount),
+                    }],
+                )
+                .unwrap();
+        }
+
+        let requested_amount = Uint128::new(100_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(10_000),
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap();
+
+        validators
+            .iter()
+            .map(|validator| get_delegated_amount(&mut router, &vault_c_addr, validator))
+            .collect()
+    }
+
     #[test]
     fn test_instantiate() {
         // Step 1
@@ -216,6 +745,11 @@ mod tests {
         // Query for the contract info to assert
         // that all important data was indeed saved
         // ------------------------------------------------------------------------------
+        let factory = router
+            .wrap()
+            .query_wasm_contract_info(&vault_c_addr)
+            .unwrap()
+            .creator;
         let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(
             info.config,
@@ -223,8 +757,51 @@ mod tests {
                 owner: Addr::unchecked(USER),
                 from_code_id: from_code_id,
                 index_number: 1u64,
+                factory: Addr::unchecked(&factory),
+                unbonding_strategy: UnbondingStrategy::Sequential,
+                unbonding_period: UNBONDING_TIME,
+                ownership_token: None,
             }
         );
+
+        // Step 3
+        // Test error case ContractError::InvalidFactory {}
+        // when the vault is not instantiated by the factory as its admin
+        // ------------------------------------------------------------------------------
+        let factory = Addr::unchecked(factory);
+        for (sender, factory_address, admin) in [
+            // An account naming the factory
+            (
+                Addr::unchecked(USER),
+                factory.clone(),
+                Some(factory.to_string()),
+            ),
+            // An account naming itself as the factory
+            (
+                Addr::unchecked(USER),
+                Addr::unchecked(USER),
+                Some(USER.to_string()),
+            ),
+            // The factory without being the admin of the vault
+            (factory.clone(), factory.clone(), None),
+        ] {
+            router
+                .instantiate_contract(
+                    from_code_id,
+                    sender,
+                    &InstantiateMsg {
+                        owner_address: USER.to_string(),
+                        factory_address: factory_address.to_string(),
+                        from_code_id,
+                        index_number: 2u64,
+                        unbonding_period: UNBONDING_TIME,
+                    },
+                    &[],
+                    "vault",
+                    admin,
+                )
+                .unwrap_err();
+        }
     }
 
     #[test]
@@ -385,6 +962,11 @@ mod tests {
             interest_amount,
             collateral_amount: requested_amount,
             duration_in_seconds: one_year_duration,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -468,9 +1050,9 @@ mod tests {
         // Repay FixedTermLoan to close option by sending the interest_amount to the contract
         //
         // We also include the 0.3% liquidity_comission that was deducted and sent to
-        // INSTANTIATOR_ADDR when the option was accepted
+        // FEE_COLLECTOR when the option was accepted
         // ------------------------------------------------------------------------------
-        let repay_loan_msg = ExecuteMsg::RepayLoan {};
+        let repay_loan_msg = ExecuteMsg::RepayLoan { amount: None };
         let liquidity_comission = Uint128::new(900);
         router
             .execute_contract(
@@ -1065,6 +1647,11 @@ mod tests {
                         interest_amount: Uint128::zero(),
                         collateral_amount: amount,
                         duration_in_seconds: 60u64,
+                        prorate_interest_on_early_repayment: false,
+                        min_interest_amount: None,
+                        grace_period_in_seconds: 0,
+                        late_fee_per_day: Uint128::zero(),
+                        keeper_fee: Uint128::zero(),
                     },
                 },
                 &[],
@@ -1087,7 +1674,12 @@ mod tests {
                         },
                         interest_amount: Uint128::zero(),
                         duration_in_seconds: 0u64,
+                        prorate_interest_on_early_repayment: false,
+                        min_interest_amount: None,
                         collateral_amount: Uint128::zero(),
+                        grace_period_in_seconds: 0,
+                        late_fee_per_day: Uint128::zero(),
+                        keeper_fee: Uint128::zero(),
                     },
                 },
                 &[],
@@ -1106,6 +1698,11 @@ mod tests {
             interest_amount: Uint128::zero(),
             collateral_amount: amount,
             duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -1140,7 +1737,10 @@ mod tests {
             Some(ActiveOption {
                 lender: None,
                 state: None,
-                msg: liquidity_request
+                msg: liquidity_request,
+                liquidity_commission: None,
+                lender_position: None,
+                protocol_params: None,
             })
         );
     }
@@ -1239,6 +1839,9 @@ mod tests {
                 lender: None,
                 state: None,
                 msg: liquidity_request,
+                liquidity_commission: None,
+                lender_position: None,
+                protocol_params: None,
             })
         );
     }
@@ -1337,7 +1940,10 @@ mod tests {
             Some(ActiveOption {
                 lender: None,
                 state: None,
-                msg: valid_liquidity_request_msg
+                msg: valid_liquidity_request_msg,
+                liquidity_commission: None,
+                lender_position: None,
+                protocol_params: None,
             })
         );
     }
@@ -1967,12 +2573,12 @@ mod tests {
     }
 
     #[test]
-    fn test_close_pending_liquidity_request() {
+    fn test_cancel_counter_offer() {
         // Step 1
         // Get vault instance
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let (vault_c_addr, _) = instantiate_vault(&mut router);
 
         // Step 2
         // Delegate to VALIDATOR_ONE_ADDRESS
@@ -1996,14 +2602,13 @@ mod tests {
 
         // Step 3
         // Test error case ContractError::Unauthorized {}
-        // When there is no open liquidity request on the vault
+        // When there is no pending liquidity request on the vault
         // ------------------------------------------------------------------------------
-        let close_liquidity_request_msg = ExecuteMsg::ClosePendingLiquidityRequest {};
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
                 vault_c_addr.clone(),
-                &close_liquidity_request_msg,
+                &ExecuteMsg::CancelCounterOffer {},
                 &[],
             )
             .unwrap_err();
@@ -2011,107 +2616,133 @@ mod tests {
         // Step 4
         // Create a valid liquidity request
         // ------------------------------------------------------------------------------
-        let requested_amount = Coin {
-            denom: IBC_DENOM_1.to_string(),
-            amount,
-        };
-        let option = LiquidityRequestMsg::FixedTermRental {
-            requested_amount: requested_amount.clone(),
+        let valid_liquidity_request_msg = LiquidityRequestMsg::FixedTermRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(1_000_000),
+            },
             duration_in_seconds: 60u64,
             can_cast_vote: false,
         };
-
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
                 &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
+                    option: valid_liquidity_request_msg.clone(),
                 },
                 &[],
             )
             .unwrap();
 
         // Step 5
-        // unauthorized close pending with wrong owner
+        // Open counter offers from COUNTER_OFFER_PROPOSERS[0] and COUNTER_OFFER_PROPOSERS[1]
         // ------------------------------------------------------------------------------
-        let wrong_owner = Addr::unchecked("WRONG_OWNER");
-        router
-            .execute_contract(
-                wrong_owner,
-                vault_c_addr.clone(),
-                &close_liquidity_request_msg,
-                &[],
-            )
-            .unwrap_err();
+        for (index, offer_amount) in [(0, 900_000u128), (1, 900_001u128)] {
+            router
+                .execute_contract(
+                    Addr::unchecked(COUNTER_OFFER_PROPOSERS[index]),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::OpenCounterOffer {
+                        new_amount: Uint128::new(offer_amount),
+                        for_option: valid_liquidity_request_msg.clone(),
+                    },
+                    &[Coin {
+                        denom: IBC_DENOM_1.into(),
+                        amount: Uint128::new(offer_amount),
+                    }],
+                )
+                .unwrap();
+        }
 
         // Step 6
-        // close pending liquidity request correctly
+        // Test error case when the caller does not have a counter offer on the list
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[2]),
                 vault_c_addr.clone(),
-                &close_liquidity_request_msg,
+                &ExecuteMsg::CancelCounterOffer {},
                 &[],
             )
-            .unwrap();
+            .unwrap_err();
 
         // Step 7
-        // Verify that the open liquidity request was closed
-        // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(info.liquidity_request, None);
-
-        // Step 8
-        // Open another liquidity request
+        // Test error case ContractError::Unauthorized {}
+        // When the vault owner tries to cancel a counter offer
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
-                },
+                &ExecuteMsg::CancelCounterOffer {},
                 &[],
             )
-            .unwrap();
+            .unwrap_err();
 
-        // Step 9
-        // Accept the liquidity request
+        // Step 8
+        // Successfully cancel the counter offer by COUNTER_OFFER_PROPOSERS[0]
+        // and verify that the escrowed amount was refunded
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest { option },
-                &[requested_amount],
+                &ExecuteMsg::CancelCounterOffer {},
+                &[],
             )
             .unwrap();
-
-        // Step 3
-        // Test error case ContractError::LiquidityRequestIsActive {}
-        // When owner tries to call ClosePendingLiquidityRequest when the option
-        // has already been accepted.
+        assert_eq!(
+            bank_balance(
+                &mut router,
+                &Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
+                IBC_DENOM_1.into(),
+            ),
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(SUPPLY)
+            }
+        );
+        assert_eq!(
+            bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into()),
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(900_001)
+            }
+        );
+
+        // Verify that only the counter offer by COUNTER_OFFER_PROPOSERS[1] remains
+        let all_counter_offers = get_all_counter_offers(&mut router, &vault_c_addr);
+        assert_eq!(
+            all_counter_offers,
+            CounterOfferListResponse {
+                data: vec![CounterOfferProposal {
+                    proposer: Addr::unchecked(COUNTER_OFFER_PROPOSERS[1]),
+                    amount: Uint128::new(900_001),
+                }]
+            }
+        );
+
+        // Step 9
+        // Test error case when COUNTER_OFFER_PROPOSERS[0] tries to cancel again
         // ------------------------------------------------------------------------------
-        let close_liquidity_request_msg = ExecuteMsg::ClosePendingLiquidityRequest {};
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
                 vault_c_addr.clone(),
-                &close_liquidity_request_msg,
+                &ExecuteMsg::CancelCounterOffer {},
                 &[],
             )
             .unwrap_err();
     }
 
     #[test]
-    fn test_mev_guard_for_accept_liquidity_request() {
+    fn test_accept_counter_offer() {
         // Step 1
         // Get vault instance
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let (vault_c_addr, _) = instantiate_vault(&mut router);
 
         // Step 2
         // Delegate to VALIDATOR_ONE_ADDRESS
@@ -2134,17 +2765,23 @@ mod tests {
             .unwrap();
 
         // Step 3
-        // Create a valid liquidity request
+        // Create a valid FixedTermLoan liquidity request
         // ------------------------------------------------------------------------------
         let duration_in_seconds = 60u64;
+        let interest_amount = Uint128::new(100);
         let option = LiquidityRequestMsg::FixedTermLoan {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
                 amount,
             },
-            interest_amount: Uint128::zero(),
+            interest_amount,
             collateral_amount: amount,
             duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -2157,200 +2794,199 @@ mod tests {
             )
             .unwrap();
 
-        // Step 3
-        // Test error case ContractError::OptionNotExactMatch {}
-        // When trying to accept the option by passing non matching terms to the contract
+        // Step 4
+        // Open counter offers from the first three COUNTER_OFFER_PROPOSERS
         // ------------------------------------------------------------------------------
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: LiquidityRequestMsg::FixedTermRental {
-                        requested_amount: Coin {
-                            denom: IBC_DENOM_1.to_string(),
-                            amount,
-                        },
-                        duration_in_seconds: 60u64,
-                        can_cast_vote: false,
+        for (index, offer_amount) in [(0, 900_000u128), (1, 900_001u128), (2, 900_002u128)] {
+            router
+                .execute_contract(
+                    Addr::unchecked(COUNTER_OFFER_PROPOSERS[index]),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::OpenCounterOffer {
+                        new_amount: Uint128::new(offer_amount),
+                        for_option: option.clone(),
                     },
-                },
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount,
-                }],
-            )
-            .unwrap_err();
-    }
-
-    #[test]
-    fn test_accept_liquidity_request_fixed_term_loan() {
-        // Step 1
-        // Get vault instance
-        // ------------------------------------------------------------------------------
-        let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
-
-        // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
-        // ------------------------------------------------------------------------------
-        let amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount,
-        };
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &delegate_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
-            )
-            .unwrap();
+                    &[Coin {
+                        denom: IBC_DENOM_1.into(),
+                        amount: Uint128::new(offer_amount),
+                    }],
+                )
+                .unwrap();
+        }
 
-        // Step 3
+        // Step 5
         // Test error case ContractError::Unauthorized {}
-        // When there is no open liquidity request on the vault
+        // When a non-owner tries to accept a counter offer
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[1]),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: LiquidityRequestMsg::FixedTermRental {
-                        requested_amount: Coin {
-                            denom: IBC_DENOM_1.to_string(),
-                            amount,
-                        },
-                        duration_in_seconds: 60u64,
-                        can_cast_vote: false,
-                    },
+                &ExecuteMsg::AcceptCounterOffer {
+                    amount: Uint128::new(900_001),
+                    proposed_by_address: COUNTER_OFFER_PROPOSERS[1].to_string(),
                 },
                 &[],
             )
             .unwrap_err();
 
-        // Step 4
-        // Create a valid FixedTermLoan liquidity request
+        // Step 6
+        // Test error case ContractError::CounterOfferNotExactMatch {}
+        // When the amount does not match the counter offer on record
         // ------------------------------------------------------------------------------
-        let duration_in_seconds = 60u64;
-        let option = LiquidityRequestMsg::FixedTermLoan {
-            requested_amount: Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount,
-            },
-            interest_amount: Uint128::zero(),
-            collateral_amount: amount,
-            duration_in_seconds,
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
+                &ExecuteMsg::AcceptCounterOffer {
+                    amount: Uint128::new(900_000),
+                    proposed_by_address: COUNTER_OFFER_PROPOSERS[1].to_string(),
                 },
                 &[],
             )
-            .unwrap();
+            .unwrap_err();
 
-        // Step 5
-        // Test error case ContractError::InvalidInputAmount {}
-        // by sending the wrong requested amount
+        // Step 7
+        // Test error case when the proposer has no counter offer on the list
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: option.clone(),
+                &ExecuteMsg::AcceptCounterOffer {
+                    amount: Uint128::new(900_001),
+                    proposed_by_address: COUNTER_OFFER_PROPOSERS[3].to_string(),
                 },
                 &[],
             )
             .unwrap_err();
 
-        // Step 6
-        // Accept the open liquidity request
+        // Step 8
+        // Accept the counter offer by COUNTER_OFFER_PROPOSERS[1]
         // ------------------------------------------------------------------------------
+        let accepted_amount = Uint128::new(900_001);
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: option.clone(),
+                &ExecuteMsg::AcceptCounterOffer {
+                    amount: accepted_amount,
+                    proposed_by_address: COUNTER_OFFER_PROPOSERS[1].to_string(),
                 },
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount,
-                }],
+                &[],
             )
             .unwrap();
 
-        // Step 7
-        // Verify that the correct info for the liquidity request was updated
+        // Step 9
+        // Verify that the option was activated with the accepted offer as requested_amount
+        // and COUNTER_OFFER_PROPOSERS[1] as the lender
         // ------------------------------------------------------------------------------
         let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(
             info.liquidity_request,
             Some(ActiveOption {
-                lender: Some(Addr::unchecked(LENDER)),
-                state: Some(crate::types::LiquidityRequestState::FixedTermLoan {
+                lender: Some(Addr::unchecked(COUNTER_OFFER_PROPOSERS[1])),
+                state: Some(LiquidityRequestState::FixedTermLoan {
                     requested_amount: Coin {
                         denom: IBC_DENOM_1.to_string(),
-                        amount,
+                        amount: accepted_amount,
                     },
-                    interest_amount: Uint128::zero(),
+                    interest_amount,
                     collateral_amount: amount,
                     start_time: router.block_info().time,
                     end_time: router.block_info().time.plus_seconds(duration_in_seconds),
                     last_liquidation_date: None,
                     already_claimed: Uint128::zero(),
-                    processing_liquidation: false
+                    already_repaid: Uint128::zero(),
+                    processing_liquidation: false,
+                    pending_extension: None,
+                    keeper_fee_paid: Uint128::zero(),
                 }),
-                msg: option.clone()
+                msg: LiquidityRequestMsg::FixedTermLoan {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: accepted_amount,
+                    },
+                    interest_amount,
+                    collateral_amount: amount,
+                    duration_in_seconds,
+                    prorate_interest_on_early_repayment: false,
+                    min_interest_amount: None,
+                    grace_period_in_seconds: 0,
+                    late_fee_per_day: Uint128::zero(),
+                    keeper_fee: Uint128::zero(),
+                },
+                liquidity_commission: expected_liquidity_commission(accepted_amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
             })
         );
 
-        // Step 8
-        // Test error case ContractError::Unauthorized {}
-        // Trying to accept an option that already has an active lender
-        // ------------------------------------------------------------------------------
-        router
-            .execute_contract(
-                Addr::unchecked(LENDER),
-                vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest { option },
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
-            )
-            .unwrap_err();
+        // Step 10
+        // Verify that every other counter offer was refunded and the list is empty
+        // ------------------------------------------------------------------------------
+        for index in [0, 2] {
+            assert_eq!(
+                bank_balance(
+                    &mut router,
+                    &Addr::unchecked(COUNTER_OFFER_PROPOSERS[index]),
+                    IBC_DENOM_1.into(),
+                ),
+                Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(SUPPLY)
+                }
+            );
+        }
+        let all_counter_offers = get_all_counter_offers(&mut router, &vault_c_addr);
+        assert_eq!(
+            all_counter_offers,
+            CounterOfferListResponse { data: vec![] }
+        );
 
-        // Step 9
-        // Verify that liquidity_request_commission was paid to INSTANTIATOR_ADDR
-        // The liquidity_request_commission is calculated as 0.3% of requested amount
+        // Step 11
+        // Verify that liquidity_request_commission was paid to FEE_COLLECTOR
+        // and the remainder of the accepted offer is held by the vault
         // ------------------------------------------------------------------------------
-        let liquidity_request_commission = Uint128::new(3_000);
-        let instantiator_balance = bank_balance(
-            &mut router,
-            &Addr::unchecked(INSTANTIATOR_ADDR),
-            IBC_DENOM_1.into(),
-        );
+        let liquidity_request_commission = Uint128::new(2_700);
         assert_eq!(
-            instantiator_balance,
+            bank_balance(
+                &mut router,
+                &Addr::unchecked(FEE_COLLECTOR),
+                IBC_DENOM_1.into(),
+            ),
             Coin {
                 denom: IBC_DENOM_1.to_string(),
                 amount: liquidity_request_commission
             }
         );
+        assert_eq!(
+            bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into()),
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: accepted_amount - liquidity_request_commission
+            }
+        );
+
+        // Step 12
+        // Test error case ContractError::Unauthorized {}
+        // Trying to accept a counter offer when the option is already active
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptCounterOffer {
+                    amount: accepted_amount,
+                    proposed_by_address: COUNTER_OFFER_PROPOSERS[1].to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
     }
 
     #[test]
-    fn test_accept_liquidity_request_fixed_interest_rental() {
+    fn test_close_pending_liquidity_request() {
         // Step 1
         // Get vault instance
         // ------------------------------------------------------------------------------
@@ -2378,117 +3014,4886 @@ mod tests {
             .unwrap();
 
         // Step 3
-        // Create a valid FixedInterestRental liquidity request
+        // Test error case ContractError::Unauthorized {}
+        // When there is no open liquidity request on the vault
         // ------------------------------------------------------------------------------
-        let option = LiquidityRequestMsg::FixedInterestRental {
-            requested_amount: Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount,
-            },
-            claimable_tokens: amount,
-            can_cast_vote: false,
-        };
+        let close_liquidity_request_msg = ExecuteMsg::ClosePendingLiquidityRequest {};
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
-                },
+                &close_liquidity_request_msg,
                 &[],
             )
-            .unwrap();
+            .unwrap_err();
 
         // Step 4
-        // Test error case ContractError::InvalidInputAmount {}
-        // by sending the wrong requested amount
+        // Create a valid liquidity request
         // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest {
-            option: option.clone(),
+        let requested_amount = Coin {
+            denom: IBC_DENOM_1.to_string(),
+            amount,
+        };
+        let option = LiquidityRequestMsg::FixedTermRental {
+            requested_amount: requested_amount.clone(),
+            duration_in_seconds: 60u64,
+            can_cast_vote: false,
         };
+
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // unauthorized close pending with wrong owner
+        // ------------------------------------------------------------------------------
+        let wrong_owner = Addr::unchecked("WRONG_OWNER");
+        router
+            .execute_contract(
+                wrong_owner,
+                vault_c_addr.clone(),
+                &close_liquidity_request_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // close pending liquidity request correctly
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &close_liquidity_request_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the open liquidity request was closed
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+
+        // Step 8
+        // Open another liquidity request
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 9
+        // Accept the liquidity request
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[requested_amount],
+            )
+            .unwrap();
+
+        // Step 3
+        // Test error case ContractError::LiquidityRequestIsActive {}
+        // When owner tries to call ClosePendingLiquidityRequest when the option
+        // has already been accepted.
+        // ------------------------------------------------------------------------------
+        let close_liquidity_request_msg = ExecuteMsg::ClosePendingLiquidityRequest {};
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &close_liquidity_request_msg,
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_close_pending_liquidity_request_refunds_counter_offers() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Create a valid liquidity request
+        // ------------------------------------------------------------------------------
+        let option = LiquidityRequestMsg::FixedTermRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(1_000_000),
+            },
+            duration_in_seconds: 60u64,
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 3
+        // Open counter offers from the first three COUNTER_OFFER_PROPOSERS
+        // ------------------------------------------------------------------------------
+        for (index, offer_amount) in [(0, 900_000u128), (1, 900_001u128), (2, 900_002u128)] {
+            router
+                .execute_contract(
+                    Addr::unchecked(COUNTER_OFFER_PROPOSERS[index]),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::OpenCounterOffer {
+                        new_amount: Uint128::new(offer_amount),
+                        for_option: option.clone(),
+                    },
+                    &[Coin {
+                        denom: IBC_DENOM_1.into(),
+                        amount: Uint128::new(offer_amount),
+                    }],
+                )
+                .unwrap();
+        }
+
+        // Step 4
+        // Close the pending liquidity request
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ClosePendingLiquidityRequest {},
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Verify that every counter offer was refunded and the list is empty
+        // ------------------------------------------------------------------------------
+        for proposer in &COUNTER_OFFER_PROPOSERS[0..3] {
+            assert_eq!(
+                bank_balance(&mut router, &Addr::unchecked(*proposer), IBC_DENOM_1.into()),
+                Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(SUPPLY)
+                }
+            );
+        }
+        let all_counter_offers = get_all_counter_offers(&mut router, &vault_c_addr);
+        assert_eq!(
+            all_counter_offers,
+            CounterOfferListResponse { data: vec![] }
+        );
+
+        // Step 6
+        // Verify that the vault owner cannot sweep any of the escrowed funds
+        // ------------------------------------------------------------------------------
+        assert_eq!(
+            bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into()),
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::zero()
+            }
+        );
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: Uint128::new(900_000),
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_accept_liquidity_request_refunds_counter_offers() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Create a valid liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Coin {
+            denom: IBC_DENOM_1.to_string(),
+            amount: Uint128::new(1_000_000),
+        };
+        let option = LiquidityRequestMsg::FixedInterestRental {
+            requested_amount: requested_amount.clone(),
+            claimable_tokens: Uint128::new(1_000),
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 3
+        // Open counter offers from the first two COUNTER_OFFER_PROPOSERS
+        // ------------------------------------------------------------------------------
+        for (index, offer_amount) in [(0, 900_000u128), (1, 900_001u128)] {
+            router
+                .execute_contract(
+                    Addr::unchecked(COUNTER_OFFER_PROPOSERS[index]),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::OpenCounterOffer {
+                        new_amount: Uint128::new(offer_amount),
+                        for_option: option.clone(),
+                    },
+                    &[Coin {
+                        denom: IBC_DENOM_1.into(),
+                        amount: Uint128::new(offer_amount),
+                    }],
+                )
+                .unwrap();
+        }
+
+        // Step 4
+        // LENDER accepts the liquidity request at the full requested amount
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount.amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 5
+        // Verify that every counter offer was refunded and the list is empty
+        // ------------------------------------------------------------------------------
+        for proposer in &COUNTER_OFFER_PROPOSERS[0..2] {
+            assert_eq!(
+                bank_balance(&mut router, &Addr::unchecked(*proposer), IBC_DENOM_1.into()),
+                Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(SUPPLY)
+                }
+            );
+        }
+        let all_counter_offers = get_all_counter_offers(&mut router, &vault_c_addr);
+        assert_eq!(
+            all_counter_offers,
+            CounterOfferListResponse { data: vec![] }
+        );
+
+        // Step 6
+        // Verify that only the requested amount less liquidity_request_commission
+        // is left in the vault for the owner to withdraw
+        // ------------------------------------------------------------------------------
+        let liquidity_request_commission = Uint128::new(3_000);
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount.amount - liquidity_request_commission
+            }
+        );
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: vault_balance.amount + Uint128::new(1),
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_mev_guard_for_accept_liquidity_request() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Create a valid liquidity request
+        // ------------------------------------------------------------------------------
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 3
+        // Test error case ContractError::OptionNotExactMatch {}
+        // When trying to accept the option by passing non matching terms to the contract
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: LiquidityRequestMsg::FixedTermRental {
+                        requested_amount: Coin {
+                            denom: IBC_DENOM_1.to_string(),
+                            amount,
+                        },
+                        duration_in_seconds: 60u64,
+                        can_cast_vote: false,
+                    },
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount,
+                }],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_accept_liquidity_request_fixed_term_loan() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Test error case ContractError::Unauthorized {}
+        // When there is no open liquidity request on the vault
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: LiquidityRequestMsg::FixedTermRental {
+                        requested_amount: Coin {
+                            denom: IBC_DENOM_1.to_string(),
+                            amount,
+                        },
+                        duration_in_seconds: 60u64,
+                        can_cast_vote: false,
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Create a valid FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount,
+            },
+            interest_amount: Uint128::zero(),
+            collateral_amount: amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Test error case ContractError::InvalidInputAmount {}
+        // by sending the wrong requested amount
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Accept the open liquidity request
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option.clone(),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the correct info for the liquidity request was updated
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request,
+            Some(ActiveOption {
+                lender: Some(Addr::unchecked(LENDER)),
+                state: Some(crate::types::LiquidityRequestState::FixedTermLoan {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount,
+                    },
+                    interest_amount: Uint128::zero(),
+                    collateral_amount: amount,
+                    start_time: router.block_info().time,
+                    end_time: router.block_info().time.plus_seconds(duration_in_seconds),
+                    last_liquidation_date: None,
+                    already_claimed: Uint128::zero(),
+                    already_repaid: Uint128::zero(),
+                    processing_liquidation: false,
+                    pending_extension: None,
+                    keeper_fee_paid: Uint128::zero(),
+                }),
+                msg: option.clone(),
+                liquidity_commission: expected_liquidity_commission(amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
+            })
+        );
+
+        // Step 8
+        // Test error case ContractError::Unauthorized {}
+        // Trying to accept an option that already has an active lender
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap_err();
+
+        // Step 9
+        // Verify that liquidity_request_commission was paid to FEE_COLLECTOR
+        // The liquidity_request_commission is calculated as 0.3% of requested amount
+        // ------------------------------------------------------------------------------
+        let liquidity_request_commission = Uint128::new(3_000);
+        let instantiator_balance = bank_balance(
+            &mut router,
+            &Addr::unchecked(FEE_COLLECTOR),
+            IBC_DENOM_1.into(),
+        );
+        assert_eq!(
+            instantiator_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: liquidity_request_commission
+            }
+        );
+    }
+
+    #[test]
+    fn test_accept_liquidity_request_fixed_interest_rental() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Create a valid FixedInterestRental liquidity request
+        // ------------------------------------------------------------------------------
+        let option = LiquidityRequestMsg::FixedInterestRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount,
+            },
+            claimable_tokens: amount,
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Test error case ContractError::InvalidInputAmount {}
+        // by sending the wrong requested amount
+        // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest {
+            option: option.clone(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Accept the option
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 6
+        // Verify that the correct info for the liquidity request was updated with the option
+        // state
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request,
+            Some(ActiveOption {
+                lender: Some(Addr::unchecked(LENDER)),
+                state: Some(LiquidityRequestState::FixedInterestRental {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount,
+                    },
+                    claimable_tokens: amount,
+                    already_claimed: Uint128::zero(),
+                    can_cast_vote: false,
+                }),
+                msg: option,
+                liquidity_commission: expected_liquidity_commission(amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
+            })
+        );
+
+        // Step 7
+        // Test error case ContractError::Unauthorized {}
+        // Trying to accept an option that already has an active lender
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Verify that liquidity_request_commission was paid to FEE_COLLECTOR
+        // The liquidity_request_commission is calculated as 0.3% of requested amount
+        // ------------------------------------------------------------------------------
+        let liquidity_request_commission = Uint128::new(3_000);
+        let instantiator_balance = bank_balance(
+            &mut router,
+            &Addr::unchecked(FEE_COLLECTOR),
+            IBC_DENOM_1.into(),
+        );
+        assert_eq!(
+            instantiator_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: liquidity_request_commission
+            }
+        );
+    }
+
+    #[test]
+    fn test_accept_liquidity_request_fixed_term_rental() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Create a valid FixedTermRental liquidity request
+        // ------------------------------------------------------------------------------
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount,
+            },
+            duration_in_seconds,
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Test error case ContractError::InvalidInputAmount {}
+        // by sending the wrong requested amount
+        // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest {
+            option: option.clone(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Foward the blockchain ahead by one year
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+
+        // Step 6
+        // Accept the option
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the vault contains the balance of the accumulated_rewards claimed
+        // ------------------------------------------------------------------------------
+        let balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        assert_eq!(
+            balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: Uint128::new(100_000)
+            }
+        );
+
+        // Step 8
+        // Verify that the correct info for the liquidity request was updated with the option
+        // state
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request,
+            Some(ActiveOption {
+                lender: Some(Addr::unchecked(LENDER)),
+                state: Some(LiquidityRequestState::FixedTermRental {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount,
+                    },
+                    start_time: router.block_info().time,
+                    last_claim_time: router.block_info().time,
+                    end_time: router.block_info().time.plus_seconds(duration_in_seconds),
+                    can_cast_vote: false,
+                }),
+                msg: option,
+                liquidity_commission: expected_liquidity_commission(amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
+            })
+        );
+
+        // Step 9
+        // Test error case ContractError::Unauthorized {}
+        // Trying to accept an option that already has an active lender
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap_err();
+
+        // Step 10
+        // Verify that liquidity_request_commission was paid to FEE_COLLECTOR
+        // The liquidity_request_commission is calculated as 0.3% of requested amount
+        // ------------------------------------------------------------------------------
+        let liquidity_request_commission = Uint128::new(3_000);
+        let instantiator_balance = bank_balance(
+            &mut router,
+            &Addr::unchecked(FEE_COLLECTOR),
+            IBC_DENOM_1.into(),
+        );
+        assert_eq!(
+            instantiator_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: liquidity_request_commission
+            }
+        );
+    }
+
+    #[test]
+    fn test_claim_delegator_rewards_no_liquidity_request() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Delegate to VALIDATOR_TWO_ADDRESS
+        // ------------------------------------------------------------------------------
+        let amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_TWO_ADDRESS.to_string(),
+            amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 4
+        // Foward the blockchain ahead by one year
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+
+        // Step 5
+        // query_staking_info and verify data
+        // ------------------------------------------------------------------------------
+        let staking_info = get_vault_staking_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            staking_info,
+            StakingInfoResponse {
+                total_staked: Uint128::new(2000000),
+                accumulated_rewards: Uint128::new(200000)
+            }
+        );
+
+        // Step 6
+        // Claim rewards from all validators
+        // ------------------------------------------------------------------------------
+        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &claim_rewards_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify by inspecting contract balance
+        // ------------------------------------------------------------------------------
+        let balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        assert_eq!(
+            balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: staking_info.accumulated_rewards
+            }
+        );
+    }
+
+    #[test]
+    fn test_claim_delegator_rewards_with_fixed_interest_rental() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let delegated_amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount: delegated_amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Create a valid FixedInterestRental liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(350_000);
+        let claimable_tokens = Uint128::new(350_000);
+        let option = LiquidityRequestMsg::FixedInterestRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            claimable_tokens,
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Accept the option
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option.clone(),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 5
+        // Fast foward the chain to a future date, where
+        // the rewards has not fully covered the claimable_tokens
+        // ------------------------------------------------------------------------------
+        let expected_claims_after_two_years = Uint128::new(200_000);
+        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365 * 2));
+
+        // Step 6
+        // Claim rewards from the validator
+        // ------------------------------------------------------------------------------
+        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &claim_rewards_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify to ensure vault balance is zero and lender's balance contains
+        // expected_claims_after_two_years
+        // ------------------------------------------------------------------------------
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: Uint128::new(0)
+            }
+        );
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_claims_after_two_years
+            }
+        );
+
+        // Step 8
+        // Verify that the correct info for the liquidity request
+        // was updated in the option state
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request,
+            Some(ActiveOption {
+                lender: Some(Addr::unchecked(LENDER)),
+                state: Some(LiquidityRequestState::FixedInterestRental {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: requested_amount,
+                    },
+                    claimable_tokens,
+                    already_claimed: expected_claims_after_two_years,
+                    can_cast_vote: false,
+                }),
+                msg: option,
+                liquidity_commission: expected_liquidity_commission(requested_amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
+            })
+        );
+
+        // Step 9
+        // Fast foward the chain to a future date, where
+        // the rewards has fully covered the claimable_tokens
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365 * 2));
+
+        // Step 10
+        // try claim rewards from the validator
+        // ------------------------------------------------------------------------------
+        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &claim_rewards_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 11
+        // Verify to ensure vault balance for STAKING_DENOM equal
+        // (2 * expected_claims_after_two_years) - claimable_tokens,
+        // and the lender's balance for STAKING_DENOM equal claimable_tokens
+        // ------------------------------------------------------------------------------
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: (expected_claims_after_two_years + expected_claims_after_two_years)
+                    - claimable_tokens
+            }
+        );
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: claimable_tokens
+            }
+        );
+
+        // Step 12
+        // Verify that the option has been finalized
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_claim_delegator_rewards_with_fixed_term_rental() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let delegated_amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount: delegated_amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Create a valid FixedTermRental liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(350_000);
+        let duration_in_seconds = 60 * 60 * 24 * 365 * 3;
+        let expected_rewards_after_duration = Uint128::new(300_000);
+        let option = LiquidityRequestMsg::FixedTermRental {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(350_000),
+            },
+            duration_in_seconds,
+            can_cast_vote: false,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Accept the option
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option.clone(),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Record start time
+        let start_time = router.block_info().time;
+
+        // Step 5
+        // fast foward the chain to a future date, before the end_timme
+        // ------------------------------------------------------------------------------
+        let expected_claims_after_two_years = Uint128::new(200_000);
+        let two_years = 60 * 60 * 24 * 365 * 2;
+        router.update_block(|block| block.time = block.time.plus_seconds(two_years));
+
+        // Step 6
+        // try claim rewards from the validator
+        // ------------------------------------------------------------------------------
+        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &claim_rewards_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Record last claim time
+        let last_claim_time = router.block_info().time;
+
+        // Step 7
+        // verify to ensure vault balance is zero and lender's balance contains
+        // expected_claims_after_two_years
+        // ------------------------------------------------------------------------------
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: Uint128::new(0)
+            }
+        );
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_claims_after_two_years
+            }
+        );
+
+        // Step 8
+        // Verify that the correct info for the liquidity request
+        // was updated in the option state
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request,
+            Some(ActiveOption {
+                lender: Some(Addr::unchecked(LENDER)),
+                state: Some(LiquidityRequestState::FixedTermRental {
+                    requested_amount: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: requested_amount,
+                    },
+                    start_time,
+                    last_claim_time,
+                    end_time: start_time.plus_seconds(duration_in_seconds),
+                    can_cast_vote: false,
+                }),
+                msg: option,
+                liquidity_commission: expected_liquidity_commission(requested_amount),
+                lender_position: None,
+                protocol_params: Some(mock_protocol_params()),
+            })
+        );
+
+        // Step 9
+        // fast foward the chain to a future date greater than the end_time
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(two_years));
+
+        // Step 10
+        // try claim rewards from the validator
+        // ------------------------------------------------------------------------------
+        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &claim_rewards_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 11
+        // verify to ensure vault balance is
+        // (2 * expected_claims_after_two_years) - expected_rewards_after_duration,
+        // and the lender's balance now has the expected_rewards_after_duration
+        // ------------------------------------------------------------------------------
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: (expected_claims_after_two_years + expected_claims_after_two_years)
+                    - expected_rewards_after_duration
+            }
+        );
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_rewards_after_duration
+            }
+        );
+
+        // Step 12
+        // Verify that the option has been finalized
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_repay_loan() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let delegated_amount = Uint128::new(1_000_000);
+        let delegate_msg = ExecuteMsg::Delegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount: delegated_amount,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &delegate_msg,
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Try to call repay loan with ContractError::Unauthorized {}
+        // because there is no active liquidity request
+        // ------------------------------------------------------------------------------
+        let repay_loan_msg = ExecuteMsg::RepayLoan { amount: None };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &repay_loan_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Create a valid FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Try to call repay loan with ContractError::Unauthorized {}
+        // because there is a liquidity request that has not been accepted yet
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &repay_loan_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Accept the open liquidity request
+        // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Try to call repay loan with ContractError::InsufficientBalance {}
+        // because at this point, the vault contract still has the requested_amount
+        // sent to it by the lender when they accept the liquidity request
+        // but does not have the extra interest_amount required
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &repay_loan_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Try to repay the loan correctly by sending the interest_amount to the contract
+        //
+        // We also include the 0.3% liquidity_comission that was deducted from the requested_amount
+        // and sent to FEE_COLLECTOR when the option was accepted
+        // ------------------------------------------------------------------------------
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &repay_loan_msg,
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + liquidity_comission,
+                }],
+            )
+            .unwrap();
+
+        // Step 9
+        // Verify that the option on the vault is closed,
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+
+        // Step 10
+        // Verify that the vault balance for IBC_DENOM_1 is zero as the balance
+        // has been used to clear the debt
+        //
+        // Also verify that the LENDER balance is back to SUPPLY + interest_amount
+        // ------------------------------------------------------------------------------
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into());
+        assert_eq!(
+            vault_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(0)
+            }
+        );
+
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(SUPPLY) + interest_amount
+            }
+        );
+    }
+
+    #[test]
+    fn test_repay_loan_early_with_prorated_interest() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Test error case ContractError::InvalidLiquidityRequestOption {}
+        // when min_interest_amount is greater than interest_amount
+        // ------------------------------------------------------------------------------
+        let one_day = 60 * 60 * 24;
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(90_000);
+        let min_interest_amount = Uint128::new(10_000);
+        let option = |min_interest_amount| LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds: one_day * 90,
+            prorate_interest_on_early_repayment: true,
+            min_interest_amount: Some(min_interest_amount),
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(interest_amount + Uint128::one()),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 3
+        // Create and accept a FixedTermLoan that prorates interest on early repayment
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(min_interest_amount),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option(min_interest_amount),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 4
+        // Verify that min_interest_amount is due when repaying on day 5
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_day * 5));
+        let simulation: SimulateRepaymentResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::SimulateRepayment {})
+            .unwrap();
+        assert_eq!(simulation.interest_due.amount, min_interest_amount);
+        assert_eq!(
+            simulation.amount_due.amount,
+            requested_amount + min_interest_amount
+        );
+
+        // Step 5
+        // Verify that a third of interest_amount is due when repaying on day 30
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_day * 25));
+        let interest_due = Uint128::new(30_000);
+        let simulation: SimulateRepaymentResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::SimulateRepayment {})
+            .unwrap();
+        assert_eq!(
+            simulation,
+            SimulateRepaymentResponse {
+                interest_due: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_due,
+                },
+                late_fee_due: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::zero(),
+                },
+                already_repaid: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::zero(),
+                },
+                amount_due: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount + interest_due,
+                },
+            }
+        );
+
+        // Step 6
+        // Repay the loan, sending the interest_due and the 0.3% liquidity_comission
+        // ------------------------------------------------------------------------------
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_due + liquidity_comission,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the option is closed and the lender received the interest_due
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(lender_balance.amount, Uint128::new(SUPPLY) + interest_due);
+        router
+            .wrap()
+            .query_wasm_smart::<SimulateRepaymentResponse>(
+                &vault_c_addr,
+                &QueryMsg::SimulateRepayment {},
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_partial_repay_loan() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let collateral_amount = Uint128::new(660_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Test error case ContractError::InvalidRepaymentAmount {}
+        // when repaying zero, or more than requested_amount + interest_amount
+        // ------------------------------------------------------------------------------
+        for amount in [
+            Uint128::zero(),
+            requested_amount + interest_amount + Uint128::one(),
+        ] {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::RepayLoan {
+                        amount: Some(amount),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+        }
+
+        // Step 4
+        // Repay a first chunk of the loan from the vault balance
+        // ------------------------------------------------------------------------------
+        let first_repayment = Uint128::new(110_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(first_repayment),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Verify that the payment was forwarded to the lender
+        // and recorded as already_repaid on the active option
+        // ------------------------------------------------------------------------------
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) - requested_amount + first_repayment
+        );
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        match info.liquidity_request.unwrap().state {
+            Some(LiquidityRequestState::FixedTermLoan { already_repaid, .. }) => {
+                assert_eq!(already_repaid, first_repayment)
+            }
+            _ => panic!("expected an active FixedTermLoan"),
+        }
+
+        // Step 6
+        // Repay the outstanding amount, sending the interest_amount
+        // and the 0.3% liquidity_comission to the vault
+        // ------------------------------------------------------------------------------
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + liquidity_comission,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the option is closed and the lender was repaid in full
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) + interest_amount
+        );
+    }
+
+    #[test]
+    fn test_repay_loan_during_grace_period() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan with a 3 day grace period
+        // ------------------------------------------------------------------------------
+        let one_day = 60 * 60 * 24;
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let late_fee_per_day = Uint128::new(1_000);
+        let duration_in_seconds = 60u64;
+        let grace_period_in_seconds = one_day * 3;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds,
+            late_fee_per_day,
+            keeper_fee: Uint128::zero(),
+        };
+        let accept_option = |router: &mut App| {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::RequestLiquidity {
+                        option: option.clone(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            router
+                .execute_contract(
+                    Addr::unchecked(LENDER),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::AcceptLiquidityRequest {
+                        option: option.clone(),
+                    },
+                    &[Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: requested_amount,
+                    }],
+                )
+                .unwrap();
+        };
+        accept_option(&mut router);
+
+        // Step 3
+        // Fast forward the time into the second day of the grace period
+        // and verify that the late fee for two days is due
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| {
+            block.time = block.time.plus_seconds(duration_in_seconds + one_day + 1)
+        });
+        let late_fee_due = Uint128::new(2_000);
+        let simulation: SimulateRepaymentResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::SimulateRepayment {})
+            .unwrap();
+        assert_eq!(simulation.late_fee_due.amount, late_fee_due);
+        assert_eq!(
+            simulation.amount_due.amount,
+            requested_amount + interest_amount + late_fee_due
+        );
+
+        // Step 4
+        // Test error case ContractError::LoanNotYetLiquidatable {}
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Repay the loan with the late fee and verify that the option is closed
+        // ------------------------------------------------------------------------------
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + late_fee_due + liquidity_comission,
+                }],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) + interest_amount + late_fee_due
+        );
+
+        // Step 6
+        // Accept the option again and verify that liquidation is only allowed
+        // once the grace period has expired
+        // ------------------------------------------------------------------------------
+        accept_option(&mut router);
+        router.update_block(|block| {
+            block.time = block
+                .time
+                .plus_seconds(duration_in_seconds + grace_period_in_seconds - 1)
+        });
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap_err();
+        router.update_block(|block| block.time = block.time.plus_seconds(1));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_loan_extension() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan with a 1 day grace period
+        // ------------------------------------------------------------------------------
+        let one_day = 60 * 60 * 24;
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: one_day,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let end_time = router.block_info().time.plus_seconds(duration_in_seconds);
+        let new_end_time = end_time.plus_seconds(one_day * 30);
+        let additional_interest = Uint128::new(10_000);
+
+        // Step 3
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the vault owner or lender proposes an extension
+        // ------------------------------------------------------------------------------
+        let propose_msg = ExecuteMsg::ProposeLoanExtension {
+            new_end_time,
+            additional_interest,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(FEE_COLLECTOR),
+                vault_c_addr.clone(),
+                &propose_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Test error case ContractError::InvalidLoanExtension {}
+        // when new_end_time is not after the current end_time
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time: end_time,
+                    additional_interest,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Test error case ContractError::NoPendingLoanExtension {}
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Test error case ContractError::Unauthorized {}
+        // when the proposer tries to accept their own extension
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time,
+                    additional_interest: additional_interest + additional_interest,
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 7
+        // Test error case ContractError::NoPendingLoanExtension {}
+        // when the lender accepts an extension proposed by a previous vault owner
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &propose_msg,
+                &[],
+            )
+            .unwrap();
+        for (from, to) in [(USER, "new_owner"), ("new_owner", USER)] {
+            router
+                .execute_contract(
+                    Addr::unchecked(from),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::TransferOwnership {
+                        to_address: to.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Vault owner replaces the lender's proposal, and the lender accepts it
+        // during the grace period
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &propose_msg,
+                &[],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds + 1));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap();
+
+        // Step 9
+        // Verify that end_time and interest_amount were updated
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        match info.liquidity_request.unwrap().state {
+            Some(LiquidityRequestState::FixedTermLoan {
+                end_time,
+                interest_amount: updated_interest_amount,
+                pending_extension,
+                ..
+            }) => {
+                assert_eq!(end_time, new_end_time);
+                assert_eq!(
+                    updated_interest_amount,
+                    interest_amount + additional_interest
+                );
+                assert_eq!(pending_extension, None);
+            }
+            _ => panic!("expected an active fixed term loan"),
+        }
+        let simulation: SimulateRepaymentResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::SimulateRepayment {})
+            .unwrap();
+        assert_eq!(
+            simulation.amount_due.amount,
+            requested_amount + interest_amount + additional_interest
+        );
+
+        // Step 10
+        // Test error case ContractError::LoanNotYetLiquidatable {}
+        // before the extended loan expires
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_day * 2));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 11
+        // Test error case ContractError::LoanExtensionWindowClosed {}
+        // once the grace period of the extended loan has expired
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = new_end_time.plus_seconds(one_day));
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time: new_end_time.plus_seconds(one_day * 30),
+                    additional_interest,
+                },
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_loan_extension_after_partial_repayment() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a 90 day FixedTermLoan that prorates interest on early repayment
+        // ------------------------------------------------------------------------------
+        let one_day = 60 * 60 * 24;
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(90_000);
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds: one_day * 90,
+            prorate_interest_on_early_repayment: true,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let end_time = router.block_info().time.plus_seconds(one_day * 90);
+
+        // Step 3
+        // Repay 320_000 of the 330_000 due on day 30,
+        // sending the 0.3% liquidity_comission along with the part above requested_amount
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_day * 30));
+        let already_repaid = Uint128::new(320_000);
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(already_repaid),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: already_repaid - requested_amount + liquidity_comission,
+                }],
+            )
+            .unwrap();
+
+        // Step 4
+        // Test error case ContractError::AlreadyRepaidExceedsAmountDue {}
+        // when doubling the term for free would prorate the interest due to 15_000
+        // ------------------------------------------------------------------------------
+        let new_end_time = end_time.plus_seconds(one_day * 90);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time,
+                    additional_interest: Uint128::zero(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Accept an extension that keeps the interest accrued so far
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time,
+                    additional_interest: interest_amount,
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap();
+
+        // Step 6
+        // Verify that the remaining 10_000 is due, and repay it
+        // ------------------------------------------------------------------------------
+        let simulation: SimulateRepaymentResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::SimulateRepayment {})
+            .unwrap();
+        let amount_due = Uint128::new(10_000);
+        assert_eq!(simulation.amount_due.amount, amount_due);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: amount_due,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the option is closed and the lender received the interest accrued
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) + already_repaid + amount_due - requested_amount
+        );
+    }
+
+    #[test]
+    fn test_installment_loan() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Test error case ContractError::InvalidLiquidityRequestOption {}
+        // when the installments do not pay back the requested_amount
+        // ------------------------------------------------------------------------------
+        let thirty_days = 60 * 60 * 24 * 30;
+        let requested_amount = Uint128::new(300_000);
+        let installment_amount = Uint128::new(80_000);
+        let option = |installment_amount| LiquidityRequestMsg::InstallmentLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            collateral_amount: Uint128::new(400_000),
+            number_of_installments: 4,
+            installment_period_in_seconds: thirty_days,
+            installment_amount,
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(Uint128::new(70_000)),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 3
+        // Test error case ContractError::InvalidLiquidityRequestOption {}
+        // when the total of the installments overflows
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(Uint128::MAX),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Create and accept an InstallmentLoan of 4 monthly installments
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(installment_amount),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option(installment_amount),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let start_time = router.block_info().time;
+
+        // Step 5
+        // Test error case ContractError::LoanNotYetLiquidatable {}
+        // before any installment is missed
+        // ------------------------------------------------------------------------------
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Test error case ContractError::InvalidRepaymentAmount {}
+        // when amount does not cover a whole number of installments
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(Uint128::new(50_000)),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 7
+        // Repay the first installment and verify the next due date
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        match info.liquidity_request.unwrap().state {
+            Some(LiquidityRequestState::InstallmentLoan {
+                next_due_date,
+                installments_paid,
+                ..
+            }) => {
+                assert_eq!(next_due_date, start_time.plus_seconds(thirty_days * 2));
+                assert_eq!(installments_paid, 1);
+            }
+            _ => panic!("expected an active installment loan"),
+        }
+
+        // Step 8
+        // Miss the second and third installments, then liquidate half of the collateral
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = start_time.plus_seconds(thirty_days * 3));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 9
+        // Verify that the lender claimed two quarters of the collateral_amount
+        // and the last installment is still open
+        // ------------------------------------------------------------------------------
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, Uint128::new(200_000));
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        match info.liquidity_request.unwrap().state {
+            Some(LiquidityRequestState::InstallmentLoan {
+                next_due_date,
+                installments_defaulted,
+                processing_liquidation,
+                ..
+            }) => {
+                assert_eq!(next_due_date, start_time.plus_seconds(thirty_days * 4));
+                assert_eq!(installments_defaulted, 2);
+                assert!(!processing_liquidation);
+            }
+            _ => panic!("expected an active installment loan"),
+        }
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 10
+        // Repay the last installment and verify that the option is closed
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) - requested_amount + installment_amount + installment_amount
+        );
+    }
+
+    #[test]
+    fn test_rewards_backed_loan() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Test error case ContractError::InvalidLiquidityRequestOption {}
+        // when exchange_rate is zero
+        // ------------------------------------------------------------------------------
+        let one_year = 60 * 60 * 24 * 365;
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let collateral_amount = Uint128::new(400_000);
+        let exchange_rate = Decimal::percent(150);
+        let option = |exchange_rate| LiquidityRequestMsg::RewardsBackedLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds: one_year,
+            exchange_rate,
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(Decimal::zero()),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 3
+        // Create and accept a RewardsBackedLoan
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(exchange_rate),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option(exchange_rate),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let get_already_repaid =
+            |router: &mut App| match get_vault_info(router, &vault_c_addr).liquidity_request {
+                Some(ActiveOption {
+                    state: Some(LiquidityRequestState::RewardsBackedLoan { already_repaid, .. }),
+                    ..
+                }) => already_repaid,
+                _ => panic!("expected an active rewards backed loan"),
+            };
+
+        // Step 4
+        // Claim six months of staking rewards and verify that they are routed to the lender
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_year / 2));
+        let first_rewards = get_vault_staking_info(&mut router, &vault_c_addr).accumulated_rewards;
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ClaimDelegatorRewards {},
+                &[],
+            )
+            .unwrap();
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, first_rewards);
+        assert_eq!(
+            get_already_repaid(&mut router),
+            first_rewards * exchange_rate
+        );
+
+        // Step 5
+        // Test error case ContractError::LoanNotYetLiquidatable {}
+        // before the loan expires
+        // ------------------------------------------------------------------------------
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Vault owner tops up the repayment
+        // ------------------------------------------------------------------------------
+        let top_up_amount = Uint128::new(105_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(top_up_amount),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Claim the staking rewards at maturity
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_year / 2 + 1));
+        let second_rewards = get_vault_staking_info(&mut router, &vault_c_addr).accumulated_rewards;
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ClaimDelegatorRewards {},
+                &[],
+            )
+            .unwrap();
+        let already_repaid = get_already_repaid(&mut router);
+        assert_eq!(
+            already_repaid,
+            first_rewards * exchange_rate + top_up_amount + second_rewards * exchange_rate
+        );
+
+        // Step 8
+        // Liquidate the collateral due on the part of the loan that was not repaid
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 9
+        // Verify that the lender claimed the collateral due and the option has been finalized
+        // ------------------------------------------------------------------------------
+        let amount_due = requested_amount + interest_amount;
+        let collateral_due =
+            collateral_amount.multiply_ratio(amount_due - already_repaid, amount_due);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            lender_balance.amount,
+            first_rewards + second_rewards + collateral_due
+        );
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_keeper_liquidate_collateral() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Test error case ContractError::KeeperFeeTooHigh {}
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let duration_in_seconds = 60u64;
+        let keeper_fee = Uint128::new(1_000);
+        let option = |keeper_fee| LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(30_000),
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(Uint128::new(MAX_KEEPER_FEE + 1)),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 3
+        // Create and accept a FixedTermLoan that pays a keeper_fee
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(keeper_fee),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option(keeper_fee),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let end_time = router.block_info().time.plus_seconds(duration_in_seconds);
+
+        // Step 4
+        // Verify that the next liquidation step is executable once the loan expires,
+        // and test error case ContractError::LoanNotYetLiquidatable {} before then
+        // ------------------------------------------------------------------------------
+        let next_step_msg = QueryMsg::NextLiquidationStep {};
+        let next_step: NextLiquidationStepResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &next_step_msg)
+            .unwrap();
+        assert_eq!(
+            next_step,
+            NextLiquidationStepResponse {
+                executable_at: end_time,
+                keeper_fee: Coin {
+                    denom: STAKING_DENOM.to_string(),
+                    amount: keeper_fee,
+                },
+            }
+        );
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(KEEPER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Keeper begins the liquidation once the loan expires,
+        // and the next step waits for the unbonded collateral
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = end_time);
+        router
+            .execute_contract(
+                Addr::unchecked(KEEPER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        let next_step: NextLiquidationStepResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &next_step_msg)
+            .unwrap();
+        assert_eq!(
+            next_step.executable_at,
+            end_time.plus_seconds(UNBONDING_TIME)
+        );
+
+        // Step 6
+        // Process the unbonding queue, then the keeper completes the liquidation
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        let next_step: NextLiquidationStepResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &next_step_msg)
+            .unwrap();
+        assert_eq!(next_step.executable_at, router.block_info().time);
+        router
+            .execute_contract(
+                Addr::unchecked(KEEPER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the keeper was paid the keeper_fee from the recovered collateral
+        // and the option has been finalized
+        // ------------------------------------------------------------------------------
+        let keeper_balance =
+            bank_balance(&mut router, &Addr::unchecked(KEEPER), STAKING_DENOM.into());
+        assert_eq!(keeper_balance.amount, keeper_fee);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, requested_amount - keeper_fee);
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_keeper_fee_is_capped_over_repeated_liquidations() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan that pays a keeper_fee
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let duration_in_seconds = 60u64;
+        let keeper_fee = Uint128::new(1_000);
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(30_000),
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Keeper liquidates the staking rewards accrued over a year after the loan expires,
+        // paying the whole keeper_fee, then keeps calling LiquidateCollateral
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| {
+            block.time = block
+                .time
+                .plus_seconds(duration_in_seconds + 60 * 60 * 24 * 365)
+        });
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        for _ in 0..3 {
+            router
+                .execute_contract(
+                    Addr::unchecked(KEEPER),
+                    vault_c_addr.clone(),
+                    &liquidation_msg,
+                    &[],
+                )
+                .unwrap();
+        }
+        let keeper_balance =
+            bank_balance(&mut router, &Addr::unchecked(KEEPER), STAKING_DENOM.into());
+        assert_eq!(keeper_balance.amount, keeper_fee);
+
+        // Step 4
+        // Verify that nothing is left of the keeper_fee for the next liquidation step
+        // ------------------------------------------------------------------------------
+        let next_step: NextLiquidationStepResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::NextLiquidationStep {})
+            .unwrap();
+        assert_eq!(next_step.keeper_fee.amount, Uint128::zero());
+
+        // Step 5
+        // Process the unbonding queue, then the keeper completes the liquidation
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(KEEPER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 6
+        // Verify that the keeper was paid the keeper_fee once over the whole liquidation
+        // and the rest of the collateral went to the lender
+        // ------------------------------------------------------------------------------
+        let keeper_balance =
+            bank_balance(&mut router, &Addr::unchecked(KEEPER), STAKING_DENOM.into());
+        assert_eq!(keeper_balance.amount, keeper_fee);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, requested_amount - keeper_fee);
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_unbonding_strategies() {
+        // Step 1
+        // Test error case ContractError::Unauthorized {}
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let set_strategy_msg = ExecuteMsg::SetUnbondingStrategy {
+            strategy: UnbondingStrategy::ProRata,
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &set_strategy_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 2
+        // Vault owner sets the unbonding strategy
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &set_strategy_msg,
+                &[],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.config.unbonding_strategy, UnbondingStrategy::ProRata);
+
+        // Step 3
+        // Verify that Sequential drains validators in the order delegations are returned
+        // ------------------------------------------------------------------------------
+        let delegated_amounts = [100_000, 500_000, 200_000, 400_000, 300_000];
+        let collateral_amount = Uint128::new(600_001);
+        let remaining = liquidate_with_unbonding_strategy(
+            UnbondingStrategy::Sequential,
+            &delegated_amounts,
+            collateral_amount,
+        );
+        assert_eq!(
+            remaining,
+            [0, 0, 199_999, 400_000, 300_000].map(Uint128::new).to_vec()
+        );
+
+        // Step 4
+        // Verify that LargestFirst drains the largest delegations first
+        // ------------------------------------------------------------------------------
+        let remaining = liquidate_with_unbonding_strategy(
+            UnbondingStrategy::LargestFirst,
+            &delegated_amounts,
+            collateral_amount,
+        );
+        assert_eq!(
+            remaining,
+            [100_000, 0, 200_000, 299_999, 300_000]
+                .map(Uint128::new)
+                .to_vec()
+        );
+
+        // Step 5
+        // Verify that ProRata unbonds from every validator in proportion to its delegation,
+        // with the rounding remainder taken from the largest delegation
+        // ------------------------------------------------------------------------------
+        let remaining = liquidate_with_unbonding_strategy(
+            UnbondingStrategy::ProRata,
+            &delegated_amounts,
+            collateral_amount,
+        );
+        assert_eq!(
+            remaining,
+            [60_000, 299_999, 120_000, 240_000, 180_000]
+                .map(Uint128::new)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_unbonding_entry_limit() {
+        // Step 1
+        // Get vault instance, tracking unbondings over UNBONDING_PERIOD,
+        // and delegate to VALIDATOR_ONE_ADDRESS and VALIDATOR_TWO_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) =
+            instantiate_vault_with_unbonding_period(&mut router, UNBONDING_PERIOD);
+        let delegated_amount = Uint128::new(1_000_000);
+        for validator in [VALIDATOR_ONE_ADDRESS, VALIDATOR_TWO_ADDRESS] {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::Delegate {
+                        validator: validator.to_string(),
+                        amount: delegated_amount,
+                    },
+                    &[Coin {
+                        denom: STAKING_DENOM.into(),
+                        amount: delegated_amount,
+                    }],
+                )
+                .unwrap();
+        }
+
+        // Step 2
+        // Vault owner fills the unbonding entries with VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let undelegate_msg = ExecuteMsg::Undelegate {
+            validator: VALIDATOR_ONE_ADDRESS.to_string(),
+            amount: Uint128::new(1),
+        };
+        for _ in 0..MAX_UNBONDING_ENTRIES {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &undelegate_msg,
+                    &[],
+                )
+                .unwrap();
+        }
+
+        // Step 3
+        // Test error case ContractError::MaxUnbondingEntriesReached {}
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &undelegate_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Verify that a new entry can be created once the unbonding period is over
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD));
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &undelegate_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Fill the unbonding entries with VALIDATOR_ONE_ADDRESS again
+        // ------------------------------------------------------------------------------
+        for _ in 1..MAX_UNBONDING_ENTRIES {
+            router
+                .execute_contract(
+                    Addr::unchecked(USER),
+                    vault_c_addr.clone(),
+                    &undelegate_msg,
+                    &[],
+                )
+                .unwrap();
+        }
+        let validator_one_amount =
+            get_delegated_amount(&mut router, &vault_c_addr, VALIDATOR_ONE_ADDRESS);
+
+        // Step 6
+        // Create and accept a FixedTermLoan, then liquidate it once it expires
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(100_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(10_000),
+            collateral_amount: Uint128::new(500_000),
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap();
+
+        // Step 7
+        // Verify that the collateral was unbonded from VALIDATOR_TWO_ADDRESS only,
+        // as VALIDATOR_ONE_ADDRESS has reached MAX_UNBONDING_ENTRIES
+        // ------------------------------------------------------------------------------
+        assert_eq!(
+            get_delegated_amount(&mut router, &vault_c_addr, VALIDATOR_ONE_ADDRESS),
+            validator_one_amount
+        );
+        assert!(
+            get_delegated_amount(&mut router, &vault_c_addr, VALIDATOR_TWO_ADDRESS)
+                < delegated_amount
+        );
+    }
+
+    #[test]
+    fn test_pending_unbondings() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Vault owner undelegates, and verify that the unbonding is pending
+        // until the unbonding period is over
+        // ------------------------------------------------------------------------------
+        let undelegated_amount = Uint128::new(100_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Undelegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: undelegated_amount,
+                },
+                &[],
+            )
+            .unwrap();
+        let start_time = router.block_info().time;
+        let pending_unbondings_msg = QueryMsg::PendingUnbondings {};
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &pending_unbondings_msg)
+            .unwrap();
+        assert_eq!(
+            pending_unbondings.unbondings,
+            vec![Unbonding {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: Coin {
+                    denom: STAKING_DENOM.to_string(),
+                    amount: undelegated_amount,
+                },
+                start_time,
+                completion_time: start_time.plus_seconds(UNBONDING_TIME),
+            }]
+        );
+
+        // Step 3
+        // Create and accept a FixedTermLoan, then forward the blockchain past its end_time
+        // and process the unbonding queue
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(100_000);
+        let collateral_amount = Uint128::new(500_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(10_000),
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let end_time = start_time.plus_seconds(duration_in_seconds);
+        router.update_block(|block| block.time = end_time);
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &pending_unbondings_msg)
+            .unwrap();
+        assert_eq!(pending_unbondings.unbondings, vec![]);
+
+        // Step 4
+        // Lender liquidates the collateral, claiming the vault balance
+        // and unbonding the rest of the collateral
+        // ------------------------------------------------------------------------------
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &pending_unbondings_msg)
+            .unwrap();
+        assert_eq!(
+            pending_unbondings.unbondings,
+            vec![Unbonding {
+                validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                amount: Coin {
+                    denom: STAKING_DENOM.to_string(),
+                    amount: collateral_amount - undelegated_amount,
+                },
+                start_time: end_time,
+                completion_time: end_time.plus_seconds(UNBONDING_TIME),
+            }]
+        );
+
+        // Step 5
+        // Verify that liquidating again does not unbond more collateral
+        // while the pending unbondings cover the outstanding collateral,
+        // and the next step waits for them to mature
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            get_delegated_amount(&mut router, &vault_c_addr, VALIDATOR_ONE_ADDRESS),
+            delegated_amount - collateral_amount
+        );
+        let next_step: NextLiquidationStepResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::NextLiquidationStep {})
+            .unwrap();
+        assert_eq!(
+            next_step.executable_at,
+            end_time.plus_seconds(UNBONDING_TIME)
+        );
+
+        // Step 6
+        // Process the unbonding queue, then complete the liquidation
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, collateral_amount);
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_ltv_loan() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS,
+        // then price STAKING_DENOM on the oracle
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+        let oracle = instantiate_oracle(&mut router);
+
+        // Step 2
+        // Test error case ContractError::Std {}
+        // when the oracle cannot price the collateral
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(200_000);
+        let interest_amount = Uint128::new(20_000);
+        let margin_call_ltv = Decimal::percent(80);
+        let margin_call_period_in_seconds = 60 * 60;
+        let option = |margin_call_ltv| LiquidityRequestMsg::LtvLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: Uint128::new(600_000),
+            duration_in_seconds: 60 * 60 * 24,
+            oracle: oracle.to_string(),
+            margin_call_ltv,
+            margin_call_period_in_seconds,
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(margin_call_ltv),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 3
+        // Test error case ContractError::InvalidLiquidityRequestOption {}
+        // with a margin_call_ltv above one
+        // ------------------------------------------------------------------------------
+        set_oracle_price(&mut router, &oracle, Decimal::percent(50));
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(Decimal::percent(101)),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 4
+        // Create and accept an LtvLoan, and verify its health
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option(margin_call_ltv),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option(margin_call_ltv),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+        let loan_health_msg = QueryMsg::LoanHealth {};
+        let health: LoanHealthResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &loan_health_msg)
+            .unwrap();
+        let ltv = Decimal::from_ratio(220_000u128, 300_000u128);
+        assert_eq!(
+            health,
+            LoanHealthResponse {
+                collateral_value: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(300_000),
+                },
+                debt: Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount + interest_amount,
+                },
+                ltv,
+                health_factor: margin_call_ltv.checked_div(ltv).unwrap(),
+                margin_call_deadline: None,
+            }
+        );
+
+        // Step 5
+        // Test error case ContractError::LoanIsHealthy {}
+        // ------------------------------------------------------------------------------
+        let margin_call_msg = ExecuteMsg::MarginCall {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &margin_call_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Lender calls a margin once the price drops, and test error case
+        // ContractError::MarginCallAlreadyActive {}
+        // ------------------------------------------------------------------------------
+        set_oracle_price(&mut router, &oracle, Decimal::percent(40));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &margin_call_msg,
+                &[],
+            )
+            .unwrap();
+        let health: LoanHealthResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &loan_health_msg)
+            .unwrap();
+        assert!(health.health_factor < Decimal::one());
+        assert_eq!(
+            health.margin_call_deadline,
+            Some(
+                router
+                    .block_info()
+                    .time
+                    .plus_seconds(margin_call_period_in_seconds)
+            )
+        );
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &margin_call_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 7
+        // Test error case ContractError::LoanNotYetLiquidatable {}
+        // before the margin call expires
+        // ------------------------------------------------------------------------------
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Vault owner repays part of the loan, which clears the margin call
+        // ------------------------------------------------------------------------------
+        let repaid_amount = Uint128::new(40_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(repaid_amount),
+                },
+                &[],
+            )
+            .unwrap();
+        let health: LoanHealthResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &loan_health_msg)
+            .unwrap();
+        assert_eq!(health.ltv, Decimal::from_ratio(180_000u128, 240_000u128));
+        assert_eq!(health.margin_call_deadline, None);
+
+        // Step 9
+        // Lender calls a margin again after another price drop, and the collateral
+        // topped up by the vault owner is not enough to clear it
+        // ------------------------------------------------------------------------------
+        set_oracle_price(&mut router, &oracle, Decimal::percent(30));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &margin_call_msg,
+                &[],
+            )
+            .unwrap();
+        let deadline = router
+            .block_info()
+            .time
+            .plus_seconds(margin_call_period_in_seconds);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::TopUpCollateral {
+                    amount: Uint128::new(100_000),
+                },
+                &[],
+            )
+            .unwrap();
+        let health: LoanHealthResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &loan_health_msg)
+            .unwrap();
+        assert_eq!(health.collateral_value.amount, Uint128::new(210_000));
+        assert_eq!(health.margin_call_deadline, Some(deadline));
+
+        // Step 10
+        // Lender liquidates the collateral early once the margin call expires
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = deadline);
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 11
+        // Verify that the collateral due on the part of the loan not yet repaid
+        // is being claimed by the lender
+        // ------------------------------------------------------------------------------
+        let collateral_due = Uint128::new(700_000).multiply_ratio(180_000u128, 220_000u128);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::PendingUnbondings {})
+            .unwrap();
+        let unbonding_amount: Uint128 = pending_unbondings
+            .unbondings
+            .iter()
+            .map(|unbonding| unbonding.amount.amount)
+            .sum();
+        assert_eq!(lender_balance.amount + unbonding_amount, collateral_due);
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        match info.liquidity_request.unwrap().state {
+            Some(LiquidityRequestState::LtvLoan {
+                processing_liquidation,
+                ..
+            }) => assert!(processing_liquidation),
+            _ => panic!("expected an active LtvLoan"),
+        }
+    }
+
+    #[test]
+    fn test_syndicated_liquidity_request() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create a FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let collateral_amount = Uint128::new(660_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 3
+        // Contribute 100_000 from each of the first two lenders
+        // ------------------------------------------------------------------------------
+        let lender_one = Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]);
+        let lender_two = Addr::unchecked(COUNTER_OFFER_PROPOSERS[1]);
+        let fund = |router: &mut App, lender: &Addr, amount: u128| {
+            router.execute_contract(
+                lender.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::FundLiquidityRequest {
+                    option: option.clone(),
+                    amount: Uint128::new(amount),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(amount),
+                }],
+            )
+        };
+        fund(&mut router, &lender_one, 100_000).unwrap();
+        fund(&mut router, &lender_two, 100_000).unwrap();
+
+        // Step 4
+        // Test error case ContractError::InvalidContributionAmount {}
+        // when contributing more than the remaining 100_000, or zero
+        // ------------------------------------------------------------------------------
+        let lender_three = Addr::unchecked(COUNTER_OFFER_PROPOSERS[2]);
+        fund(&mut router, &lender_three, 100_001).unwrap_err();
+        router
+            .execute_contract(
+                lender_three,
+                vault_c_addr.clone(),
+                &ExecuteMsg::FundLiquidityRequest {
+                    option: option.clone(),
+                    amount: Uint128::zero(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Verify that the request is still pending with 200_000 funded,
+        // and that the vault owner can not withdraw the contributions
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request.unwrap().lender, None);
+        let shares = get_lender_shares(&mut router, &vault_c_addr);
+        assert_eq!(shares.total_funded.amount, Uint128::new(200_000));
+        assert_eq!(shares.shares.len(), 2);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: Uint128::one(),
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Withdraw the contribution of the second lender, and contribute 50_000 again
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                lender_two.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawContribution {},
+                &[],
+            )
+            .unwrap();
+        let lender_two_balance = bank_balance(&mut router, &lender_two, IBC_DENOM_1.into());
+        assert_eq!(lender_two_balance.amount, Uint128::new(SUPPLY));
+        router
+            .execute_contract(
+                lender_two.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawContribution {},
+                &[],
+            )
+            .unwrap_err();
+        fund(&mut router, &lender_two, 50_000).unwrap();
+
+        // Step 7
+        // Accept the liquidity request by funding the remaining 150_000
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest {
+                    option: option.clone(),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(150_000),
+                }],
+            )
+            .unwrap();
+
+        // Step 8
+        // Verify that the option is active and the requested_amount is available
+        // to the vault owner, less the liquidity commission
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request.unwrap().lender,
+            Some(Addr::unchecked(LENDER))
+        );
+        let shares = get_lender_shares(&mut router, &vault_c_addr);
+        assert_eq!(shares.total_funded.amount, requested_amount);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: requested_amount - Uint128::new(900),
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 9
+        // Test error case ContractError::LenderPositionIsSyndicated {}
+        // when the lead lender, holding half the shares, proposes or accepts a loan extension,
+        // or transfers the lender position. Other funders are not authorized either
+        // ------------------------------------------------------------------------------
+        let propose_msg = ExecuteMsg::ProposeLoanExtension {
+            new_end_time: router
+                .block_info()
+                .time
+                .plus_seconds(duration_in_seconds * 2),
+            additional_interest: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &propose_msg,
+                &[],
+            )
+            .unwrap_err();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &propose_msg,
+                &[],
+            )
+            .unwrap();
+        for caller in [Addr::unchecked(LENDER), lender_one.clone()] {
+            router
+                .execute_contract(
+                    caller,
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::AcceptLoanExtension {},
+                    &[],
+                )
+                .unwrap_err();
+        }
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::TransferLenderPosition {
+                    to_address: lender_one.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 10
+        // Repay a third of requested_amount + interest_amount,
+        // and verify that it is split pro rata between the lenders
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(Uint128::new(110_000)),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(110_000),
+                }],
+            )
+            .unwrap();
+        for (lender, funded, repaid) in [
+            (&lender_one, 100_000u128, 36_666u128),
+            (&lender_two, 50_000u128, 18_333u128),
+            (&Addr::unchecked(LENDER), 150_000u128, 55_001u128),
+        ] {
+            let balance = bank_balance(&mut router, lender, IBC_DENOM_1.into());
+            assert_eq!(balance.amount.u128(), SUPPLY - funded + repaid);
+        }
+
+        // Step 11
+        // Fast foward the time so the option expires, and liquidate the collateral
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds));
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 12
+        // Verify that the two thirds of the collateral_amount owed
+        // were split pro rata between the lenders, and the option has been finalized
+        // ------------------------------------------------------------------------------
+        for (lender, claimed) in [
+            (&lender_one, 146_666u128),
+            (&lender_two, 73_333u128),
+            (&Addr::unchecked(LENDER), 220_001u128),
+        ] {
+            let balance = bank_balance(&mut router, lender, STAKING_DENOM.into());
+            assert_eq!(balance.amount.u128(), claimed);
+        }
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_syndicated_lead_lender() {
+        // Step 1
+        // Get vault instance, delegate to VALIDATOR_ONE_ADDRESS,
+        // and create a FixedTermLoan liquidity request paying a keeper_fee
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+        let requested_amount = Uint128::new(300_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount: Uint128::new(30_000),
+            collateral_amount: Uint128::new(660_000),
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::new(1_000),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 2
+        // Contribute 200_000 from a first lender, and accept the liquidity request
+        // by funding the remaining 100_000 from LENDER
+        // ------------------------------------------------------------------------------
+        let lender_one = Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]);
+        router
+            .execute_contract(
+                lender_one.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::FundLiquidityRequest {
+                    option: option.clone(),
+                    amount: Uint128::new(200_000),
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(200_000),
+                }],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(100_000),
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Verify that the lender holding the largest share leads the option,
+        // rather than LENDER who completed its funding
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request.unwrap().lender,
+            Some(lender_one.clone())
+        );
+
+        // Step 4
+        // Fast foward the time so the option expires, and liquidate the collateral from LENDER
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds));
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Verify that LENDER, holding a share of the option, is not paid the keeper_fee,
+        // and the collateral_amount is split pro rata between the lenders
+        // ------------------------------------------------------------------------------
+        for (lender, claimed) in [
+            (&lender_one, 440_000u128),
+            (&Addr::unchecked(LENDER), 220_000u128),
+        ] {
+            let balance = bank_balance(&mut router, lender, STAKING_DENOM.into());
+            assert_eq!(balance.amount.u128(), claimed);
+        }
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_transfer_lender_position() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Test error case ContractError::Unauthorized {}
+        // when someone other than the lender transfers the lender position
+        // ------------------------------------------------------------------------------
+        let new_lender = Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]);
+        let transfer_msg = ExecuteMsg::TransferLenderPosition {
+            to_address: new_lender.to_string(),
+        };
+        for caller in [USER, COUNTER_OFFER_PROPOSERS[0]] {
+            router
+                .execute_contract(
+                    Addr::unchecked(caller),
+                    vault_c_addr.clone(),
+                    &transfer_msg,
+                    &[],
+                )
+                .unwrap_err();
+        }
+
+        // Step 4
+        // Test error case ContractError::InvalidLenderPositionRecipient {}
+        // when transferring the lender position to the vault owner
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::TransferLenderPosition {
+                    to_address: USER.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 5
+        // Propose a loan extension, then transfer the lender position to new_lender
+        // ------------------------------------------------------------------------------
+        let new_end_time = router
+            .block_info()
+            .time
+            .plus_seconds(duration_in_seconds * 2);
+        let additional_interest = Uint128::new(10_000);
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time,
+                    additional_interest,
+                },
+                &[],
+            )
+            .unwrap();
+        let res = router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &transfer_msg,
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm")
+                .add_attribute("method", "transfer_lender_position")
+                .add_attribute("from_address", LENDER)
+                .add_attribute("to_address", new_lender.to_string())
+                .add_attribute("share", requested_amount.to_string())
+        ));
+
+        // Step 6
+        // Verify that new_lender holds the position and the share of the previous lender
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request.unwrap().lender,
+            Some(new_lender.clone())
+        );
+        let shares = get_lender_shares(&mut router, &vault_c_addr);
+        assert_eq!(shares.shares.len(), 1);
+        assert_eq!(shares.shares[0].lender, new_lender);
+        assert_eq!(shares.shares[0].amount, requested_amount);
+
+        // Step 7
+        // Test error case ContractError::NoPendingLoanExtension {}
+        // when the vault owner accepts the extension proposed by the previous lender
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Test error case ContractError::Unauthorized {}
+        // when the previous lender, or new_lender as the proposer, accepts
+        // the extension proposed by new_lender
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                new_lender.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::ProposeLoanExtension {
+                    new_end_time,
+                    additional_interest,
+                },
+                &[],
+            )
+            .unwrap();
+        for caller in [LENDER, COUNTER_OFFER_PROPOSERS[0]] {
+            router
+                .execute_contract(
+                    Addr::unchecked(caller),
+                    vault_c_addr.clone(),
+                    &ExecuteMsg::AcceptLoanExtension {},
+                    &[],
+                )
+                .unwrap_err();
+        }
+
+        // Step 9
+        // Accept the extension and repay the loan, sending the interest
+        // and the 0.3% liquidity_comission to the vault
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLoanExtension {},
+                &[],
+            )
+            .unwrap();
+        let liquidity_comission = Uint128::new(900);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + additional_interest + liquidity_comission,
+                }],
+            )
+            .unwrap();
+
+        // Step 10
+        // Verify that the repayment went to new_lender
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+        let new_lender_balance = bank_balance(&mut router, &new_lender, IBC_DENOM_1.into());
+        assert_eq!(
+            new_lender_balance.amount,
+            Uint128::new(SUPPLY) + requested_amount + interest_amount + additional_interest
+        );
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
+        assert_eq!(
+            lender_balance.amount,
+            Uint128::new(SUPPLY) - requested_amount
+        );
+    }
+
+    #[test]
+    fn test_liquidate_collateral_after_partial_repayment() {
+        // Step 1
+        // Get vault instance and delegate to VALIDATOR_ONE_ADDRESS
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 2
+        // Create and accept a FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let collateral_amount = Uint128::new(660_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount,
+            duration_in_seconds,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::AcceptLiquidityRequest { option },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Repay a third of requested_amount + interest_amount
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan {
+                    amount: Some(Uint128::new(110_000)),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Fast foward the time so the option expires, then begin liquidation
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(duration_in_seconds));
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 5
+        // Process the unbonding queue and complete the liquidation
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 6
+        // Verify that the lender claimed two thirds of the collateral_amount
+        // and the option has been finalized
+        // ------------------------------------------------------------------------------
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(lender_balance.amount, Uint128::new(440_000));
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_liquidate_collateral() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Delegate to VALIDATOR_ONE_ADDRESS & VALIDATOR_TWO_ADDRESS
+        // ------------------------------------------------------------------------------
+        let delegated_amount = Uint128::new(1_000_000);
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_TWO_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
+                &[Coin {
+                    denom: STAKING_DENOM.into(),
+                    amount: delegated_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 3
+        // Error liquidating fixed term loan when no option is available
+        // ------------------------------------------------------------------------------
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
                 &[],
             )
             .unwrap_err();
 
+        // Step 4
+        // Create a valid FixedTermLoan liquidity request
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let one_year_duration = 60 * 60 * 24 * 365; // 1 year;
+        let option = LiquidityRequestMsg::FixedTermLoan {
+            requested_amount: Coin {
+                denom: IBC_DENOM_1.to_string(),
+                amount: requested_amount,
+            },
+            interest_amount,
+            collateral_amount: requested_amount,
+            duration_in_seconds: one_year_duration,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RequestLiquidity {
+                    option: option.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+
         // Step 5
-        // Accept the option
+        // Test error case ContractError::Unauthorized {}
+        // because there is a liquidity request that has not been accepted yet
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 6
+        // Accept the open liquidity request
+        // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &accept_liquidity_request_msg,
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: requested_amount,
+                }],
+            )
+            .unwrap();
+
+        // Step 7
+        // Try to call liquidate collateral with ContractError::Unauthorized {}
+        // because there is a fixed term loans that have not yet expired
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 8
+        // Try to call liquidate collateral with ContractError::LoanNotYetLiquidatable {}
+        // when a third party is calling this method before the option expires
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked("WRONG_OWNER"),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap_err();
+
+        // Step 9
+        // Fast foward the time so the option expires without repayment
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(one_year_duration));
+
+        // Step 10
+        // Begin liquidation of collateral, partial liquidation
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &liquidation_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 11
+        // Verify that accumulated staking rewards and
+        // available staking_demon vault balance was send to lender
+        // ------------------------------------------------------------------------------
+        let expected_claims_after_one_years = Uint128::new(200_000);
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        assert_eq!(
+            lender_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_claims_after_one_years
+            }
+        );
+
+        // Step 12
+        // Try to repay loan with ContractError::Unauthorized {}
+        // when liquidation is processing
         // ------------------------------------------------------------------------------
+        let repay_loan_msg = ExecuteMsg::RepayLoan { amount: None };
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount,
-                }],
+                &repay_loan_msg,
+                &[],
             )
+            .unwrap_err();
+
+        // Step 13
+        // Foward the blockchain ahead and process unbonding queue
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(10));
+        router
+            .sudo(cw_multi_test::SudoMsg::Staking(
+                cw_multi_test::StakingSudo::ProcessQueue {},
+            ))
             .unwrap();
 
-        // Step 6
-        // Verify that the correct info for the liquidity request was updated with the option
-        // state
+        // Step 14
+        // Verify that vault balance contains the expected_outstanding_balance
         // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
+        let expected_outstanding_balance = Uint128::new(100_000);
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
         assert_eq!(
-            info.liquidity_request,
-            Some(ActiveOption {
-                lender: Some(Addr::unchecked(LENDER)),
-                state: Some(LiquidityRequestState::FixedInterestRental {
-                    requested_amount: Coin {
-                        denom: IBC_DENOM_1.to_string(),
-                        amount,
-                    },
-                    claimable_tokens: amount,
-                    already_claimed: Uint128::zero(),
-                    can_cast_vote: false,
-                }),
-                msg: option
-            })
+            vault_balance,
+            Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_outstanding_balance
+            }
         );
 
-        // Step 7
-        // Test error case ContractError::Unauthorized {}
-        // Trying to accept an option that already has an active lender
+        // Step 15
+        // Complete liquidation of collateral
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
+                &liquidation_msg,
+                &[],
             )
-            .unwrap_err();
+            .unwrap();
 
-        // Step 8
-        // Verify that liquidity_request_commission was paid to INSTANTIATOR_ADDR
-        // The liquidity_request_commission is calculated as 0.3% of requested amount
+        // Step 16
+        // Verify that outstanding balance was send to lender
         // ------------------------------------------------------------------------------
-        let liquidity_request_commission = Uint128::new(3_000);
-        let instantiator_balance = bank_balance(
-            &mut router,
-            &Addr::unchecked(INSTANTIATOR_ADDR),
-            IBC_DENOM_1.into(),
-        );
+        let lender_balance =
+            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
         assert_eq!(
-            instantiator_balance,
+            lender_balance,
             Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: liquidity_request_commission
+                denom: STAKING_DENOM.to_string(),
+                amount: expected_claims_after_one_years + expected_outstanding_balance
             }
         );
+
+        // Step 17
+        // Verify that the option has been finalized
+        // ------------------------------------------------------------------------------
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
     }
 
     #[test]
-    fn test_accept_liquidity_request_fixed_term_rental() {
+    fn test_withdraw_balance() {
         // Step 1
         // Get vault instance
         // ------------------------------------------------------------------------------
@@ -2496,36 +7901,64 @@ mod tests {
         let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
 
         // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
+        // Delegate 600_000 STAKING_DENOM to VALIDATOR_ONE_ADDRESS
+        // Leave 1_000_000 STAKING_DENOM as balance
         // ------------------------------------------------------------------------------
-        let amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount,
-        };
+        let total_sent_to_vult = Uint128::new(1_600_000);
+        let delegated_amount = Uint128::new(600_000);
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &delegate_msg,
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
                 &[Coin {
                     denom: STAKING_DENOM.into(),
-                    amount,
+                    amount: total_sent_to_vult,
                 }],
             )
             .unwrap();
 
         // Step 3
-        // Create a valid FixedTermRental liquidity request
+        // Withdraw 100_000 STAKING_DENOM leaving 900_000 balance
         // ------------------------------------------------------------------------------
-        let duration_in_seconds = 60u64;
-        let option = LiquidityRequestMsg::FixedTermRental {
+        let withdraw_balance_msg = ExecuteMsg::WithdrawBalance {
+            to_address: None,
+            funds: Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: Uint128::new(100_000),
+            },
+        };
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &withdraw_balance_msg,
+                &[],
+            )
+            .unwrap();
+
+        // Step 4
+        // Create a fixed term loan liquidity using 600_000 STAKING_DENOM as collateral
+        // ------------------------------------------------------------------------------
+        let requested_amount = Uint128::new(300_000);
+        let collateral_amount = Uint128::new(600_000);
+        let one_year_duration = 60 * 60 * 24 * 365; // 1 year;
+        let option = LiquidityRequestMsg::FixedTermLoan {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
-                amount,
+                amount: requested_amount,
             },
-            duration_in_seconds,
-            can_cast_vote: false,
+            interest_amount: Uint128::new(30_000),
+            collateral_amount,
+            duration_in_seconds: one_year_duration,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -2538,30 +7971,29 @@ mod tests {
             )
             .unwrap();
 
-        // Step 4
-        // Test error case ContractError::InvalidInputAmount {}
-        // by sending the wrong requested amount
+        // Step 5
+        // Withdraw 100_000 STAKING_DENOM leaving 800_000 balance
         // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest {
-            option: option.clone(),
+        let withdraw_balance_msg = ExecuteMsg::WithdrawBalance {
+            to_address: None,
+            funds: Coin {
+                denom: STAKING_DENOM.to_string(),
+                amount: Uint128::new(100_000),
+            },
         };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
+                &withdraw_balance_msg,
                 &[],
             )
-            .unwrap_err();
-
-        // Step 5
-        // Foward the blockchain ahead by one year
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+            .unwrap();
 
         // Step 6
-        // Accept the option
+        // Accept the fixed term loan
         // ------------------------------------------------------------------------------
+        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
         router
             .execute_contract(
                 Addr::unchecked(LENDER),
@@ -2569,174 +8001,82 @@ mod tests {
                 &accept_liquidity_request_msg,
                 &[Coin {
                     denom: IBC_DENOM_1.to_string(),
-                    amount,
+                    amount: requested_amount,
                 }],
             )
             .unwrap();
 
         // Step 7
-        // Verify that the vault contains the balance of the accumulated_rewards claimed
-        // ------------------------------------------------------------------------------
-        let balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        assert_eq!(
-            balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(100_000)
-            }
-        );
-
-        // Step 8
-        // Verify that the correct info for the liquidity request was updated with the option
-        // state
-        // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(
-            info.liquidity_request,
-            Some(ActiveOption {
-                lender: Some(Addr::unchecked(LENDER)),
-                state: Some(LiquidityRequestState::FixedTermRental {
-                    requested_amount: Coin {
-                        denom: IBC_DENOM_1.to_string(),
-                        amount,
-                    },
-                    start_time: router.block_info().time,
-                    last_claim_time: router.block_info().time,
-                    end_time: router.block_info().time.plus_seconds(duration_in_seconds),
-                    can_cast_vote: false,
-                }),
-                msg: option
-            })
-        );
-
-        // Step 9
-        // Test error case ContractError::Unauthorized {}
-        // Trying to accept an option that already has an active lender
+        // Withdraw 100_000 STAKING_DENOM leaving 700_000 balance
+        // Withdrawal still possible because the fixed term loan has not expired yet
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
+                &withdraw_balance_msg,
+                &[],
             )
-            .unwrap_err();
-
-        // Step 10
-        // Verify that liquidity_request_commission was paid to INSTANTIATOR_ADDR
-        // The liquidity_request_commission is calculated as 0.3% of requested amount
-        // ------------------------------------------------------------------------------
-        let liquidity_request_commission = Uint128::new(3_000);
-        let instantiator_balance = bank_balance(
-            &mut router,
-            &Addr::unchecked(INSTANTIATOR_ADDR),
-            IBC_DENOM_1.into(),
-        );
-        assert_eq!(
-            instantiator_balance,
-            Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: liquidity_request_commission
-            }
-        );
-    }
+            .unwrap();
 
-    #[test]
-    fn test_claim_delegator_rewards_no_liquidity_request() {
-        // Step 1
-        // Get vault instance
+        // Step 8
+        // Expire the fixed term loan without repayment
         // ------------------------------------------------------------------------------
-        let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        router.update_block(|block| block.time = block.time.plus_seconds(one_year_duration));
 
-        // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
+        // Step 8
+        // Try to withdraw some of the balance with ContractError::ClearOutstandingDebt {}
         // ------------------------------------------------------------------------------
-        let amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount,
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &delegate_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
+                &withdraw_balance_msg,
+                &[],
             )
-            .unwrap();
+            .unwrap_err();
 
-        // Step 3
-        // Delegate to VALIDATOR_TWO_ADDRESS
+        // Step 9
+        // Liquidate collateral to clear the debt
         // ------------------------------------------------------------------------------
-        let amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_TWO_ADDRESS.to_string(),
-            amount,
-        };
+        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &delegate_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount,
-                }],
+                &liquidation_msg,
+                &[],
             )
             .unwrap();
 
-        // Step 4
-        // Foward the blockchain ahead by one year
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
-
-        // Step 5
-        // query_staking_info and verify data
-        // ------------------------------------------------------------------------------
-        let staking_info = get_vault_staking_info(&mut router, &vault_c_addr);
-        assert_eq!(
-            staking_info,
-            StakingInfoResponse {
-                total_staked: Uint128::new(2000000),
-                accumulated_rewards: Uint128::new(200000)
-            }
-        );
-
-        // Step 6
-        // Claim rewards from all validators
+        // Step 10
+        // Withdraw the remaining 100_000 STAKING_DENOM from the vault
         // ------------------------------------------------------------------------------
-        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &claim_rewards_msg,
+                &withdraw_balance_msg,
                 &[],
             )
             .unwrap();
 
-        // Step 7
-        // Verify by inspecting contract balance
+        // Step 11
+        // Verify that the vault balance is 60_000, which is the accumulated
+        // staking rewards claimed during liquidation
         // ------------------------------------------------------------------------------
-        let balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
+        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
         assert_eq!(
-            balance,
+            vault_balance,
             Coin {
                 denom: STAKING_DENOM.to_string(),
-                amount: staking_info.accumulated_rewards
+                amount: Uint128::new(60_000)
             }
         );
     }
 
     #[test]
-    fn test_claim_delegator_rewards_with_fixed_interest_rental() {
+    fn test_withdraw_balance_with_reserved_counter_offers() {
         // Step 1
         // Get vault instance
         // ------------------------------------------------------------------------------
@@ -2744,36 +8084,30 @@ mod tests {
         let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
 
         // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
+        // Send 100_000 IBC_DENOM_1 to the vault as the owner's free balance
         // ------------------------------------------------------------------------------
-        let delegated_amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount: delegated_amount,
-        };
+        let owner_balance = Uint128::new(100_000);
         router
-            .execute_contract(
+            .send_tokens(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &delegate_msg,
                 &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount: delegated_amount,
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: owner_balance,
                 }],
             )
             .unwrap();
 
         // Step 3
-        // Create a valid FixedInterestRental liquidity request
+        // Create a valid liquidity request and open a counter offer
+        // from COUNTER_OFFER_PROPOSERS[0]
         // ------------------------------------------------------------------------------
-        let requested_amount = Uint128::new(350_000);
-        let claimable_tokens = Uint128::new(350_000);
-        let option = LiquidityRequestMsg::FixedInterestRental {
+        let option = LiquidityRequestMsg::FixedTermRental {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
-                amount: requested_amount,
+                amount: Uint128::new(1_000_000),
             },
-            claimable_tokens,
+            duration_in_seconds: 60u64,
             can_cast_vote: false,
         };
         router
@@ -2786,159 +8120,171 @@ mod tests {
                 &[],
             )
             .unwrap();
-
-        // Step 4
-        // Accept the option
-        // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: option.clone(),
+                &ExecuteMsg::OpenCounterOffer {
+                    new_amount: Uint128::new(900_000),
+                    for_option: option,
                 },
                 &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount: requested_amount,
+                    denom: IBC_DENOM_1.into(),
+                    amount: Uint128::new(900_000),
                 }],
             )
             .unwrap();
 
+        // Step 4
+        // Test error case ContractError::InsufficientWithdrawableBalance {}
+        // When the vault owner tries to withdraw into the escrowed counter offer
+        // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: owner_balance + Uint128::new(1),
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+
         // Step 5
-        // Fast foward the chain to a future date, where
-        // the rewards has not fully covered the claimable_tokens
+        // Increase the counter offer and verify the vault owner still
+        // cannot withdraw the additional escrowed funds
         // ------------------------------------------------------------------------------
-        let expected_claims_after_two_years = Uint128::new(200_000);
-        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365 * 2));
+        router
+            .execute_contract(
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
+                vault_c_addr.clone(),
+                &ExecuteMsg::UpdateCounterOffer {
+                    by_amount: Uint128::new(10),
+                    operator: CounterOfferOperator::Add,
+                },
+                &[Coin {
+                    denom: IBC_DENOM_1.into(),
+                    amount: Uint128::new(10),
+                }],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: owner_balance + Uint128::new(10),
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
 
         // Step 6
-        // Claim rewards from the validator
+        // Withdraw the owner's free balance successfully
         // ------------------------------------------------------------------------------
-        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &claim_rewards_msg,
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: owner_balance,
+                    },
+                },
                 &[],
             )
             .unwrap();
-
-        // Step 7
-        // Verify to ensure vault balance is zero and lender's balance contains
-        // expected_claims_after_two_years
-        // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(0)
-            }
-        );
         assert_eq!(
-            lender_balance,
+            bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into()),
             Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_claims_after_two_years
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(900_010)
             }
         );
 
-        // Step 8
-        // Verify that the correct info for the liquidity request
-        // was updated in the option state
-        // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(
-            info.liquidity_request,
-            Some(ActiveOption {
-                lender: Some(Addr::unchecked(LENDER)),
-                state: Some(LiquidityRequestState::FixedInterestRental {
-                    requested_amount: Coin {
-                        denom: IBC_DENOM_1.to_string(),
-                        amount: requested_amount,
-                    },
-                    claimable_tokens,
-                    already_claimed: expected_claims_after_two_years,
-                    can_cast_vote: false,
-                }),
-                msg: option
-            })
-        );
-
-        // Step 9
-        // Fast foward the chain to a future date, where
-        // the rewards has fully covered the claimable_tokens
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365 * 2));
-
-        // Step 10
-        // try claim rewards from the validator
+        // Step 7
+        // Cancel the counter offer and verify the proposer is refunded in full
         // ------------------------------------------------------------------------------
-        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
                 vault_c_addr.clone(),
-                &claim_rewards_msg,
+                &ExecuteMsg::CancelCounterOffer {},
                 &[],
             )
             .unwrap();
-
-        // Step 11
-        // Verify to ensure vault balance for STAKING_DENOM equal
-        // (2 * expected_claims_after_two_years) - claimable_tokens,
-        // and the lender's balance for STAKING_DENOM equal claimable_tokens
-        // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: (expected_claims_after_two_years + expected_claims_after_two_years)
-                    - claimable_tokens
-            }
-        );
         assert_eq!(
-            lender_balance,
+            bank_balance(
+                &mut router,
+                &Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
+                IBC_DENOM_1.into(),
+            ),
             Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: claimable_tokens
+                denom: IBC_DENOM_1.to_string(),
+                amount: Uint128::new(SUPPLY)
             }
         );
 
-        // Step 12
-        // Verify that the option has been finalized
+        // Step 8
+        // Send 1 IBC_DENOM_1 to the vault and verify that the released reservation
+        // no longer blocks the vault owner's withdrawal
         // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(info.liquidity_request, None);
+        router
+            .send_tokens(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(1),
+                }],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: Uint128::new(1),
+                    },
+                },
+                &[],
+            )
+            .unwrap();
     }
 
     #[test]
-    fn test_claim_delegator_rewards_with_fixed_term_rental() {
+    fn test_tokenized_lender_position() {
         // Step 1
-        // Get vault instance
+        // Get vault instance from a factory minting lender positions on collection,
+        // and delegate to VALIDATOR_ONE_ADDRESS
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
-
-        // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
-        // ------------------------------------------------------------------------------
+        let collection = instantiate_collection(&mut router);
+        let factory = instantiate_factory_with_collection(&mut router, Some(collection.clone()));
+        let (vault_c_addr, _from_code_id) =
+            instantiate_vault_with_factory(&mut router, factory, UNBONDING_TIME);
         let delegated_amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount: delegated_amount,
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &delegate_msg,
+                &ExecuteMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: delegated_amount,
+                },
                 &[Coin {
                     denom: STAKING_DENOM.into(),
                     amount: delegated_amount,
@@ -2946,19 +8292,26 @@ mod tests {
             )
             .unwrap();
 
-        // Step 3
-        // Create a valid FixedTermRental liquidity request
+        // Step 2
+        // Create and accept a FixedTermLoan, and verify that the lender position
+        // was minted to LENDER
         // ------------------------------------------------------------------------------
-        let requested_amount = Uint128::new(350_000);
-        let duration_in_seconds = 60 * 60 * 24 * 365 * 3;
-        let expected_rewards_after_duration = Uint128::new(300_000);
-        let option = LiquidityRequestMsg::FixedTermRental {
+        let requested_amount = Uint128::new(300_000);
+        let interest_amount = Uint128::new(30_000);
+        let duration_in_seconds = 60u64;
+        let option = LiquidityRequestMsg::FixedTermLoan {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
-                amount: Uint128::new(350_000),
+                amount: requested_amount,
             },
+            interest_amount,
+            collateral_amount: requested_amount,
             duration_in_seconds,
-            can_cast_vote: false,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -2970,375 +8323,363 @@ mod tests {
                 &[],
             )
             .unwrap();
-
-        // Step 4
-        // Accept the option
-        // ------------------------------------------------------------------------------
         router
             .execute_contract(
                 Addr::unchecked(LENDER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::AcceptLiquidityRequest {
-                    option: option.clone(),
-                },
+                &ExecuteMsg::AcceptLiquidityRequest { option },
                 &[Coin {
                     denom: IBC_DENOM_1.to_string(),
                     amount: requested_amount,
                 }],
             )
             .unwrap();
+        let position = get_vault_info(&mut router, &vault_c_addr)
+            .liquidity_request
+            .unwrap()
+            .lender_position
+            .unwrap();
+        assert_eq!(position.collection, collection);
+        assert_eq!(position.token_id, format!("{}/1", vault_c_addr));
+        assert_eq!(
+            get_token_owner(&mut router, &collection, &position.token_id).unwrap(),
+            Addr::unchecked(LENDER)
+        );
 
-        // Record start time
-        let start_time = router.block_info().time;
-
-        // Step 5
-        // fast foward the chain to a future date, before the end_timme
+        // Step 3
+        // Transfer the lender position token to new_lender,
+        // and verify that new_lender is reported as the lender
         // ------------------------------------------------------------------------------
-        let expected_claims_after_two_years = Uint128::new(200_000);
-        let two_years = 60 * 60 * 24 * 365 * 2;
-        router.update_block(|block| block.time = block.time.plus_seconds(two_years));
+        let new_lender = Addr::unchecked("new_lender");
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                collection.clone(),
+                &MockCollectionExecuteMsg::TransferNft {
+                    recipient: new_lender.to_string(),
+                    token_id: position.token_id.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.liquidity_request.unwrap().lender,
+            Some(new_lender.clone())
+        );
 
-        // Step 6
-        // try claim rewards from the validator
-        // ------------------------------------------------------------------------------
-        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
+        // Step 4
+        // Test error case ContractError::Unauthorized {}
+        // when the previous lender proposes a loan extension.
+        // new_lender can propose one as the holder of the token
+        // ------------------------------------------------------------------------------
+        let propose_msg = ExecuteMsg::ProposeLoanExtension {
+            new_end_time: router
+                .block_info()
+                .time
+                .plus_seconds(duration_in_seconds * 2),
+            additional_interest: Uint128::zero(),
+        };
         router
             .execute_contract(
                 Addr::unchecked(LENDER),
                 vault_c_addr.clone(),
-                &claim_rewards_msg,
+                &propose_msg,
                 &[],
             )
+            .unwrap_err();
+        router
+            .execute_contract(new_lender.clone(), vault_c_addr.clone(), &propose_msg, &[])
             .unwrap();
 
-        // Record last claim time
-        let last_claim_time = router.block_info().time;
-
-        // Step 7
-        // verify to ensure vault balance is zero and lender's balance contains
-        // expected_claims_after_two_years
+        // Step 5
+        // Test error case ContractError::LenderPositionIsTokenized {}
+        // when new_lender transfers the lender position through the vault
         // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(0)
-            }
-        );
-        assert_eq!(
-            lender_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_claims_after_two_years
-            }
-        );
+        router
+            .execute_contract(
+                new_lender.clone(),
+                vault_c_addr.clone(),
+                &ExecuteMsg::TransferLenderPosition {
+                    to_address: LENDER.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
 
-        // Step 8
-        // Verify that the correct info for the liquidity request
-        // was updated in the option state
+        // Step 6
+        // Burn the lender position token outside of the vault, and verify that
+        // the vault keeps new_lender, the last known holder, as the lender
         // ------------------------------------------------------------------------------
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                collection.clone(),
+                &MockCollectionExecuteMsg::Burn {
+                    token_id: position.token_id.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        get_token_owner(&mut router, &collection, &position.token_id).unwrap_err();
         let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(
-            info.liquidity_request,
-            Some(ActiveOption {
-                lender: Some(Addr::unchecked(LENDER)),
-                state: Some(LiquidityRequestState::FixedTermRental {
-                    requested_amount: Coin {
-                        denom: IBC_DENOM_1.to_string(),
-                        amount: requested_amount,
-                    },
-                    start_time,
-                    last_claim_time,
-                    end_time: start_time.plus_seconds(duration_in_seconds),
-                    can_cast_vote: false,
-                }),
-                msg: option
-            })
+            info.liquidity_request.unwrap().lender,
+            Some(new_lender.clone())
         );
 
-        // Step 9
-        // fast foward the chain to a future date greater than the end_time
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(two_years));
-
-        // Step 10
-        // try claim rewards from the validator
+        // Step 7
+        // Messages unrelated to the lender, and those paying the lender, still go through.
+        // Repay the loan, sending the interest and the 0.3% liquidity_comission to the vault
         // ------------------------------------------------------------------------------
-        let claim_rewards_msg = ExecuteMsg::ClaimDelegatorRewards {};
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &claim_rewards_msg,
+                &ExecuteMsg::SetUnbondingStrategy {
+                    strategy: UnbondingStrategy::ProRata,
+                },
                 &[],
             )
             .unwrap();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + Uint128::new(900),
+                }],
+            )
+            .unwrap();
 
-        // Step 11
-        // verify to ensure vault balance is
-        // (2 * expected_claims_after_two_years) - expected_rewards_after_duration,
-        // and the lender's balance now has the expected_rewards_after_duration
+        // Step 8
+        // Verify that the repayment went to new_lender and the option has been finalized
         // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: (expected_claims_after_two_years + expected_claims_after_two_years)
-                    - expected_rewards_after_duration
-            }
-        );
+        let new_lender_balance = bank_balance(&mut router, &new_lender, IBC_DENOM_1.into());
         assert_eq!(
-            lender_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_rewards_after_duration
-            }
+            new_lender_balance.amount,
+            requested_amount + interest_amount
         );
-
-        // Step 12
-        // Verify that the option has been finalized
-        // ------------------------------------------------------------------------------
         let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(info.liquidity_request, None);
     }
 
     #[test]
-    fn test_repay_loan() {
+    fn test_tokenized_ownership() {
         // Step 1
-        // Get vault instance
+        // Get vault instance, and have the factory tokenize its ownership
+        // as token_id of collection, minted to USER
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let collection = instantiate_collection(&mut router);
+        let factory = instantiate_factory_with_collection(&mut router, Some(collection.clone()));
+        let (vault_c_addr, _from_code_id) =
+            instantiate_vault_with_factory(&mut router, factory.clone(), UNBONDING_TIME);
+        let token_id = "1".to_string();
+        router
+            .execute_contract(
+                Addr::unchecked(USER),
+                collection.clone(),
+                &MockCollectionExecuteMsg::Mint {
+                    token_id: token_id.clone(),
+                    owner: USER.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                factory,
+                vault_c_addr.clone(),
+                &ExecuteMsg::TokenizeOwnership {
+                    collection: collection.to_string(),
+                    token_id: token_id.clone(),
+                },
+                &[],
+            )
+            .unwrap();
 
         // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS
+        // Transfer the ownership token to new_owner,
+        // and verify that new_owner is reported as the vault owner
         // ------------------------------------------------------------------------------
-        let delegated_amount = Uint128::new(1_000_000);
-        let delegate_msg = ExecuteMsg::Delegate {
-            validator: VALIDATOR_ONE_ADDRESS.to_string(),
-            amount: delegated_amount,
-        };
+        let new_owner = Addr::unchecked("new_owner");
         router
             .execute_contract(
                 Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &delegate_msg,
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount: delegated_amount,
-                }],
+                collection.clone(),
+                &MockCollectionExecuteMsg::TransferNft {
+                    recipient: new_owner.to_string(),
+                    token_id: token_id.clone(),
+                },
+                &[],
             )
             .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.config.owner, new_owner);
 
         // Step 3
-        // Try to call repay loan with ContractError::Unauthorized {}
-        // because there is no active liquidity request
+        // Test error case ContractError::Unauthorized {}
+        // when the previous owner of the token manages the vault.
+        // new_owner can manage it as the holder of the token
         // ------------------------------------------------------------------------------
-        let repay_loan_msg = ExecuteMsg::RepayLoan {};
+        let set_unbonding_strategy_msg = ExecuteMsg::SetUnbondingStrategy {
+            strategy: UnbondingStrategy::ProRata,
+        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &repay_loan_msg,
+                &set_unbonding_strategy_msg,
                 &[],
             )
             .unwrap_err();
+        router
+            .execute_contract(
+                new_owner.clone(),
+                vault_c_addr.clone(),
+                &set_unbonding_strategy_msg,
+                &[],
+            )
+            .unwrap();
 
         // Step 4
-        // Create a valid FixedTermLoan liquidity request
+        // Burn the ownership token outside of the vault, and verify that the vault
+        // keeps new_owner, the last known holder, as its owner
         // ------------------------------------------------------------------------------
-        let requested_amount = Uint128::new(300_000);
-        let interest_amount = Uint128::new(30_000);
-        let duration_in_seconds = 60u64;
-        let option = LiquidityRequestMsg::FixedTermLoan {
-            requested_amount: Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: requested_amount,
-            },
-            interest_amount,
-            collateral_amount: requested_amount,
-            duration_in_seconds,
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
+                collection.clone(),
+                &MockCollectionExecuteMsg::Burn {
+                    token_id: token_id.clone(),
                 },
                 &[],
             )
             .unwrap();
+        get_token_owner(&mut router, &collection, &token_id).unwrap_err();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.config.owner, new_owner);
 
         // Step 5
-        // Try to call repay loan with ContractError::Unauthorized {}
-        // because there is a liquidity request that has not been accepted yet
+        // Verify that the vault can still be managed by new_owner, and only by new_owner
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                new_owner,
                 vault_c_addr.clone(),
-                &repay_loan_msg,
+                &set_unbonding_strategy_msg,
                 &[],
             )
-            .unwrap_err();
-
-        // Step 6
-        // Accept the open liquidity request
-        // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
+            .unwrap();
         router
             .execute_contract(
-                Addr::unchecked(LENDER),
-                vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount: requested_amount,
-                }],
+                Addr::unchecked(USER),
+                vault_c_addr,
+                &set_unbonding_strategy_msg,
+                &[],
             )
-            .unwrap();
+            .unwrap_err();
+    }
 
-        // Step 7
-        // Try to call repay loan with ContractError::InsufficientBalance {}
-        // because at this point, the vault contract still has the requested_amount
-        // sent to it by the lender when they accept the liquidity request
-        // but does not have the extra interest_amount required
+    #[test]
+    fn test_transfer_ownership() {
+        // Step 1
+        // Get vault instance
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+
+        // Step 2
+        // Test error case  ContractError::Unauthorized {}
+        // When transfer_ownership is called by a user who is not the current
+        // vault owner
         // ------------------------------------------------------------------------------
+        let new_owner = "new_owner".to_string();
+        let transfer_ownership_msg = ExecuteMsg::TransferOwnership {
+            to_address: new_owner.clone(),
+        };
         router
             .execute_contract(
-                Addr::unchecked(USER),
+                Addr::unchecked("fake_owner"),
                 vault_c_addr.clone(),
-                &repay_loan_msg,
+                &transfer_ownership_msg,
                 &[],
             )
             .unwrap_err();
 
-        // Step 8
-        // Try to repay the loan correctly by sending the interest_amount to the contract
-        //
-        // We also include the 0.3% liquidity_comission that was deducted from the requested_amount
-        // and sent to INSTANTIATOR_ADDR when the option was accepted
+        // Step 3
+        // set the new vault owner by the current vault owner
         // ------------------------------------------------------------------------------
-        let liquidity_comission = Uint128::new(900);
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &repay_loan_msg,
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount: interest_amount + liquidity_comission,
-                }],
+                &transfer_ownership_msg,
+                &[],
             )
             .unwrap();
 
-        // Step 9
-        // Verify that the option on the vault is closed,
+        // Step 4
+        // Query the vault info to verify the new owner
         // ------------------------------------------------------------------------------
         let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(info.liquidity_request, None);
-
-        // Step 10
-        // Verify that the vault balance for IBC_DENOM_1 is zero as the balance
-        // has been used to clear the debt
-        //
-        // Also verify that the LENDER balance is back to SUPPLY + interest_amount
-        // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: Uint128::new(0)
-            }
-        );
-
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), IBC_DENOM_1.into());
-        assert_eq!(
-            lender_balance,
-            Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: Uint128::new(SUPPLY) + interest_amount
-            }
-        );
+        assert_eq!(info.config.owner, Addr::unchecked(new_owner));
     }
 
     #[test]
-    fn test_liquidate_collateral() {
+    fn test_migrate() {
         // Step 1
-        // Get vault instance
+        // Get a vault instance with the factory set as admin
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let (vault_c_addr, _) = instantiate_vault(&mut router);
+        let factory = router
+            .wrap()
+            .query_wasm_contract_info(&vault_c_addr)
+            .unwrap()
+            .creator;
+        let factory = Addr::unchecked(factory);
 
         // Step 2
-        // Delegate to VALIDATOR_ONE_ADDRESS & VALIDATOR_TWO_ADDRESS
+        // Delegate to VALIDATOR_ONE_ADDRESS
         // ------------------------------------------------------------------------------
-        let delegated_amount = Uint128::new(1_000_000);
+        let amount = Uint128::new(1_000_000);
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
                 &ExecuteMsg::Delegate {
                     validator: VALIDATOR_ONE_ADDRESS.to_string(),
-                    amount: delegated_amount,
-                },
-                &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount: delegated_amount,
-                }],
-            )
-            .unwrap();
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &ExecuteMsg::Delegate {
-                    validator: VALIDATOR_TWO_ADDRESS.to_string(),
-                    amount: delegated_amount,
+                    amount,
                 },
                 &[Coin {
                     denom: STAKING_DENOM.into(),
-                    amount: delegated_amount,
+                    amount,
                 }],
             )
             .unwrap();
 
         // Step 3
-        // Error liquidating fixed term loan when no option is available
-        // ------------------------------------------------------------------------------
-        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
-            )
-            .unwrap_err();
-
-        // Step 4
-        // Create a valid FixedTermLoan liquidity request
+        // Create and accept a FixedTermLoan liquidity request
         // ------------------------------------------------------------------------------
         let requested_amount = Uint128::new(300_000);
         let interest_amount = Uint128::new(30_000);
-        let one_year_duration = 60 * 60 * 24 * 365; // 1 year;
         let option = LiquidityRequestMsg::FixedTermLoan {
             requested_amount: Coin {
                 denom: IBC_DENOM_1.to_string(),
                 amount: requested_amount,
             },
             interest_amount,
-            collateral_amount: requested_amount,
-            duration_in_seconds: one_year_duration,
+            collateral_amount: amount,
+            duration_in_seconds: 60u64,
+            prorate_interest_on_early_repayment: false,
+            min_interest_amount: None,
+            grace_period_in_seconds: 0,
+            late_fee_per_day: Uint128::zero(),
+            keeper_fee: Uint128::zero(),
         };
         router
             .execute_contract(
@@ -3350,383 +8691,595 @@ mod tests {
                 &[],
             )
             .unwrap();
-
-        // Step 5
-        // Test error case ContractError::Unauthorized {}
-        // because there is a liquidity request that has not been accepted yet
-        // ------------------------------------------------------------------------------
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
-            )
-            .unwrap_err();
-
-        // Step 6
-        // Accept the open liquidity request
-        // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
         router
             .execute_contract(
                 Addr::unchecked(LENDER),
                 vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
+                &ExecuteMsg::AcceptLiquidityRequest { option },
                 &[Coin {
                     denom: IBC_DENOM_1.to_string(),
                     amount: requested_amount,
                 }],
             )
             .unwrap();
+        let info_before_migration = get_vault_info(&mut router, &vault_c_addr);
 
-        // Step 7
-        // Try to call liquidate collateral with ContractError::Unauthorized {}
-        // because there is a fixed term loans that have not yet expired
-        // ------------------------------------------------------------------------------
-        router
-            .execute_contract(
-                Addr::unchecked(LENDER),
-                vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
-            )
-            .unwrap_err();
-
-        // Step 8
-        // Fast foward the time so the option expires without repayment
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(one_year_duration));
-
-        // Step 9
-        // Try to call liquidate collateral with ContractError::Unauthorized {}
-        // when a wrong_user is calling this method
+        // Step 4
+        // Test error case when the caller is not the admin of the vault
         // ------------------------------------------------------------------------------
+        let new_code_id = router.store_code(contract_template());
         router
-            .execute_contract(
-                Addr::unchecked("WRONG_OWNER"),
+            .migrate_contract(
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap_err();
 
-        // Step 10
-        // Begin liquidation of collateral, partial liquidation
+        // Step 5
+        // Migrate the vault to new_code_id
         // ------------------------------------------------------------------------------
         router
-            .execute_contract(
-                Addr::unchecked(LENDER),
+            .migrate_contract(
+                factory.clone(),
                 vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap();
 
-        // Step 11
-        // Verify that accumulated staking rewards and
-        // available staking_demon vault balance was send to lender
+        // Step 6
+        // Verify that from_code_id was updated and the active loan was preserved
         // ------------------------------------------------------------------------------
-        let expected_claims_after_one_years = Uint128::new(200_000);
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(
-            lender_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_claims_after_one_years
+            info.config,
+            Config {
+                owner: Addr::unchecked(USER),
+                from_code_id: new_code_id,
+                index_number: 1u64,
+                factory,
+                unbonding_strategy: UnbondingStrategy::Sequential,
+                unbonding_period: UNBONDING_TIME,
+                ownership_token: None,
             }
         );
+        assert_eq!(
+            info.liquidity_request,
+            info_before_migration.liquidity_request
+        );
 
-        // Step 12
-        // Try to repay loan with ContractError::Unauthorized {}
-        // when liquidation is processing
+        // Step 7
+        // Verify that the migrated vault can still repay the active loan
         // ------------------------------------------------------------------------------
-        let repay_loan_msg = ExecuteMsg::RepayLoan {};
+        let liquidity_comission = Uint128::new(900);
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &repay_loan_msg,
-                &[],
+                &ExecuteMsg::RepayLoan { amount: None },
+                &[Coin {
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: interest_amount + liquidity_comission,
+                }],
+            )
+            .unwrap();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.liquidity_request, None);
+    }
+
+    #[test]
+    fn test_migrate_from_invalid_contract_version() {
+        // Step 1
+        // Get instances of a contract that is not a vault, and a vault on a newer version
+        // ------------------------------------------------------------------------------
+        let mut router = mock_app();
+        let vault_code_id = router.store_code(contract_template());
+        let legacy_code_id = router.store_code(legacy_contract_template());
+        let mut instantiate_legacy_contract = |contract: &str, version: &str| {
+            router
+                .instantiate_contract(
+                    legacy_code_id,
+                    Addr::unchecked(FACTORY),
+                    &LegacyInstantiateMsg {
+                        contract: contract.to_string(),
+                        version: version.to_string(),
+                        config: None,
+                        liquidity_request: None,
+                        counter_offers: vec![],
+                    },
+                    &[],
+                    "legacy",
+                    Some(FACTORY.to_string()),
+                )
+                .unwrap()
+        };
+        let not_a_vault_c_addr = instantiate_legacy_contract("not_a_vault", "0.1.0");
+        let newer_vault_c_addr = instantiate_legacy_contract(CONTRACT_NAME, "99.0.0");
+
+        // Step 2
+        // Test error case ContractError::InvalidMigrationContract {}
+        // When migrating a contract that is not a vault
+        // ------------------------------------------------------------------------------
+        router
+            .migrate_contract(
+                Addr::unchecked(FACTORY),
+                not_a_vault_c_addr,
+                &MigrateMsg {},
+                vault_code_id,
             )
             .unwrap_err();
 
-        // Step 13
-        // Foward the blockchain ahead and process unbonding queue
+        // Step 3
+        // Test error case ContractError::MigrationDowngradeNotAllowed {}
+        // When migrating a vault to an older version
         // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(10));
         router
-            .sudo(cw_multi_test::SudoMsg::Staking(
-                cw_multi_test::StakingSudo::ProcessQueue {},
-            ))
-            .unwrap();
+            .migrate_contract(
+                Addr::unchecked(FACTORY),
+                newer_vault_c_addr,
+                &MigrateMsg {},
+                vault_code_id,
+            )
+            .unwrap_err();
+    }
 
-        // Step 14
-        // Verify that vault balance contains the expected_outstanding_balance
+    // The legacy FixedTermLoan accepted by lender at start_time, as read by the current vault
+    fn migrated_fixed_term_loan(lender: &str, start_time: Timestamp) -> ActiveOption {
+        let requested_amount = Coin {
+            denom: IBC_DENOM_1.to_string(),
+            amount: Uint128::new(300_000),
+        };
+        ActiveOption {
+            lender: Some(Addr::unchecked(lender)),
+            msg: LiquidityRequestMsg::FixedTermLoan {
+                requested_amount: requested_amount.clone(),
+                interest_amount: Uint128::new(30_000),
+                collateral_amount: Uint128::new(1_000_000),
+                duration_in_seconds: 60u64,
+                prorate_interest_on_early_repayment: false,
+                min_interest_amount: None,
+                grace_period_in_seconds: 0,
+                late_fee_per_day: Uint128::zero(),
+                keeper_fee: Uint128::zero(),
+            },
+            state: Some(LiquidityRequestState::FixedTermLoan {
+                requested_amount,
+                interest_amount: Uint128::new(30_000),
+                collateral_amount: Uint128::new(1_000_000),
+                start_time,
+                end_time: start_time.plus_seconds(60),
+                last_liquidation_date: None,
+                already_claimed: Uint128::zero(),
+                already_repaid: Uint128::zero(),
+                processing_liquidation: false,
+                pending_extension: None,
+                keeper_fee_paid: Uint128::zero(),
+            }),
+            liquidity_commission: None,
+            lender_position: None,
+            protocol_params: None,
+        }
+    }
+
+    #[test]
+    fn test_migrate_from_v0_2_0() {
+        // Step 1
+        // Get an instance of a vault on v0.2.0, with a FixedTermLoan accepted by LENDER
+        // stored in the layout of v0.2.0
         // ------------------------------------------------------------------------------
-        let expected_outstanding_balance = Uint128::new(100_000);
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_outstanding_balance
-            }
-        );
+        let mut router = mock_app();
+        let legacy_code_id = router.store_code(legacy_contract_template());
+        let start_time = router.block_info().time;
+        let vault_c_addr = router
+            .instantiate_contract(
+                legacy_code_id,
+                Addr::unchecked(FACTORY),
+                &LegacyInstantiateMsg {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: "0.2.0".to_string(),
+                    config: Some(legacy_config(legacy_code_id)),
+                    liquidity_request: Some(legacy_fixed_term_loan(
+                        Some(LENDER),
+                        Some(legacy_fixed_term_loan_state(
+                            start_time,
+                            false,
+                            Uint128::zero(),
+                            None,
+                        )),
+                    )),
+                    counter_offers: vec![],
+                },
+                &[],
+                "vault",
+                Some(FACTORY.to_string()),
+            )
+            .unwrap();
 
-        // Step 15
-        // Complete liquidation of collateral
+        // Step 2
+        // Migrate the vault to the latest vault code in a single migration
         // ------------------------------------------------------------------------------
+        let new_code_id = router.store_code(contract_template());
         router
-            .execute_contract(
-                Addr::unchecked(USER),
+            .migrate_contract(
+                Addr::unchecked(FACTORY),
                 vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap();
 
-        // Step 16
-        // Verify that outstanding balance was send to lender
+        // Step 3
+        // Verify that the creator of the vault is recorded as its factory,
+        // and the accepted option is kept as is
         // ------------------------------------------------------------------------------
-        let lender_balance =
-            bank_balance(&mut router, &Addr::unchecked(LENDER), STAKING_DENOM.into());
+        let info = get_vault_info(&mut router, &vault_c_addr);
         assert_eq!(
-            lender_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: expected_claims_after_one_years + expected_outstanding_balance
+            info.config,
+            Config {
+                owner: Addr::unchecked(USER),
+                from_code_id: new_code_id,
+                index_number: 1u64,
+                factory: Addr::unchecked(FACTORY),
+                unbonding_strategy: UnbondingStrategy::Sequential,
+                unbonding_period: UNBONDING_PERIOD,
+                ownership_token: None,
             }
         );
+        assert_eq!(
+            info.liquidity_request,
+            Some(migrated_fixed_term_loan(LENDER, start_time))
+        );
 
-        // Step 17
-        // Verify that the option has been finalized
+        // Step 4
+        // Verify that LENDER is recorded as having funded the whole requested_amount
         // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(info.liquidity_request, None);
+        let shares = get_lender_shares(&mut router, &vault_c_addr);
+        assert_eq!(shares.total_funded.amount, Uint128::new(300_000));
+        assert_eq!(shares.shares.len(), 1);
+        assert_eq!(shares.shares[0].lender, Addr::unchecked(LENDER));
     }
 
     #[test]
-    fn test_withdraw_balance() {
+    fn test_migrate_counter_offers_from_v0_2_0() {
         // Step 1
-        // Get vault instance
+        // Get an instance of a vault on v0.2.0 with a pending FixedTermLoan and three
+        // counter offers, holding less than the total escrowed by their proposers
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
-
-        // Step 2
-        // Delegate 600_000 STAKING_DENOM to VALIDATOR_ONE_ADDRESS
-        // Leave 1_000_000 STAKING_DENOM as balance
-        // ------------------------------------------------------------------------------
-        let total_sent_to_vult = Uint128::new(1_600_000);
-        let delegated_amount = Uint128::new(600_000);
+        let legacy_code_id = router.store_code(legacy_contract_template());
+        let vault_c_addr = router
+            .instantiate_contract(
+                legacy_code_id,
+                Addr::unchecked(FACTORY),
+                &LegacyInstantiateMsg {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: "0.2.0".to_string(),
+                    config: Some(legacy_config(legacy_code_id)),
+                    liquidity_request: Some(legacy_fixed_term_loan(None, None)),
+                    counter_offers: vec![
+                        CounterOfferProposal {
+                            proposer: Addr::unchecked(COUNTER_OFFER_PROPOSERS[0]),
+                            amount: Uint128::new(200_000),
+                        },
+                        CounterOfferProposal {
+                            proposer: Addr::unchecked(COUNTER_OFFER_PROPOSERS[1]),
+                            amount: Uint128::new(150_000),
+                        },
+                        CounterOfferProposal {
+                            proposer: Addr::unchecked(COUNTER_OFFER_PROPOSERS[2]),
+                            amount: Uint128::new(50_000),
+                        },
+                    ],
+                },
+                &[],
+                "vault",
+                Some(FACTORY.to_string()),
+            )
+            .unwrap();
         router
-            .execute_contract(
+            .send_tokens(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::Delegate {
-                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
-                    amount: delegated_amount,
-                },
                 &[Coin {
-                    denom: STAKING_DENOM.into(),
-                    amount: total_sent_to_vult,
+                    denom: IBC_DENOM_1.to_string(),
+                    amount: Uint128::new(260_000),
                 }],
             )
             .unwrap();
 
-        // Step 3
-        // Withdraw 100_000 STAKING_DENOM leaving 900_000 balance
+        // Step 2
+        // Migrate the vault to the latest vault code
         // ------------------------------------------------------------------------------
-        let withdraw_balance_msg = ExecuteMsg::WithdrawBalance {
-            to_address: None,
-            funds: Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(100_000),
-            },
-        };
+        let new_code_id = router.store_code(contract_template());
         router
-            .execute_contract(
-                Addr::unchecked(USER),
+            .migrate_contract(
+                Addr::unchecked(FACTORY),
                 vault_c_addr.clone(),
-                &withdraw_balance_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap();
 
+        // Step 3
+        // Verify that the counter offer the vault can not cover anymore is dropped
+        // ------------------------------------------------------------------------------
+        let counter_offers = get_all_counter_offers(&mut router, &vault_c_addr);
+        assert_eq!(counter_offers.data.len(), 2);
+        assert!(!counter_offers
+            .data
+            .iter()
+            .any(|proposal| proposal.proposer == Addr::unchecked(COUNTER_OFFER_PROPOSERS[1])));
+
         // Step 4
-        // Create a fixed term loan liquidity using 600_000 STAKING_DENOM as collateral
+        // Test error case ContractError::InsufficientWithdrawableBalance {}
+        // When the vault owner tries to withdraw into the reserved counter offers
         // ------------------------------------------------------------------------------
-        let requested_amount = Uint128::new(300_000);
-        let collateral_amount = Uint128::new(600_000);
-        let one_year_duration = 60 * 60 * 24 * 365; // 1 year;
-        let option = LiquidityRequestMsg::FixedTermLoan {
-            requested_amount: Coin {
-                denom: IBC_DENOM_1.to_string(),
-                amount: requested_amount,
-            },
-            interest_amount: Uint128::new(30_000),
-            collateral_amount,
-            duration_in_seconds: one_year_duration,
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &ExecuteMsg::RequestLiquidity {
-                    option: option.clone(),
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: Uint128::new(10_001),
+                    },
                 },
                 &[],
             )
-            .unwrap();
+            .unwrap_err();
 
         // Step 5
-        // Withdraw 100_000 STAKING_DENOM leaving 800_000 balance
+        // Verify that only the balance left after the 250_000 reserved is withdrawable
         // ------------------------------------------------------------------------------
-        let withdraw_balance_msg = ExecuteMsg::WithdrawBalance {
-            to_address: None,
-            funds: Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(100_000),
-            },
-        };
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &withdraw_balance_msg,
+                &ExecuteMsg::WithdrawBalance {
+                    to_address: None,
+                    funds: Coin {
+                        denom: IBC_DENOM_1.to_string(),
+                        amount: Uint128::new(10_000),
+                    },
+                },
                 &[],
             )
             .unwrap();
+        assert_eq!(
+            bank_balance(&mut router, &vault_c_addr, IBC_DENOM_1.to_string()).amount,
+            Uint128::new(250_000)
+        );
+    }
 
-        // Step 6
-        // Accept the fixed term loan
+    #[test]
+    fn test_migrate_from_v0_3_0() {
+        // Step 1
+        // Get an instance of a vault on v0.3.0, with a FixedTermLoan accepted by LENDER
+        // stored in the layout of v0.3.0, before factory and lender shares were recorded
         // ------------------------------------------------------------------------------
-        let accept_liquidity_request_msg = ExecuteMsg::AcceptLiquidityRequest { option };
-        router
-            .execute_contract(
-                Addr::unchecked(LENDER),
-                vault_c_addr.clone(),
-                &accept_liquidity_request_msg,
-                &[Coin {
-                    denom: IBC_DENOM_1.to_string(),
-                    amount: requested_amount,
-                }],
+        let mut router = mock_app();
+        let legacy_code_id = router.store_code(legacy_contract_template());
+        let start_time = router.block_info().time;
+        let vault_c_addr = router
+            .instantiate_contract(
+                legacy_code_id,
+                Addr::unchecked(FACTORY),
+                &LegacyInstantiateMsg {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: "0.3.0".to_string(),
+                    config: Some(legacy_config(legacy_code_id)),
+                    liquidity_request: Some(legacy_fixed_term_loan(
+                        Some(LENDER),
+                        Some(legacy_fixed_term_loan_state(
+                            start_time,
+                            false,
+                            Uint128::zero(),
+                            None,
+                        )),
+                    )),
+                    counter_offers: vec![],
+                },
+                &[],
+                "vault",
+                Some(FACTORY.to_string()),
             )
             .unwrap();
 
-        // Step 7
-        // Withdraw 100_000 STAKING_DENOM leaving 700_000 balance
-        // Withdrawal still possible because the fixed term loan has not expired yet
+        // Step 2
+        // Migrate the vault to the latest vault code
         // ------------------------------------------------------------------------------
+        let new_code_id = router.store_code(contract_template());
         router
-            .execute_contract(
-                Addr::unchecked(USER),
+            .migrate_contract(
+                Addr::unchecked(FACTORY),
                 vault_c_addr.clone(),
-                &withdraw_balance_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap();
 
-        // Step 8
-        // Expire the fixed term loan without repayment
-        // ------------------------------------------------------------------------------
-        router.update_block(|block| block.time = block.time.plus_seconds(one_year_duration));
-
-        // Step 8
-        // Try to withdraw some of the balance with ContractError::ClearOutstandingDebt {}
+        // Step 3
+        // Verify that the creator of the vault is recorded as its factory,
+        // and the accepted option is kept as is
         // ------------------------------------------------------------------------------
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &withdraw_balance_msg,
-                &[],
-            )
-            .unwrap_err();
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(
+            info.config,
+            Config {
+                owner: Addr::unchecked(USER),
+                from_code_id: new_code_id,
+                index_number: 1u64,
+                factory: Addr::unchecked(FACTORY),
+                unbonding_strategy: UnbondingStrategy::Sequential,
+                unbonding_period: UNBONDING_PERIOD,
+                ownership_token: None,
+            }
+        );
+        assert_eq!(
+            info.liquidity_request,
+            Some(migrated_fixed_term_loan(LENDER, start_time))
+        );
 
-        // Step 9
-        // Liquidate collateral to clear the debt
+        // Step 4
+        // Verify that LENDER is recorded as having funded the whole requested_amount
         // ------------------------------------------------------------------------------
-        let liquidation_msg = ExecuteMsg::LiquidateCollateral {};
-        router
-            .execute_contract(
-                Addr::unchecked(USER),
-                vault_c_addr.clone(),
-                &liquidation_msg,
-                &[],
-            )
-            .unwrap();
+        let shares = get_lender_shares(&mut router, &vault_c_addr);
+        assert_eq!(shares.total_funded.amount, Uint128::new(300_000));
+        assert_eq!(shares.shares.len(), 1);
+        assert_eq!(shares.shares[0].lender, Addr::unchecked(LENDER));
 
-        // Step 10
-        // Withdraw the remaining 100_000 STAKING_DENOM from the vault
+        // Step 5
+        // Verify that the migrated vault can still be managed by USER
         // ------------------------------------------------------------------------------
         router
             .execute_contract(
                 Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &withdraw_balance_msg,
+                &ExecuteMsg::SetUnbondingStrategy {
+                    strategy: UnbondingStrategy::ProRata,
+                },
                 &[],
             )
             .unwrap();
-
-        // Step 11
-        // Verify that the vault balance is 60_000, which is the accumulated
-        // staking rewards claimed during liquidation
-        // ------------------------------------------------------------------------------
-        let vault_balance = bank_balance(&mut router, &vault_c_addr, STAKING_DENOM.into());
-        assert_eq!(
-            vault_balance,
-            Coin {
-                denom: STAKING_DENOM.to_string(),
-                amount: Uint128::new(60_000)
-            }
-        );
+        let info = get_vault_info(&mut router, &vault_c_addr);
+        assert_eq!(info.config.unbonding_strategy, UnbondingStrategy::ProRata);
     }
 
     #[test]
-    fn test_transfer_ownership() {
+    fn test_migrate_from_v0_2_0_during_liquidation() {
         // Step 1
-        // Get vault instance
+        // Get an instance of a vault on v0.2.0, instantiated by the mock factory,
+        // with a FixedTermLoan accepted by LENDER whose liquidation was started
         // ------------------------------------------------------------------------------
         let mut router = mock_app();
-        let (vault_c_addr, _from_code_id) = instantiate_vault(&mut router);
+        let factory_addr = instantiate_factory(&mut router);
+        let legacy_code_id = router.store_code(legacy_contract_template());
+        let start_time = router.block_info().time;
+        router.update_block(|block| block.time = block.time.plus_seconds(61));
+        let last_liquidation_date = router.block_info().time;
+        let vault_c_addr = router
+            .instantiate_contract(
+                legacy_code_id,
+                factory_addr.clone(),
+                &LegacyInstantiateMsg {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: "0.2.0".to_string(),
+                    config: Some(legacy_config(legacy_code_id)),
+                    liquidity_request: Some(legacy_fixed_term_loan(
+                        Some(LENDER),
+                        Some(legacy_fixed_term_loan_state(
+                            start_time,
+                            true,
+                            Uint128::zero(),
+                            Some(last_liquidation_date),
+                        )),
+                    )),
+                    counter_offers: vec![],
+                },
+                &[],
+                "vault",
+                Some(factory_addr.to_string()),
+            )
+            .unwrap();
 
         // Step 2
-        // Test error case  ContractError::Unauthorized {}
-        // When transfer_ownership is called by a user who is not the current
-        // vault owner
+        // Delegate 1_200_000 from the vault, and undelegate the 1_000_000 collateral
+        // as the liquidation of v0.2.0 did, without recording it
         // ------------------------------------------------------------------------------
-        let new_owner = "new_owner".to_string();
-        let transfer_ownership_msg = ExecuteMsg::TransferOwnership {
-            to_address: new_owner.clone(),
-        };
         router
-            .execute_contract(
-                Addr::unchecked("fake_owner"),
+            .send_tokens(
+                Addr::unchecked(USER),
                 vault_c_addr.clone(),
-                &transfer_ownership_msg,
-                &[],
+                &[Coin {
+                    denom: STAKING_DENOM.to_string(),
+                    amount: Uint128::new(1_200_000),
+                }],
             )
-            .unwrap_err();
+            .unwrap();
+        router
+            .execute(
+                vault_c_addr.clone(),
+                StakingMsg::Delegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: Coin {
+                        denom: STAKING_DENOM.to_string(),
+                        amount: Uint128::new(1_200_000),
+                    },
+                }
+                .into(),
+            )
+            .unwrap();
+        router
+            .execute(
+                vault_c_addr.clone(),
+                StakingMsg::Undelegate {
+                    validator: VALIDATOR_ONE_ADDRESS.to_string(),
+                    amount: Coin {
+                        denom: STAKING_DENOM.to_string(),
+                        amount: Uint128::new(1_000_000),
+                    },
+                }
+                .into(),
+            )
+            .unwrap();
 
         // Step 3
-        // set the new vault owner by the current vault owner
+        // Migrate the vault to the latest vault code
         // ------------------------------------------------------------------------------
+        let new_code_id = router.store_code(contract_template());
         router
-            .execute_contract(
-                Addr::unchecked(USER),
+            .migrate_contract(
+                factory_addr,
                 vault_c_addr.clone(),
-                &transfer_ownership_msg,
-                &[],
+                &MigrateMsg {},
+                new_code_id,
             )
             .unwrap();
 
         // Step 4
-        // Query the vault info to verify the new owner
+        // Verify that the collateral left to claim is recorded as pending,
+        // maturing one unbonding_period after the last liquidation
         // ------------------------------------------------------------------------------
-        let info = get_vault_info(&mut router, &vault_c_addr);
-        assert_eq!(info.config.owner, Addr::unchecked(new_owner));
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::PendingUnbondings {})
+            .unwrap();
+        assert_eq!(
+            pending_unbondings.unbondings,
+            vec![Unbonding {
+                validator: "legacy".to_string(),
+                amount: Coin {
+                    denom: STAKING_DENOM.to_string(),
+                    amount: Uint128::new(1_000_000),
+                },
+                start_time: last_liquidation_date,
+                completion_time: last_liquidation_date.plus_seconds(UNBONDING_PERIOD),
+            }]
+        );
+
+        // Step 5
+        // Liquidate the collateral again, and verify that the vault does not unbond
+        // the remaining delegation while the recorded unbonding is pending
+        // ------------------------------------------------------------------------------
+        router.update_block(|block| block.time = block.time.plus_seconds(60));
+        router
+            .execute_contract(
+                Addr::unchecked(LENDER),
+                vault_c_addr.clone(),
+                &ExecuteMsg::LiquidateCollateral {},
+                &[],
+            )
+            .unwrap();
+        let delegations = get_all_delegations(&mut router, &vault_c_addr);
+        assert_eq!(delegations.data.len(), 1);
+        assert_eq!(delegations.data[0].amount.amount, Uint128::new(200_000));
+        let pending_unbondings: PendingUnbondingsResponse = router
+            .wrap()
+            .query_wasm_smart(&vault_c_addr, &QueryMsg::PendingUnbondings {})
+            .unwrap();
+        assert_eq!(pending_unbondings.unbondings.len(), 1);
     }
 }
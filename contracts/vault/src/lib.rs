@@ -4,6 +4,7 @@ pub mod contract;
 mod error;
 pub mod helpers;
 pub mod integration_test;
+pub mod migrations;
 pub mod msg;
 pub mod state;
 pub mod unit_tests;
@@ -0,0 +1,155 @@
+use crate::{
+    helpers,
+    state::{
+        counter_offer_list, CONFIG, LEGACY_UNBONDINGS_KEY, LENDER_SHARES, LIQUIDITY_REQUEST_STATE,
+        RESERVED_BALANCES, UNBONDINGS, UNBONDING_PERIOD,
+    },
+    types::{ActiveOption, Config, LiquidityRequestState, Unbonding, UnbondingStrategy},
+};
+use cosmwasm_std::{
+    Addr, Coin, ContractInfoResponse, DepsMut, Env, Order, StdError, StdResult, Uint128,
+};
+use cw_storage_plus::Item;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+// Config layout used by vaults prior to v0.4.0
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct ConfigV0_3 {
+    owner: Addr,
+    from_code_id: u64,
+    index_number: u64,
+}
+
+const CONFIG_V0_3: Item<ConfigV0_3> = Item::new("CONFIG");
+
+pub fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|err: semver::Error| StdError::generic_err(err.to_string()))
+}
+
+pub fn migrate_state(
+    deps: &mut DepsMut,
+    env: &Env,
+    stored_version: &Version,
+    contract_info: &ContractInfoResponse,
+) -> StdResult<()> {
+    if *stored_version < Version::new(0, 3, 0) {
+        migrate_to_v0_3_0(deps, env)?;
+    }
+    if *stored_version < Version::new(0, 4, 0) {
+        migrate_to_v0_4_0(deps, env, contract_info)?;
+    }
+
+    Ok(())
+}
+
+// v0.3.0 introduced RESERVED_BALANCES for funds escrowed by counter offer proposers
+fn migrate_to_v0_3_0(deps: &mut DepsMut, env: &Env) -> StdResult<()> {
+    // Rebuild the reserved balance ledger from the counter offers on record
+    RESERVED_BALANCES.clear(deps.storage);
+    let option = match LIQUIDITY_REQUEST_STATE.load(deps.storage)? {
+        Some(option) => option,
+        None => return Ok(()),
+    };
+    let denom = helpers::get_requested_amount(option.msg).denom;
+    let counter_offers = counter_offer_list()
+        .idx
+        .amount
+        .range(deps.storage, None, None, Order::Descending)
+        .map(|res| res.map(|(_, proposal)| proposal))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Only reserve the escrowed funds the vault still holds, highest offers first.
+    // Offers whose funds already left the vault can never be refunded, so they are dropped.
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+    let mut reserved = Uint128::zero();
+    for proposal in counter_offers {
+        if reserved + proposal.amount > balance {
+            counter_offer_list().remove(deps.storage, proposal.proposer)?;
+            continue;
+        }
+
+        helpers::reserve_balance(deps.storage, &denom, proposal.amount)?;
+        reserved += proposal.amount;
+    }
+
+    Ok(())
+}
+
+// v0.4.0 records the factory in CONFIG instead of a hardcoded address,
+// the amount funded by each lender in LENDER_SHARES and the unbondings started in UNBONDINGS
+fn migrate_to_v0_4_0(
+    deps: &mut DepsMut,
+    env: &Env,
+    contract_info: &ContractInfoResponse,
+) -> StdResult<()> {
+    // Older vaults were instantiated by sudomod
+    let legacy_config = CONFIG_V0_3.load(deps.storage)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: legacy_config.owner,
+            from_code_id: legacy_config.from_code_id,
+            index_number: legacy_config.index_number,
+            factory: Addr::unchecked(&contract_info.creator),
+            unbonding_strategy: UnbondingStrategy::default(),
+            unbonding_period: UNBONDING_PERIOD,
+            ownership_token: None,
+        },
+    )?;
+
+    // Ensure LIQUIDITY_REQUEST_STATE deserializes into the current layout
+    let liquidity_request = LIQUIDITY_REQUEST_STATE.load(deps.storage)?;
+    LIQUIDITY_REQUEST_STATE.save(deps.storage, &liquidity_request)?;
+
+    // The unbonding entries of the vault can not be queried, so a liquidation in progress
+    // is assumed to have unbonded all the collateral left to claim on its last liquidation.
+    // This keeps the next liquidation from unbonding it again before that would mature.
+    UNBONDINGS.clear(deps.storage);
+    if let Some(ActiveOption {
+        state:
+            Some(LiquidityRequestState::FixedTermLoan {
+                collateral_amount,
+                already_claimed,
+                processing_liquidation: true,
+                last_liquidation_date: Some(last_liquidation_date),
+                ..
+            }),
+        ..
+    }) = &liquidity_request
+    {
+        let completion_time = last_liquidation_date.plus_seconds(UNBONDING_PERIOD);
+        let pending_amount = collateral_amount.saturating_sub(*already_claimed);
+        if completion_time > env.block.time && !pending_amount.is_zero() {
+            let unbonding = Unbonding {
+                validator: LEGACY_UNBONDINGS_KEY.to_string(),
+                amount: Coin {
+                    denom: deps.querier.query_bonded_denom()?,
+                    amount: pending_amount,
+                },
+                start_time: *last_liquidation_date,
+                completion_time,
+            };
+            UNBONDINGS.save(deps.storage, LEGACY_UNBONDINGS_KEY, &vec![unbonding])?;
+        }
+    }
+
+    // Options accepted by older vaults were funded in full by a single lender
+    LENDER_SHARES.clear(deps.storage);
+    if let Some(ActiveOption {
+        lender: Some(lender),
+        msg,
+        ..
+    }) = liquidity_request
+    {
+        let requested_amount = helpers::get_requested_amount(msg).amount;
+        LENDER_SHARES.save(deps.storage, &lender, &requested_amount)?;
+    }
+
+    Ok(())
+}
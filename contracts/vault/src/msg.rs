@@ -1,7 +1,8 @@
 use crate::types::{
-    ActiveOption, Config, CounterOfferOperator, CounterOfferProposal, LiquidityRequestMsg,
+    ActiveOption, Config, CounterOfferOperator, CounterOfferProposal, LenderShare,
+    LiquidityRequestMsg, ProtocolParams, Unbonding, UnbondingStrategy,
 };
-use cosmwasm_std::{Coin, Delegation, Uint128, VoteOption};
+use cosmwasm_std::{Addr, Coin, Decimal, Delegation, Timestamp, Uint128, VoteOption};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,10 @@ pub struct InstantiateMsg {
     /// Assigned as the owner of the vault instance
     pub owner_address: String,
 
+    /// The factory (sudomod) instantiating this vault, which collects its commissions
+    /// and sets its protocol params. It must instantiate the vault as its wasm admin.
+    pub factory_address: String,
+
     /// from_code_id allows us to easily tell the code_id this vault
     /// was instantiated from.
     /// This is useful when we want to check if the vault is outdated
@@ -18,8 +23,15 @@ pub struct InstantiateMsg {
 
     // This is the index number of the current vault
     pub index_number: u64,
+
+    /// Unbonding period of the staking module in seconds,
+    /// used to track when the unbondings started by the vault mature
+    pub unbonding_period: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -74,21 +86,66 @@ pub enum ExecuteMsg {
     /// before the offer is accepted by lenders.
     ClosePendingLiquidityRequest {},
 
-    /// Allows a lender to accept the pending liquidity request.
+    /// Allows a lender to accept the pending liquidity request,
+    /// funding the part of the requested_amount not yet contributed by other lenders.
     AcceptLiquidityRequest {
         option: LiquidityRequestMsg,
     },
 
+    /// Allows a lender to contribute amount toward the requested_amount
+    /// of the pending liquidity request, which starts once it is fully funded.
+    FundLiquidityRequest {
+        option: LiquidityRequestMsg,
+        amount: Uint128,
+    },
+
+    /// Allows a lender to withdraw their contribution
+    /// before the pending liquidity request is fully funded.
+    WithdrawContribution {},
+
     // Allows the vault owner/lender to claim delegator rewards
     ClaimDelegatorRewards {},
 
     /// Allows the vault owner to repay the amount borrowed from the lender
-    /// before a liquidation event is trigged by the lender
-    RepayLoan {},
+    /// before a liquidation event is trigged by the lender.
+    /// Repays the outstanding requested_amount + interest_amount when amount is None,
+    /// otherwise forwards amount to the lender as a partial repayment.
+    /// Installment loans repay the next installment when amount is None,
+    /// otherwise amount must cover a whole number of installments.
+    RepayLoan {
+        amount: Option<Uint128>,
+    },
+
+    /// Allows the vault owner/lender to propose extending the active fixed term loan
+    /// to new_end_time in exchange for additional_interest, until the grace period expires.
+    /// Replaces any extension pending on the loan.
+    ProposeLoanExtension {
+        new_end_time: Timestamp,
+        additional_interest: Uint128,
+    },
+
+    /// Allows the counterparty of a pending loan extension to accept it,
+    /// updating the end_time and interest_amount of the active fixed term loan
+    AcceptLoanExtension {},
+
+    /// Allows the lender to call a margin on the active LtvLoan
+    /// when its loan-to-value ratio is above margin_call_ltv
+    MarginCall {},
+
+    /// Allows the vault owner to pledge amount more of the tokens staked in the vault
+    /// as collateral for the active LtvLoan, clearing the margin call once the loan is healthy
+    TopUpCollateral {
+        amount: Uint128,
+    },
 
     /// Allows the vault owner/lender to liquidate collateral
     /// which may include unstaking the outstanding amount owed to the lender.
     /// after all free balance is spent.
+    /// Installment loans can be liquidated for the slice of collateral owed
+    /// on every missed installment.
+    /// LtvLoans can be liquidated early once a margin call expires unresolved.
+    /// Anyone can drive the liquidation of a past due loan, and third parties
+    /// are paid the keeper_fee in the loan terms from the recovered collateral.
     LiquidateCollateral {},
 
     /// Allows vault owner/lender to cast a simple vote
@@ -99,10 +156,25 @@ pub enum ExecuteMsg {
 
     /// Allows owner_address to transfer ownership to another owner's address
     /// Note: To burn this contract account, set to_address = env.contract.address
+    /// Vaults owned through a token minted by sudomod are transferred through the collection instead.
     TransferOwnership {
         to_address: String,
     },
 
+    /// Called by the factory (sudomod) once it has minted token_id of collection
+    /// to the vault owner. From then on, whoever owns the token owns the vault.
+    TokenizeOwnership {
+        collection: String,
+        token_id: String,
+    },
+
+    /// Allows the lender to transfer their position on the active option to_address,
+    /// which receives every future reward claim, repayment and liquidation proceed.
+    /// Positions minted as a token by sudomod are transferred through the collection instead.
+    TransferLenderPosition {
+        to_address: String,
+    },
+
     /// Allows the vault owner to withdraw funds from the vault.
     /// While liquidation is processing, the lender's withdrawal
     /// is prioritized over the vault's owner.
@@ -110,6 +182,81 @@ pub enum ExecuteMsg {
         to_address: Option<String>,
         funds: Coin,
     },
+
+    /// Allows the vault owner to choose how collateral is unbonded
+    /// from validators during liquidation
+    SetUnbondingStrategy {
+        strategy: UnbondingStrategy,
+    },
+}
+
+/// Hooks sent by the vault to the sudomod contract that minted it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudomodQueryMsg {
+    /// Returns SudomodConfigResponse
+    Info {},
+
+    /// Returns SudomodParamsResponse
+    Params {},
+}
+
+/// sudomod's ProtocolParams, snapshotted on accepted options
+pub type SudomodParamsResponse = ProtocolParams;
+
+/// The fields of sudomod's Config read by the vault
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SudomodConfigResponse {
+    pub liquidity_commission: Decimal,
+    pub fee_collector: Addr,
+    #[serde(default)]
+    pub lender_position_collection: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudomodHookMsg {
+    /// Keeps the owner index on sudomod in sync after TransferOwnership
+    VaultOwnerChanged { new_owner: String },
+
+    /// Mints token_id of the lender position collection to lender
+    LenderPositionOpened { token_id: String, lender: String },
+
+    /// Burns token_id of collection once the option it represents closes
+    LenderPositionClosed {
+        collection: String,
+        token_id: String,
+    },
+}
+
+/// Queries sent by the vault to the oracle of an LtvLoan
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    /// Returns OraclePriceResponse
+    Price { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePriceResponse {
+    /// Price of one base token in quote tokens
+    pub price: Decimal,
+}
+
+/// Queries sent by the vault to the cw721 collections holding its ownership and lender positions
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721QueryMsg {
+    /// Returns Cw721OwnerOfResponse
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw721OwnerOfResponse {
+    pub owner: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -126,6 +273,26 @@ pub enum QueryMsg {
 
     /// Returns CounterOfferListResponse
     CounterOfferList {},
+
+    /// Returns SimulateRepaymentResponse for the active FixedTermLoan,
+    /// as of the current block time
+    SimulateRepayment {},
+
+    /// Returns NextLiquidationStepResponse for the active loan,
+    /// telling keepers when LiquidateCollateral can make progress
+    NextLiquidationStep {},
+
+    /// Returns LoanHealthResponse for the active LtvLoan,
+    /// valuing the collateral at the oracle price
+    LoanHealth {},
+
+    /// Returns LenderSharesResponse, listing the amount funded by each lender
+    /// of the pending or active liquidity request
+    LenderShares {},
+
+    /// Returns PendingUnbondingsResponse, listing the unbondings
+    /// started by the vault that are yet to mature
+    PendingUnbondings {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -145,6 +312,48 @@ pub struct AllDelegationsResponse {
     pub data: Vec<Delegation>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateRepaymentResponse {
+    pub interest_due: Coin,
+    /// Late fee accrued since end_time during the grace period
+    pub late_fee_due: Coin,
+    pub already_repaid: Coin,
+    /// Amount RepayLoan must forward to the lender to close the option
+    pub amount_due: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NextLiquidationStepResponse {
+    /// Time from which LiquidateCollateral can claim or unbond more collateral
+    pub executable_at: Timestamp,
+    /// Paid from the recovered collateral to third parties calling LiquidateCollateral
+    pub keeper_fee: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LoanHealthResponse {
+    /// collateral_amount valued at the oracle price, in requested_amount.denom
+    pub collateral_value: Coin,
+    /// Outstanding requested_amount + interest_amount
+    pub debt: Coin,
+    /// debt / collateral_value
+    pub ltv: Decimal,
+    /// margin_call_ltv / ltv, the loan can be margin called below one
+    pub health_factor: Decimal,
+    pub margin_call_deadline: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LenderSharesResponse {
+    pub total_funded: Coin,
+    pub shares: Vec<LenderShare>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingUnbondingsResponse {
+    pub unbondings: Vec<Unbonding>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CounterOfferListResponse {
     pub data: Vec<CounterOfferProposal>,
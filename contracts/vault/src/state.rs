@@ -1,16 +1,21 @@
-use crate::types::{ActiveOption, Config, CounterOfferProposal};
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use crate::types::{ActiveOption, Config, CounterOfferProposal, Unbonding};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 // contract info
 pub const CONTRACT_NAME: &str = "vault_contract";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// Only INSTANTIATOR_ADDR can call the InstantiateMsg
-pub const INSTANTIATOR_ADDR: &str = "contract1";
+// Late fees on FixedTermLoan options are charged per started day
+pub const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
 
-// Minimum duration between calls to unbond collateral during liquidation
-pub const STAKE_LIQUIDATION_INTERVAL: u64 = 60 * 60 * 24 * 30;
+// The staking module rejects undelegations once a delegator-validator pair
+// has this many unbonding entries that are yet to mature
+pub const MAX_UNBONDING_ENTRIES: usize = 7;
+
+// Unbonding period of the staking module,
+// for vaults instantiated before it was set at instantiation
+pub const UNBONDING_PERIOD: u64 = 60 * 60 * 24 * 21;
 
 // This stores the config variables during initialization of the contract
 pub const CONFIG: Item<Config> = Item::new("CONFIG");
@@ -19,8 +24,23 @@ pub const CONFIG: Item<Config> = Item::new("CONFIG");
 pub const LIQUIDITY_REQUEST_STATE: Item<Option<ActiveOption>> =
     Item::new("LIQUIDITY_REQUEST_STATE");
 
-// This stores max allowed conter offers
-pub const MAX_COUNTER_OFFERS: usize = 10;
+// This stores the funds held by the vault on behalf of lenders and proposers, per denom.
+// Reserved funds are excluded from the balance available to the vault owner.
+pub const RESERVED_BALANCES: Map<&str, Uint128> = Map::new("RESERVED_BALANCES");
+
+// This stores the amount of requested_amount funded by each lender of the liquidity request.
+// Payouts to lenders are split pro rata to these shares.
+pub const LENDER_SHARES: Map<&Addr, Uint128> = Map::new("LENDER_SHARES");
+
+// Keeps count of the lender position tokens minted for this vault
+pub const LENDER_POSITION_SEQ: Item<u64> = Item::new("LENDER_POSITION_SEQ");
+
+// This stores the unbondings started by the vault that are yet to be pruned, per validator
+pub const UNBONDINGS: Map<&str, Vec<Unbonding>> = Map::new("UNBONDINGS");
+
+// Vaults prior to v0.4.0 did not record their unbondings, so the ones started by their
+// liquidation are recorded under this key instead of the validators they were started from
+pub const LEGACY_UNBONDINGS_KEY: &str = "legacy";
 
 // Define the indexes for counter offers
 pub struct CounterOfferIndexes<'a> {
@@ -1,4 +1,5 @@
-use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use crate::state::UNBONDING_PERIOD;
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,13 +8,85 @@ pub struct Config {
     pub owner: Addr,
     pub from_code_id: u64,
     pub index_number: u64,
+    pub factory: Addr,
+    #[serde(default)]
+    pub unbonding_strategy: UnbondingStrategy,
+    /// Unbonding period of the staking module in seconds, set at instantiation
+    /// and used for every unbonding the vault starts
+    #[serde(default = "default_unbonding_period")]
+    pub unbonding_period: u64,
+    /// Token minted by sudomod to the vault owner.
+    /// Whoever owns it owns the vault, so ownership moves with the token.
+    #[serde(default)]
+    pub ownership_token: Option<OwnershipToken>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipToken {
+    /// cw721 collection minted by sudomod
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+/// Determines how collateral is unbonded from validators during liquidation
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnbondingStrategy {
+    /// Drains validators one by one, in the order delegations are returned
+    #[default]
+    Sequential,
+    /// Unbonds from every validator in proportion to its delegation
+    ProRata,
+    /// Drains the largest delegations first
+    LargestFirst,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ActiveOption {
+    /// Lead lender of the option, acting on behalf of every lender in LENDER_SHARES.
+    /// See helpers::get_lead_lender for how it is selected.
     pub lender: Option<Addr>,
     pub msg: LiquidityRequestMsg,
     pub state: Option<LiquidityRequestState>,
+    #[serde(default)]
+    pub liquidity_commission: Option<LiquidityCommission>,
+    /// Token minted by sudomod for the lender when the option is accepted.
+    /// Whoever owns it acts as the lender, until it is burned as the option closes.
+    #[serde(default)]
+    pub lender_position: Option<LenderPosition>,
+    /// Snapshot of the factory's ProtocolParams when the option was accepted,
+    /// so later changes to them do not alter the terms of the option
+    #[serde(default)]
+    pub protocol_params: Option<ProtocolParams>,
+}
+
+/// sudomod's ProtocolParams, as read by the vault
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProtocolParams {
+    /// Vaults keep the unbonding_period of their Config, set at instantiation
+    #[serde(default = "default_unbonding_period")]
+    pub unbonding_period: u64,
+    pub max_counter_offers: u32,
+    pub min_vault_code_update_interval: u64,
+    pub max_limit: u32,
+    pub default_limit: u32,
+    #[serde(default)]
+    pub max_keeper_fee: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LenderPosition {
+    /// cw721 collection minted by sudomod
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+// Snapshot of the factory's commission terms, taken when the option is accepted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LiquidityCommission {
+    pub rate: Decimal,
+    pub amount: Coin,
+    pub fee_collector: Addr,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -36,6 +109,72 @@ pub enum LiquidityRequestMsg {
         /// Implicitly denominated in bonded_denom
         collateral_amount: Uint128,
         duration_in_seconds: u64,
+        /// Charges interest_amount pro rata to the time elapsed when repaid before end_time
+        #[serde(default)]
+        prorate_interest_on_early_repayment: bool,
+        /// Minimum interest charged on early repayment,
+        /// implicitly denominated in requested_amount.denom
+        #[serde(default)]
+        min_interest_amount: Option<Uint128>,
+        /// Time after end_time during which the owner can still repay before liquidation
+        #[serde(default)]
+        grace_period_in_seconds: u64,
+        /// Charged for every started day of late repayment during the grace period,
+        /// implicitly denominated in requested_amount.denom
+        #[serde(default)]
+        late_fee_per_day: Uint128,
+        /// Paid in bonded_denom from the recovered collateral to third parties
+        /// calling LiquidateCollateral, capped by the factory's max_keeper_fee
+        #[serde(default)]
+        keeper_fee: Uint128,
+    },
+    InstallmentLoan {
+        requested_amount: Coin,
+        /// Implicitly denominated in bonded_denom
+        collateral_amount: Uint128,
+        number_of_installments: u32,
+        installment_period_in_seconds: u64,
+        /// Principal + interest repaid every installment_period_in_seconds,
+        /// implicitly denominated in requested_amount.denom
+        installment_amount: Uint128,
+        /// Paid in bonded_denom from the recovered collateral to third parties
+        /// calling LiquidateCollateral, capped by the factory's max_keeper_fee
+        #[serde(default)]
+        keeper_fee: Uint128,
+    },
+    RewardsBackedLoan {
+        requested_amount: Coin,
+        /// Implicitly denominated in requested_amount.denom
+        interest_amount: Uint128,
+        /// Implicitly denominated in bonded_denom
+        collateral_amount: Uint128,
+        duration_in_seconds: u64,
+        /// Price of one bonded_denom token in requested_amount.denom,
+        /// used to value the staking rewards routed to the lender
+        exchange_rate: Decimal,
+        /// Paid in bonded_denom from the recovered collateral to third parties
+        /// calling LiquidateCollateral, capped by the factory's max_keeper_fee
+        #[serde(default)]
+        keeper_fee: Uint128,
+    },
+    LtvLoan {
+        requested_amount: Coin,
+        /// Implicitly denominated in requested_amount.denom
+        interest_amount: Uint128,
+        /// Implicitly denominated in bonded_denom
+        collateral_amount: Uint128,
+        duration_in_seconds: u64,
+        /// Contract answering OracleQueryMsg::Price for bonded_denom in requested_amount.denom
+        oracle: String,
+        /// Loan-to-value ratio above which the lender can call a margin
+        margin_call_ltv: Decimal,
+        /// Time the owner has to top up collateral or repay after a margin call,
+        /// before the loan can be liquidated early
+        margin_call_period_in_seconds: u64,
+        /// Paid in bonded_denom from the recovered collateral to third parties
+        /// calling LiquidateCollateral, capped by the factory's max_keeper_fee
+        #[serde(default)]
+        keeper_fee: Uint128,
     },
 }
 
@@ -52,6 +191,13 @@ pub struct CounterOfferProposal {
     pub amount: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LenderShare {
+    pub lender: Addr,
+    /// Implicitly denominated in requested_amount.denom
+    pub amount: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum LiquidityRequestState {
     FixedTermRental {
@@ -76,9 +222,100 @@ pub enum LiquidityRequestState {
         processing_liquidation: bool,
         already_claimed: Uint128,
         last_liquidation_date: Option<Timestamp>,
+        /// Total repaid to the lender through RepayLoan,
+        /// implicitly denominated in requested_amount.denom
+        #[serde(default)]
+        already_repaid: Uint128,
+        /// Extension of the loan term awaiting the counterparty's acceptance
+        #[serde(default)]
+        pending_extension: Option<LoanExtensionProposal>,
+        /// Total keeper_fee paid to keepers driving the liquidation, never above keeper_fee
+        #[serde(default)]
+        keeper_fee_paid: Uint128,
+    },
+    InstallmentLoan {
+        requested_amount: Coin,
+        collateral_amount: Uint128,
+        installment_amount: Uint128,
+        number_of_installments: u32,
+        installment_period_in_seconds: u64,
+        start_time: Timestamp,
+        /// Due date of the first installment that is neither paid nor defaulted
+        next_due_date: Timestamp,
+        installments_paid: u32,
+        /// Missed installments whose slice of collateral_amount is owed to the lender
+        installments_defaulted: u32,
+        processing_liquidation: bool,
+        already_claimed: Uint128,
+        last_liquidation_date: Option<Timestamp>,
+        /// Total keeper_fee paid to keepers driving the liquidation, never above keeper_fee
+        #[serde(default)]
+        keeper_fee_paid: Uint128,
+    },
+    RewardsBackedLoan {
+        requested_amount: Coin,
+        interest_amount: Uint128,
+        collateral_amount: Uint128,
+        exchange_rate: Decimal,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        /// Staking rewards routed to the lender, implicitly denominated in bonded_denom
+        rewards_claimed: Uint128,
+        /// Total repaid to the lender through staking rewards valued at exchange_rate
+        /// and RepayLoan, implicitly denominated in requested_amount.denom
+        already_repaid: Uint128,
+        processing_liquidation: bool,
+        already_claimed: Uint128,
+        last_liquidation_date: Option<Timestamp>,
+        /// Total keeper_fee paid to keepers driving the liquidation, never above keeper_fee
+        #[serde(default)]
+        keeper_fee_paid: Uint128,
+    },
+    LtvLoan {
+        requested_amount: Coin,
+        interest_amount: Uint128,
+        /// Increased by the owner through TopUpCollateral
+        collateral_amount: Uint128,
+        oracle: Addr,
+        margin_call_ltv: Decimal,
+        margin_call_period_in_seconds: u64,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        /// Total repaid to the lender through RepayLoan,
+        /// implicitly denominated in requested_amount.denom
+        already_repaid: Uint128,
+        /// Set by a margin call, cleared once the loan is healthy again
+        margin_call_deadline: Option<Timestamp>,
+        processing_liquidation: bool,
+        already_claimed: Uint128,
+        last_liquidation_date: Option<Timestamp>,
+        /// Total keeper_fee paid to keepers driving the liquidation, never above keeper_fee
+        #[serde(default)]
+        keeper_fee_paid: Uint128,
     },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LoanExtensionProposal {
+    pub proposed_by: Addr,
+    pub new_end_time: Timestamp,
+    /// Implicitly denominated in requested_amount.denom
+    pub additional_interest: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unbonding {
+    pub validator: String,
+    pub amount: Coin,
+    pub start_time: Timestamp,
+    /// Expected time the unbonded amount is returned to the vault balance
+    pub completion_time: Timestamp,
+}
+
+fn default_unbonding_period() -> u64 {
+    UNBONDING_PERIOD
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LiquidityRequestStatus {
     Pending,
@@ -98,10 +335,28 @@ pub enum ActionTypes {
     AcceptCounterOffer(LiquidityRequestStatus),
     ClosePendingLiquidityRequest(LiquidityRequestStatus),
     AcceptLiquidityRequest(LiquidityRequestStatus),
+    FundLiquidityRequest(LiquidityRequestStatus),
+    WithdrawContribution(LiquidityRequestStatus),
     ClaimDelegatorRewards,
     LiquidateCollateral(LiquidityRequestStatus),
     RepayLoan(LiquidityRequestStatus),
+    ProposeLoanExtension(LiquidityRequestStatus),
+    AcceptLoanExtension(LiquidityRequestStatus),
+    MarginCall(LiquidityRequestStatus),
+    TopUpCollateral(LiquidityRequestStatus),
     WithdrawBalance,
+    SetUnbondingStrategy,
     TransferOwnership,
+    TransferLenderPosition(LiquidityRequestStatus),
     Vote,
 }
+
+/// Recipients of the collateral recovered by a liquidation step
+pub struct LiquidationPayees {
+    pub lender: Addr,
+    /// Third party calling LiquidateCollateral, paid the keeper_fee before the lender
+    pub keeper: Option<Addr>,
+    pub keeper_fee: Uint128,
+    /// Total keeper_fee paid over the liquidation, updated by claim_collateral
+    pub keeper_fee_paid: Uint128,
+}